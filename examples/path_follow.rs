@@ -143,7 +143,7 @@ fn setup(
     println!();
     println!("Controls:");
     println!("  Space  - Pause/Resume all followers");
-    println!("  R      - Reset all followers");
+    println!("  Home   - Reset all followers");
     println!("  F      - Toggle camera mode");
     println!("================================\n");
 }
@@ -162,8 +162,8 @@ fn handle_input(keyboard: Res<ButtonInput<KeyCode>>, mut followers: Query<&mut S
         }
     }
 
-    // R to reset
-    if keyboard.just_pressed(KeyCode::KeyR) {
+    // Home to reset (R rotates the selected splines in the editor)
+    if keyboard.just_pressed(KeyCode::Home) {
         for mut follower in &mut followers {
             follower.reset();
         }
@@ -188,6 +188,12 @@ fn log_follower_events(mut events: MessageReader<FollowerEvent>, labels: Query<&
             FollowerEventKind::Finished => {
                 println!("[{}] Finished!", label);
             }
+            FollowerEventKind::ReachedWaypoint(index) => {
+                println!("[{}] Reached waypoint {}", label, index);
+            }
+            FollowerEventKind::EnteredSegment(index) => {
+                println!("[{}] Entered path segment {}", label, index);
+            }
         }
     }
 }
@@ -24,7 +24,7 @@ fn main() {
         .add_plugins(SplineEditorPlugin)
         .add_plugins(SplineRoadPlugin)
         .add_plugins(SplineDistributionPlugin)
-        .add_plugins(SurfaceProjectionPlugin)
+        .add_plugins(SplineMeshProjectionPlugin)
         .add_plugins(CameraPlugin)
         .add_systems(Startup, setup)
         .run();
@@ -118,7 +118,7 @@ fn setup(
         MeshMaterial3d(road_material),
         Transform::default(),
         Visibility::default(),
-        SurfaceProjection::new()
+        SplineMeshProjection::new()
             .with_ray_offset(20.0)
             .with_normal_offset(0.15),
     ));
@@ -154,7 +154,7 @@ fn setup(
         MeshMaterial3d(complex_road_material),
         Transform::default(),
         Visibility::default(),
-        SurfaceProjection::new()
+        SplineMeshProjection::new()
             .with_ray_offset(20.0)
             .with_normal_offset(0.15),
     ));
@@ -191,7 +191,7 @@ fn setup(
         MeshMaterial3d(curb_road_material),
         Transform::default(),
         Visibility::default(),
-        SurfaceProjection::new()
+        SplineMeshProjection::new()
             .with_ray_offset(20.0)
             .with_normal_offset(0.12),
     ));
@@ -234,8 +234,9 @@ fn setup(
             orientation: DistributionOrientation::PositionOnly,
             spacing: DistributionSpacing::Uniform,
             offset: Vec3::new(0.0, 0.5, 0.0), // Offset up so post sits on surface
+            ..default()
         },
-        SurfaceProjection::new()
+        SplineMeshProjection::new()
             .with_ray_offset(20.0)
             .with_normal_alignment(true),
     ));
@@ -341,7 +342,7 @@ fn create_road_with_sidewalks(
     // left sidewalk outer -> left sidewalk inner (top) -> left sidewalk inner (bottom/road level)
     // -> road left -> road right
     // -> right sidewalk inner (bottom) -> right sidewalk inner (top) -> right sidewalk outer
-    let profile = vec![
+    let profile = [
         // Left sidewalk - outer edge top
         Vec3::new(-hw, sidewalk_height, 0.0),
         // Left sidewalk - inner edge top
@@ -0,0 +1,207 @@
+//! Named camera viewpoints to jump between.
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::{CameraSettings, FlyCamera, OrbitCamera};
+
+/// Number keys that save (with Ctrl) and recall the bookmarks named after their digit.
+const BOOKMARK_KEYS: [(KeyCode, &str); 10] = [
+    (KeyCode::Digit0, "0"),
+    (KeyCode::Digit1, "1"),
+    (KeyCode::Digit2, "2"),
+    (KeyCode::Digit3, "3"),
+    (KeyCode::Digit4, "4"),
+    (KeyCode::Digit5, "5"),
+    (KeyCode::Digit6, "6"),
+    (KeyCode::Digit7, "7"),
+    (KeyCode::Digit8, "8"),
+    (KeyCode::Digit9, "9"),
+];
+
+/// A saved camera viewpoint.
+#[derive(Debug, Clone)]
+pub struct CameraBookmark {
+    /// The orbit camera when the bookmark was saved.
+    pub orbit: OrbitCamera,
+    /// The camera transform when the bookmark was saved.
+    pub transform: Transform,
+}
+
+impl CameraBookmark {
+    /// Move a camera to this viewpoint.
+    ///
+    /// Only the orbit's `focus`, `radius`, `yaw` and `pitch` are restored, so
+    /// sensitivity and limit changes made since saving are kept. These are the
+    /// orbit camera's targets, so with [`OrbitCamera::smoothing`] enabled the view
+    /// eases over to the bookmark instead of jumping.
+    pub fn apply(&self, orbit: &mut OrbitCamera, transform: &mut Transform) {
+        orbit.focus = self.orbit.focus;
+        orbit.radius = self.orbit.radius;
+        orbit.yaw = self.orbit.yaw;
+        orbit.pitch = self.orbit.pitch;
+        *transform = self.transform;
+    }
+}
+
+/// Named camera viewpoints.
+///
+/// Bookmarks can be saved and recalled from code, or with the number keys when
+/// [`CameraSettings::bookmark_hotkeys`] is enabled: Ctrl+number saves the bookmark
+/// named after the digit, and the number alone recalls it.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CameraBookmarks {
+    bookmarks: HashMap<String, CameraBookmark>,
+}
+
+impl CameraBookmarks {
+    /// Save the camera's current viewpoint under `name`, replacing any bookmark
+    /// with that name.
+    pub fn save_bookmark(
+        &mut self,
+        name: impl Into<String>,
+        orbit: &OrbitCamera,
+        transform: &Transform,
+    ) {
+        self.bookmarks.insert(
+            name.into(),
+            CameraBookmark {
+                orbit: orbit.clone(),
+                transform: *transform,
+            },
+        );
+    }
+
+    /// Move the camera to the bookmark saved under `name`.
+    ///
+    /// Returns `false`, leaving the camera untouched, if there is no such bookmark.
+    /// See [`CameraBookmark::apply`].
+    pub fn goto_bookmark(
+        &self,
+        name: &str,
+        orbit: &mut OrbitCamera,
+        transform: &mut Transform,
+    ) -> bool {
+        let Some(bookmark) = self.bookmarks.get(name) else {
+            return false;
+        };
+        bookmark.apply(orbit, transform);
+        true
+    }
+
+    /// Get the bookmark saved under `name`.
+    pub fn get(&self, name: &str) -> Option<&CameraBookmark> {
+        self.bookmarks.get(name)
+    }
+
+    /// Remove the bookmark saved under `name`, returning it.
+    pub fn remove(&mut self, name: &str) -> Option<CameraBookmark> {
+        self.bookmarks.remove(name)
+    }
+
+    /// Iterate over the bookmarks and their names, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &CameraBookmark)> {
+        self.bookmarks
+            .iter()
+            .map(|(name, bookmark)| (name.as_str(), bookmark))
+    }
+}
+
+/// System to save (Ctrl+number) and recall (number) camera bookmarks, when
+/// [`CameraSettings::bookmark_hotkeys`] is enabled.
+pub fn bookmark_hotkeys(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<CameraSettings>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut cameras: Query<(&mut OrbitCamera, &mut Transform, Option<&mut FlyCamera>)>,
+) {
+    if !settings.bookmark_hotkeys {
+        return;
+    }
+
+    let Some(name) = BOOKMARK_KEYS
+        .iter()
+        .find(|(key, _)| keyboard.just_pressed(*key))
+        .map(|(_, name)| *name)
+    else {
+        return;
+    };
+
+    let Ok((mut orbit, mut transform, fly)) = cameras.single_mut() else {
+        return;
+    };
+
+    if keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]) {
+        bookmarks.save_bookmark(name, &orbit, &transform);
+    } else if bookmarks.goto_bookmark(name, &mut orbit, &mut transform) {
+        // Keep looking the saved way when recalled in fly mode
+        if let Some(mut fly) = fly {
+            let (yaw, pitch, _) = transform.rotation.to_euler(EulerRot::YXZ);
+            fly.yaw = yaw;
+            fly.pitch = pitch;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bookmark_hotkeys_save_and_recall() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<CameraBookmarks>()
+            .insert_resource(CameraSettings {
+                bookmark_hotkeys: true,
+                ..default()
+            })
+            .add_systems(Update, bookmark_hotkeys);
+
+        let saved = OrbitCamera {
+            focus: Vec3::new(1.0, 2.0, 3.0),
+            yaw: 0.5,
+            ..default()
+        };
+        let saved_transform = Transform::from_translation(saved.calculate_position())
+            .looking_at(saved.focus, Vec3::Y);
+        let camera = app
+            .world_mut()
+            .spawn((saved.clone(), saved_transform, FlyCamera::default()))
+            .id();
+        let press = |app: &mut App, keys: &[KeyCode]| {
+            let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.reset_all();
+            for &key in keys {
+                keyboard.press(key);
+            }
+            app.update();
+        };
+
+        press(&mut app, &[KeyCode::ControlLeft, KeyCode::Digit3]);
+        assert!(app.world().resource::<CameraBookmarks>().get("3").is_some());
+
+        // Move away, keeping a changed sensitivity
+        let mut orbit = app.world_mut().get_mut::<OrbitCamera>(camera).unwrap();
+        orbit.focus = Vec3::ZERO;
+        orbit.yaw = -1.0;
+        orbit.sensitivity = 0.01;
+        *app.world_mut().get_mut::<Transform>(camera).unwrap() = Transform::default();
+
+        // Unsaved bookmarks do nothing
+        press(&mut app, &[KeyCode::Digit4]);
+        assert_eq!(app.world().get::<OrbitCamera>(camera).unwrap().yaw, -1.0);
+
+        press(&mut app, &[KeyCode::Digit3]);
+        let orbit = app.world().get::<OrbitCamera>(camera).unwrap();
+        assert_eq!((orbit.focus, orbit.yaw), (saved.focus, saved.yaw));
+        assert_eq!(orbit.sensitivity, 0.01);
+        assert_eq!(
+            *app.world().get::<Transform>(camera).unwrap(),
+            saved_transform
+        );
+        let fly = app.world().get::<FlyCamera>(camera).unwrap();
+        assert!((fly.yaw - saved.yaw).abs() < 1e-4);
+    }
+}
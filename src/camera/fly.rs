@@ -1,6 +1,6 @@
 use bevy::{input::mouse::MouseMotion, prelude::*};
 
-use super::CameraMode;
+use super::{smoothing_blend, CameraMode, CameraModeOverride, GamepadSticks};
 
 /// Component for fly camera behavior.
 #[derive(Component, Debug, Clone)]
@@ -19,6 +19,18 @@ pub struct FlyCamera {
     pub min_pitch: f32,
     /// Maximum pitch (prevents flipping).
     pub max_pitch: f32,
+    /// Optional axis-aligned bounds `(min, max)` the camera position is clamped to.
+    /// The corners are ordered per axis, so swapped values still form a box.
+    /// `None` allows unrestricted movement.
+    pub bounds: Option<(Vec3, Vec3)>,
+    /// Time constant (seconds) for easing the transform toward the position and
+    /// rotation set by input. `0.0` moves the camera instantly.
+    pub smoothing: f32,
+    /// Gamepad look speed (radians per second at full right stick deflection).
+    pub gamepad_look_sensitivity: f32,
+    /// Gamepad movement speed at full left stick or trigger deflection, as a
+    /// multiple of `speed`.
+    pub gamepad_move_sensitivity: f32,
 }
 
 impl Default for FlyCamera {
@@ -31,21 +43,31 @@ impl Default for FlyCamera {
             pitch: 0.0,
             min_pitch: -std::f32::consts::FRAC_PI_2 + 0.1,
             max_pitch: std::f32::consts::FRAC_PI_2 - 0.1,
+            bounds: None,
+            smoothing: 0.0,
+            gamepad_look_sensitivity: 2.0,
+            gamepad_move_sensitivity: 1.0,
         }
     }
 }
 
 /// System to handle fly camera input.
+#[allow(clippy::too_many_arguments)]
 pub fn fly_camera_input(
     mut cameras: Query<(&mut FlyCamera, &mut Transform)>,
     camera_mode: Res<CameraMode>,
+    mode_override: Res<CameraModeOverride>,
     mouse: Res<ButtonInput<MouseButton>>,
     keyboard: Res<ButtonInput<KeyCode>>,
     mut motion: MessageReader<MouseMotion>,
+    gamepads: Query<&Gamepad>,
     time: Res<Time>,
+    mut target_position: Local<Option<Vec3>>,
 ) {
-    if *camera_mode != CameraMode::Fly {
+    if mode_override.resolve(*camera_mode) != CameraMode::Fly {
         motion.clear();
+        // Start from the transform when flying resumes
+        *target_position = None;
         return;
     }
 
@@ -53,6 +75,12 @@ pub fn fly_camera_input(
         return;
     };
 
+    // Without smoothing the transform is the target, so outside changes stick
+    let mut target = match *target_position {
+        Some(target) if fly.smoothing > 0.0 => target,
+        _ => transform.translation,
+    };
+
     // Handle mouse look (right mouse button held)
     if mouse.pressed(MouseButton::Right) {
         for ev in motion.read() {
@@ -64,6 +92,12 @@ pub fn fly_camera_input(
         motion.clear();
     }
 
+    // Right stick looks around, up looking up
+    let sticks = GamepadSticks::read(&gamepads);
+    let look = sticks.right * fly.gamepad_look_sensitivity * time.delta_secs();
+    fly.yaw -= look.x;
+    fly.pitch = (fly.pitch + look.y).clamp(fly.min_pitch, fly.max_pitch);
+
     // Calculate movement direction
     let mut direction = Vec3::ZERO;
 
@@ -95,9 +129,43 @@ pub fn fly_camera_input(
             fly.speed
         };
 
-        transform.translation += direction.normalize() * speed * time.delta_secs();
+        target += direction.normalize() * speed * time.delta_secs();
     }
 
-    // Apply rotation
-    transform.rotation = Quat::from_euler(EulerRot::YXZ, fly.yaw, fly.pitch, 0.0);
+    // Left stick moves along the view, triggers move up and down
+    let stick_direction = *transform.forward() * sticks.left.y
+        + *transform.right() * sticks.left.x
+        + Vec3::Y * sticks.triggers;
+    if stick_direction != Vec3::ZERO {
+        let sprinting = gamepads
+            .iter()
+            .any(|gamepad| gamepad.pressed(GamepadButton::LeftThumb));
+        let speed = if sprinting {
+            fly.speed * fly.sprint_multiplier
+        } else {
+            fly.speed
+        };
+        target += stick_direction.clamp_length_max(1.0)
+            * speed
+            * fly.gamepad_move_sensitivity
+            * time.delta_secs();
+    }
+
+    // Keep the camera inside the play area
+    if let Some((a, b)) = fly.bounds {
+        // Order the corners per axis, as `Vec3::clamp` panics if min > max
+        target = target.clamp(a.min(b), a.max(b));
+    }
+    *target_position = Some(target);
+
+    // Ease toward the target position and rotation
+    let blend = smoothing_blend(fly.smoothing, time.delta_secs());
+    let rotation = Quat::from_euler(EulerRot::YXZ, fly.yaw, fly.pitch, 0.0);
+    if blend >= 1.0 {
+        transform.translation = target;
+        transform.rotation = rotation;
+    } else {
+        transform.translation = transform.translation.lerp(target, blend);
+        transform.rotation = transform.rotation.slerp(rotation, blend);
+    }
 }
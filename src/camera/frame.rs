@@ -0,0 +1,140 @@
+//! Framing splines with the orbit camera.
+
+use bevy::prelude::*;
+
+use crate::spline::{SelectedSpline, Spline};
+
+use super::{CameraMode, CameraModeOverride, CameraSettings, OrbitCamera};
+
+/// Get the orbit focus and radius that fit `points` in view.
+///
+/// `fov` is the vertical field of view and `aspect_ratio` the viewport's width
+/// over its height; the narrower of the two angles decides the fit. Returns
+/// `None` if there are no points.
+pub fn frame_points(
+    points: impl IntoIterator<Item = Vec3>,
+    fov: f32,
+    aspect_ratio: f32,
+) -> Option<(Vec3, f32)> {
+    let mut points = points.into_iter();
+    let first = points.next()?;
+    let (min, max) = points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+
+    let center = (min + max) / 2.0;
+    let bounding_radius = (max - min).length() / 2.0;
+
+    let half_vertical = fov / 2.0;
+    let half_horizontal = (half_vertical.tan() * aspect_ratio).atan();
+    let half_angle = half_vertical.min(half_horizontal);
+
+    // Distance at which the bounding sphere touches the edges of the view
+    Some((center, bounding_radius / half_angle.sin()))
+}
+
+/// System to frame the selected splines, or all splines when nothing is selected,
+/// when [`CameraSettings::frame_key`] is pressed.
+pub fn frame_selection(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<CameraSettings>,
+    camera_mode: Res<CameraMode>,
+    mode_override: Res<CameraModeOverride>,
+    selected: Query<(&Spline, Option<&GlobalTransform>), With<SelectedSpline>>,
+    splines: Query<(&Spline, Option<&GlobalTransform>)>,
+    mut cameras: Query<(&mut OrbitCamera, Option<&Projection>)>,
+) {
+    let Some(key) = settings.frame_key else {
+        return;
+    };
+    if !keyboard.just_pressed(key) || mode_override.resolve(*camera_mode) != CameraMode::Orbit {
+        return;
+    }
+
+    let Ok((mut orbit, projection)) = cameras.single_mut() else {
+        return;
+    };
+
+    let targets = if selected.is_empty() {
+        splines.iter().collect::<Vec<_>>()
+    } else {
+        selected.iter().collect()
+    };
+    let points = targets.into_iter().flat_map(|(spline, transform)| {
+        let transform = transform.copied().unwrap_or_default();
+        spline
+            .control_points
+            .iter()
+            .map(move |&p| transform.transform_point(p))
+    });
+
+    // Orthographic cameras (e.g. snap views) size their view from the orbit radius
+    // as if they were perspective, so fit against the default field of view
+    let perspective = match projection {
+        Some(Projection::Perspective(perspective)) => perspective.clone(),
+        _ => PerspectiveProjection::default(),
+    };
+    let Some((focus, radius)) = frame_points(points, perspective.fov, perspective.aspect_ratio)
+    else {
+        return;
+    };
+
+    orbit.focus = focus;
+    orbit.radius = radius.clamp(orbit.min_radius, orbit.max_radius);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_frame_selection_prefers_selected_splines() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<CameraSettings>()
+            .init_resource::<CameraMode>()
+            .init_resource::<CameraModeOverride>()
+            .add_systems(Update, frame_selection);
+
+        let camera = app.world_mut().spawn(OrbitCamera::default()).id();
+        let near = app
+            .world_mut()
+            .spawn(Spline::new(
+                SplineType::CatmullRom,
+                vec![Vec3::new(-2.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0)],
+            ))
+            .id();
+        app.world_mut().spawn((
+            Spline::new(
+                SplineType::CatmullRom,
+                vec![Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 10.0)],
+            ),
+            GlobalTransform::from_translation(Vec3::new(20.0, 0.0, 0.0)),
+        ));
+
+        let frame = |app: &mut App| {
+            let key = app.world().resource::<CameraSettings>().frame_key.unwrap();
+            let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.clear();
+            keyboard.press(key);
+            app.update();
+            app.world_mut()
+                .resource_mut::<ButtonInput<KeyCode>>()
+                .release(key);
+            app.world().get::<OrbitCamera>(camera).unwrap().clone()
+        };
+
+        // Nothing selected: frame both splines
+        let orbit = frame(&mut app);
+        assert!(orbit.focus.distance(Vec3::new(9.0, 0.0, 5.0)) < 1e-5);
+        let all_radius = orbit.radius;
+
+        app.world_mut().entity_mut(near).insert(SelectedSpline);
+        let orbit = frame(&mut app);
+        assert!(orbit.focus.distance(Vec3::ZERO) < 1e-5);
+        assert!(orbit.radius < all_radius);
+
+        // The fitted sphere stays inside the view cone
+        let fov = PerspectiveProjection::default().fov;
+        assert!(orbit.radius * (fov / 2.0).sin() >= 2.0 - 1e-4);
+    }
+}
@@ -1,8 +1,18 @@
+mod bookmark;
 mod fly;
+mod frame;
 mod orbit;
+mod rail;
+mod snap;
+mod touch;
 
+pub use bookmark::{CameraBookmark, CameraBookmarks};
 pub use fly::FlyCamera;
-pub use orbit::OrbitCamera;
+pub use frame::frame_points;
+pub use orbit::{cursor_pivot, OrbitCamera};
+pub use rail::RailCamera;
+pub use snap::{SnapView, SnappedView};
+pub use touch::pinch_zoom_factor;
 
 use bevy::prelude::*;
 
@@ -34,6 +44,107 @@ impl CameraMode {
     }
 }
 
+/// Temporary camera mode that takes precedence over [`CameraMode`] while set.
+///
+/// Set each frame from [`CameraSettings::hold_to_fly_key`]; [`CameraMode`] itself is
+/// left untouched, so the previous mode resumes when the override clears.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CameraModeOverride(pub Option<CameraMode>);
+
+impl CameraModeOverride {
+    /// Get the mode the camera systems should use, given the persistent mode.
+    pub fn resolve(&self, mode: CameraMode) -> CameraMode {
+        self.0.unwrap_or(mode)
+    }
+}
+
+/// System set of the [`CameraPlugin`] systems that read keyboard, mouse, touch and
+/// gamepad input.
+///
+/// Systems that consume input the camera shouldn't see, like typed text, run
+/// before it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CameraInputSystems;
+
+/// Settings for the camera controls.
+#[derive(Resource, Debug, Clone)]
+pub struct CameraSettings {
+    /// Key that switches to fly mode while held, returning to the previous mode on
+    /// release. `None` disables hold-to-fly.
+    pub hold_to_fly_key: Option<KeyCode>,
+    /// Key that points the orbit camera at the selected splines, or at all splines
+    /// when nothing is selected. `None` disables framing.
+    ///
+    /// Defaults to `.` since `F` already toggles the camera mode.
+    pub frame_key: Option<KeyCode>,
+    /// Whether the numpad keys snap the orbit camera to a [`SnapView`].
+    pub snap_views: bool,
+    /// Whether snapping to a view switches to an orthographic projection.
+    /// Perspective is restored once the camera orbits away.
+    pub snap_orthographic: bool,
+    /// Seconds the camera takes to turn into a snap view. `0.0` jumps instantly.
+    pub snap_duration: f32,
+    /// Whether Ctrl+number saves a [`CameraBookmarks`] entry named after the digit,
+    /// and the number alone recalls it.
+    ///
+    /// Off by default, as the number keys are also used to type positions in the
+    /// spline editor.
+    pub bookmark_hotkeys: bool,
+}
+
+impl Default for CameraSettings {
+    fn default() -> Self {
+        Self {
+            hold_to_fly_key: None,
+            frame_key: Some(KeyCode::Period),
+            snap_views: true,
+            snap_orthographic: true,
+            snap_duration: 0.2,
+            bookmark_hotkeys: false,
+        }
+    }
+}
+
+/// Stick and trigger input summed over all connected gamepads.
+///
+/// Zero when no gamepad is connected, so gamepad handling can always run
+/// alongside mouse and keyboard input.
+#[derive(Debug, Clone, Copy, Default)]
+struct GamepadSticks {
+    left: Vec2,
+    right: Vec2,
+    /// Right trigger minus left trigger, from -1 to 1.
+    triggers: f32,
+}
+
+impl GamepadSticks {
+    fn read(gamepads: &Query<&Gamepad>) -> Self {
+        let mut sticks = Self::default();
+        for gamepad in gamepads {
+            sticks.left += gamepad.left_stick();
+            sticks.right += gamepad.right_stick();
+            sticks.triggers += gamepad.get(GamepadButton::RightTrigger2).unwrap_or(0.0)
+                - gamepad.get(GamepadButton::LeftTrigger2).unwrap_or(0.0);
+        }
+        sticks.left = sticks.left.clamp_length_max(1.0);
+        sticks.right = sticks.right.clamp_length_max(1.0);
+        sticks.triggers = sticks.triggers.clamp(-1.0, 1.0);
+        sticks
+    }
+}
+
+/// Fraction of the remaining distance to a target to cover this frame.
+///
+/// `smoothing` is the time constant of the exponential approach in seconds; `0.0`
+/// or less jumps straight to the target.
+fn smoothing_blend(smoothing: f32, delta_secs: f32) -> f32 {
+    if smoothing > 0.0 {
+        1.0 - (-delta_secs / smoothing).exp()
+    } else {
+        1.0
+    }
+}
+
 /// System to toggle camera mode with F key.
 fn toggle_camera_mode(keyboard: Res<ButtonInput<KeyCode>>, mut mode: ResMut<CameraMode>) {
     if keyboard.just_pressed(KeyCode::KeyF) {
@@ -41,12 +152,26 @@ fn toggle_camera_mode(keyboard: Res<ButtonInput<KeyCode>>, mut mode: ResMut<Came
     }
 }
 
+/// System to apply the hold-to-fly override while its key is held.
+fn update_camera_mode_override(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<CameraSettings>,
+    mut mode_override: ResMut<CameraModeOverride>,
+) {
+    let held = settings
+        .hold_to_fly_key
+        .is_some_and(|key| keyboard.pressed(key));
+    mode_override.set_if_neq(CameraModeOverride(held.then_some(CameraMode::Fly)));
+}
+
 /// System to sync orbit camera state when switching from fly mode.
 fn sync_orbit_from_transform(
     mode: Res<CameraMode>,
+    mode_override: Res<CameraModeOverride>,
     mut cameras: Query<(&mut OrbitCamera, &Transform)>,
 ) {
-    if !mode.is_changed() || *mode != CameraMode::Orbit {
+    let switched = mode.is_changed() || mode_override.is_changed();
+    if !switched || mode_override.resolve(*mode) != CameraMode::Orbit {
         return;
     }
 
@@ -66,8 +191,13 @@ fn sync_orbit_from_transform(
 }
 
 /// System to sync fly camera state when switching from orbit mode.
-fn sync_fly_from_transform(mode: Res<CameraMode>, mut cameras: Query<(&mut FlyCamera, &Transform)>) {
-    if !mode.is_changed() || *mode != CameraMode::Fly {
+fn sync_fly_from_transform(
+    mode: Res<CameraMode>,
+    mode_override: Res<CameraModeOverride>,
+    mut cameras: Query<(&mut FlyCamera, &Transform)>,
+) {
+    let switched = mode.is_changed() || mode_override.is_changed();
+    if !switched || mode_override.resolve(*mode) != CameraMode::Fly {
         return;
     }
 
@@ -89,10 +219,24 @@ fn sync_fly_from_transform(mode: Res<CameraMode>, mut cameras: Query<(&mut FlyCa
 ///
 /// # Controls
 /// - `F`: Toggle between orbit and fly modes
+/// - [`CameraSettings::hold_to_fly_key`] (if set): Fly while held
+/// - Ctrl + 0-9 / 0-9: Save / recall a [`CameraBookmarks`] entry, if
+///   [`CameraSettings::bookmark_hotkeys`] is enabled
 ///
 /// ## Orbit Mode
-/// - Right mouse button + drag: Orbit around focus
+/// - Right mouse button + drag: Orbit around focus, or around the point under the
+///   cursor with [`OrbitCamera::pivot_on_cursor`]
+/// - Shift + right mouse button + drag: Pan the focus
 /// - Scroll wheel: Zoom in/out
+/// - [`CameraSettings::frame_key`] (`.` by default): Frame the selected splines, or all
+///   splines when nothing is selected
+/// - Numpad 1/3/7: Snap to front/right/top view (with Ctrl: back/left/bottom),
+///   unless [`CameraSettings::snap_views`] is disabled
+///
+/// ## Touch (Orbit Mode)
+/// - One finger drag: Orbit around focus
+/// - Two finger pinch: Zoom in/out
+/// - Two finger drag: Pan the focus
 ///
 /// ## Fly Mode
 /// - Right mouse button + drag: Look around
@@ -100,20 +244,43 @@ fn sync_fly_from_transform(mode: Res<CameraMode>, mut cameras: Query<(&mut FlyCa
 /// - Q/Space: Move up
 /// - E/Ctrl: Move down
 /// - Shift: Sprint
+///
+/// ## Gamepad
+/// Connected gamepads work alongside the mouse and keyboard, at rates set by the
+/// `gamepad_*` fields of [`OrbitCamera`] and [`FlyCamera`]:
+/// - Orbit mode: left stick pans the focus, right stick orbits (up raises the
+///   camera), right/left trigger zooms in/out
+/// - Fly mode: left stick moves, right stick looks around, right/left trigger moves
+///   up/down, pressing the left stick sprints
+///
+/// ## Rail Camera
+/// Add a [`RailCamera`] component instead to ride a spline while tracking a target.
 pub struct CameraPlugin;
 
 impl Plugin for CameraPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<CameraMode>().add_systems(
-            Update,
-            (
-                toggle_camera_mode,
-                sync_orbit_from_transform,
-                sync_fly_from_transform,
-                orbit::orbit_camera_input,
-                fly::fly_camera_input,
+        app.init_resource::<CameraMode>()
+            .init_resource::<CameraModeOverride>()
+            .init_resource::<CameraSettings>()
+            .init_resource::<CameraBookmarks>()
+            .add_systems(
+                Update,
+                (
+                    toggle_camera_mode,
+                    update_camera_mode_override,
+                    sync_orbit_from_transform,
+                    sync_fly_from_transform,
+                    snap::snap_view_input,
+                    snap::update_snapped_views,
+                    frame::frame_selection,
+                    bookmark::bookmark_hotkeys,
+                    touch::orbit_camera_touch,
+                    orbit::orbit_camera_input,
+                    fly::fly_camera_input,
+                )
+                    .chain()
+                    .in_set(CameraInputSystems),
             )
-                .chain(),
-        );
+            .add_systems(Update, rail::rail_camera_follow);
     }
 }
@@ -1,6 +1,14 @@
-use bevy::{input::mouse::MouseMotion, prelude::*};
+use bevy::{input::mouse::MouseMotion, prelude::*, window::PrimaryWindow};
 
-use super::CameraMode;
+use std::f32::consts::{PI, TAU};
+
+use crate::spline::Spline;
+
+use super::{smoothing_blend, CameraMode, CameraModeOverride, GamepadSticks};
+
+/// How close the cursor ray must pass to a spline for [`OrbitCamera::pivot_on_cursor`]
+/// to pivot on it, as a fraction of the orbit radius.
+const CURSOR_PIVOT_SPLINE_DISTANCE: f32 = 0.02;
 
 /// Component for orbit camera behavior.
 #[derive(Component, Debug, Clone)]
@@ -15,8 +23,18 @@ pub struct OrbitCamera {
     pub pitch: f32,
     /// Orbit sensitivity (radians per pixel).
     pub sensitivity: f32,
-    /// Zoom sensitivity (units per scroll).
+    /// Pan sensitivity (focus movement per pixel, as a fraction of the radius).
+    pub pan_sensitivity: f32,
+    /// Zoom sensitivity (scroll units per scroll wheel step).
+    /// Each step zooms by [`zoom_factor`](Self::zoom_factor) raised to this power.
     pub zoom_sensitivity: f32,
+    /// Multiplicative zoom per scroll unit.
+    /// The radius is divided by this factor when scrolling in and multiplied when scrolling out,
+    /// so zoom speed stays proportional to the distance from the focus point.
+    pub zoom_factor: f32,
+    /// Cumulative mouse motion (pixels) required after pressing the orbit button
+    /// before rotation begins. Filters out small unintended movements during clicks.
+    pub drag_deadzone: f32,
     /// Minimum orbit radius.
     pub min_radius: f32,
     /// Maximum orbit radius.
@@ -25,6 +43,22 @@ pub struct OrbitCamera {
     pub min_pitch: f32,
     /// Maximum pitch (prevents flipping).
     pub max_pitch: f32,
+    /// Time constant (seconds) for easing the view toward `focus`, `radius`, `yaw`
+    /// and `pitch`, which act as targets. `0.0` follows them instantly.
+    pub smoothing: f32,
+    /// Whether starting an orbit drag moves `focus` to the point under the cursor,
+    /// so the view rotates around it. The camera stays in place and turns to face it.
+    ///
+    /// Splines under the cursor are hit first, then the horizontal plane through the
+    /// current focus. When the cursor hits neither, the focus is left unchanged.
+    pub pivot_on_cursor: bool,
+    /// Gamepad orbit speed (radians per second at full right stick deflection).
+    pub gamepad_sensitivity: f32,
+    /// Gamepad pan speed (focus movement per second at full left stick deflection,
+    /// as a fraction of the radius).
+    pub gamepad_pan_sensitivity: f32,
+    /// Gamepad zoom speed (scroll units per second at full trigger deflection).
+    pub gamepad_zoom_sensitivity: f32,
 }
 
 impl Default for OrbitCamera {
@@ -35,70 +69,417 @@ impl Default for OrbitCamera {
             yaw: 0.0,
             pitch: 0.5,
             sensitivity: 0.005,
+            pan_sensitivity: 0.002,
             zoom_sensitivity: 1.0,
+            zoom_factor: 1.1,
+            drag_deadzone: 0.0,
             min_radius: 1.0,
             max_radius: 100.0,
             min_pitch: -std::f32::consts::FRAC_PI_2 + 0.1,
             max_pitch: std::f32::consts::FRAC_PI_2 - 0.1,
+            smoothing: 0.0,
+            pivot_on_cursor: false,
+            gamepad_sensitivity: 2.0,
+            gamepad_pan_sensitivity: 1.0,
+            gamepad_zoom_sensitivity: 10.0,
         }
     }
 }
 
 impl OrbitCamera {
     /// Calculate the camera position from current orbit parameters.
+    ///
+    /// With [`smoothing`](Self::smoothing) enabled, this is where the camera is
+    /// heading rather than where it is this frame.
     pub fn calculate_position(&self) -> Vec3 {
-        let x = self.radius * self.pitch.cos() * self.yaw.sin();
-        let y = self.radius * self.pitch.sin();
-        let z = self.radius * self.pitch.cos() * self.yaw.cos();
-        self.focus + Vec3::new(x, y, z)
+        orbit_position(self.focus, self.radius, self.yaw, self.pitch)
+    }
+
+    /// Move the focus to `focus`, keeping the camera at `position` by recomputing
+    /// the radius, yaw and pitch (within their limits).
+    pub fn refocus(&mut self, focus: Vec3, position: Vec3) {
+        let offset = position - focus;
+        let distance = offset.length();
+        self.focus = focus;
+        if distance > 0.0 {
+            let direction = offset / distance;
+            self.radius = distance.clamp(self.min_radius, self.max_radius);
+            self.yaw = direction.x.atan2(direction.z);
+            self.pitch = direction.y.asin().clamp(self.min_pitch, self.max_pitch);
+        }
+    }
+}
+
+/// Find the point for an orbit camera to pivot on under a cursor `ray`.
+///
+/// Returns the nearest point on a spline curve that the ray passes within
+/// `pick_distance` of, or else where the ray meets the horizontal plane at
+/// `plane_height`. Returns `None` if the ray misses both.
+pub fn cursor_pivot<'a>(
+    ray: Ray3d,
+    splines: impl IntoIterator<Item = (&'a Spline, &'a GlobalTransform)>,
+    pick_distance: f32,
+    plane_height: f32,
+) -> Option<Vec3> {
+    let spline_hit = splines
+        .into_iter()
+        .filter_map(|(spline, transform)| {
+            // Pick in the spline's local space
+            let to_local = transform.affine().inverse();
+            let origin = to_local.transform_point3(ray.origin);
+            let direction = to_local.transform_vector3(*ray.direction).try_normalize()?;
+            let t = spline.pick_curve(origin, direction, pick_distance)?;
+            let point = transform.transform_point(spline.evaluate(t)?);
+            Some((point, (point - ray.origin).dot(*ray.direction)))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(point, _)| point);
+
+    spline_hit.or_else(|| {
+        let distance = ray.intersect_plane(
+            Vec3::new(0.0, plane_height, 0.0),
+            InfinitePlane3d::new(Vec3::Y),
+        )?;
+        Some(ray.get_point(distance))
+    })
+}
+
+fn orbit_position(focus: Vec3, radius: f32, yaw: f32, pitch: f32) -> Vec3 {
+    let x = radius * pitch.cos() * yaw.sin();
+    let y = radius * pitch.sin();
+    let z = radius * pitch.cos() * yaw.cos();
+    focus + Vec3::new(x, y, z)
+}
+
+/// The orbit parameters the camera is displaying, trailing the targets while
+/// smoothing.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct SmoothedOrbit {
+    focus: Vec3,
+    radius: f32,
+    yaw: f32,
+    pitch: f32,
+}
+
+impl SmoothedOrbit {
+    fn new(orbit: &OrbitCamera) -> Self {
+        Self {
+            focus: orbit.focus,
+            radius: orbit.radius,
+            yaw: orbit.yaw,
+            pitch: orbit.pitch,
+        }
+    }
+
+    /// Move a fraction `blend` of the way toward the orbit's targets.
+    fn approach(&mut self, orbit: &OrbitCamera, blend: f32) {
+        if blend >= 1.0 {
+            *self = Self::new(orbit);
+            return;
+        }
+        self.focus = self.focus.lerp(orbit.focus, blend);
+        self.radius = self.radius.lerp(orbit.radius, blend);
+        // Turn the short way around, in case the target yaw was wrapped
+        let yaw_delta = (orbit.yaw - self.yaw + PI).rem_euclid(TAU) - PI;
+        self.yaw += yaw_delta * blend;
+        self.pitch = self.pitch.lerp(orbit.pitch, blend);
     }
 }
 
 /// System to handle orbit camera input.
+#[allow(clippy::too_many_arguments)]
 pub fn orbit_camera_input(
-    mut cameras: Query<(&mut OrbitCamera, &mut Transform)>,
+    mut cameras: Query<(
+        &mut OrbitCamera,
+        &mut Transform,
+        Option<&Camera>,
+        Option<&GlobalTransform>,
+    )>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    splines: Query<(&Spline, &GlobalTransform)>,
     camera_mode: Res<CameraMode>,
+    mode_override: Res<CameraModeOverride>,
     mouse: Res<ButtonInput<MouseButton>>,
     mut motion: MessageReader<MouseMotion>,
     mut scroll: MessageReader<bevy::input::mouse::MouseWheel>,
     keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    time: Res<Time>,
+    mut drag_motion: Local<f32>,
+    mut smoothed: Local<Option<SmoothedOrbit>>,
 ) {
-    if *camera_mode != CameraMode::Orbit {
+    if mode_override.resolve(*camera_mode) != CameraMode::Orbit {
         motion.clear();
         scroll.clear();
+        // Start from the targets when orbiting resumes
+        *smoothed = None;
         return;
     }
 
-    let Ok((mut orbit, mut transform)) = cameras.single_mut() else {
+    let Ok((mut orbit, mut transform, camera, camera_transform)) = cameras.single_mut() else {
         return;
     };
 
+    // Shift + right mouse button pans instead of rotating
+    let panning = mouse.pressed(MouseButton::Right)
+        && (keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight));
+
     // Handle orbit rotation (right mouse button or middle mouse button)
     if mouse.pressed(MouseButton::Right) || mouse.pressed(MouseButton::Middle) {
+        if mouse.any_just_pressed([MouseButton::Right, MouseButton::Middle]) {
+            *drag_motion = 0.0;
+
+            if orbit.pivot_on_cursor && !panning {
+                let ray = camera
+                    .zip(camera_transform)
+                    .and_then(|(camera, camera_transform)| {
+                        let cursor = windows.single().ok()?.cursor_position()?;
+                        camera.viewport_to_world(camera_transform, cursor).ok()
+                    });
+                let pivot = ray.and_then(|ray| {
+                    let pick_distance = orbit.radius * CURSOR_PIVOT_SPLINE_DISTANCE;
+                    cursor_pivot(ray, splines, pick_distance, orbit.focus.y)
+                });
+                if let Some(pivot) = pivot {
+                    orbit.refocus(pivot, transform.translation);
+                }
+            }
+        }
+        // Pan in the view plane of the current transform
+        let right = transform.right();
+        let up = transform.up();
         for ev in motion.read() {
-            orbit.yaw -= ev.delta.x * orbit.sensitivity;
-            orbit.pitch += ev.delta.y * orbit.sensitivity;
-            orbit.pitch = orbit.pitch.clamp(orbit.min_pitch, orbit.max_pitch);
+            // Ignore motion until the drag leaves the deadzone
+            if *drag_motion <= orbit.drag_deadzone {
+                *drag_motion += ev.delta.length();
+                if *drag_motion <= orbit.drag_deadzone {
+                    continue;
+                }
+            }
+            if panning {
+                // Scale with the radius so the scene keeps pace with the cursor
+                let scale = orbit.pan_sensitivity * orbit.radius;
+                orbit.focus += (up * ev.delta.y - right * ev.delta.x) * scale;
+            } else {
+                orbit.yaw -= ev.delta.x * orbit.sensitivity;
+                orbit.pitch += ev.delta.y * orbit.sensitivity;
+                orbit.pitch = orbit.pitch.clamp(orbit.min_pitch, orbit.max_pitch);
+            }
         }
     } else {
         motion.clear();
-    }
-
-    // Handle panning (shift + right mouse button)
-    if mouse.pressed(MouseButton::Right)
-        && (keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight))
-    {
-        // Pan is handled separately if needed
+        *drag_motion = 0.0;
     }
 
     // Handle zoom (scroll wheel)
     for ev in scroll.read() {
-        orbit.radius -= ev.y * orbit.zoom_sensitivity;
+        orbit.radius *= orbit.zoom_factor.powf(-ev.y * orbit.zoom_sensitivity);
+        orbit.radius = orbit.radius.clamp(orbit.min_radius, orbit.max_radius);
+    }
+
+    // Gamepad: left stick pans, right stick orbits, triggers zoom
+    let sticks = GamepadSticks::read(&gamepads);
+    let delta_secs = time.delta_secs();
+    if sticks.left != Vec2::ZERO {
+        let scale = orbit.gamepad_pan_sensitivity * orbit.radius * delta_secs;
+        orbit.focus += (transform.right() * sticks.left.x + transform.up() * sticks.left.y) * scale;
+    }
+    if sticks.right != Vec2::ZERO {
+        let turn = sticks.right * orbit.gamepad_sensitivity * delta_secs;
+        orbit.yaw -= turn.x;
+        orbit.pitch = (orbit.pitch + turn.y).clamp(orbit.min_pitch, orbit.max_pitch);
+    }
+    if sticks.triggers != 0.0 {
+        let scroll = sticks.triggers * orbit.gamepad_zoom_sensitivity * delta_secs;
+        orbit.radius *= orbit.zoom_factor.powf(-scroll);
         orbit.radius = orbit.radius.clamp(orbit.min_radius, orbit.max_radius);
     }
 
+    // Ease toward the targets
+    let view = smoothed.get_or_insert_with(|| SmoothedOrbit::new(&orbit));
+    view.approach(&orbit, smoothing_blend(orbit.smoothing, time.delta_secs()));
+
     // Update transform
-    let position = orbit.calculate_position();
-    transform.translation = position;
-    transform.look_at(orbit.focus, Vec3::Y);
+    transform.translation = orbit_position(view.focus, view.radius, view.yaw, view.pitch);
+    transform.look_at(view.focus, Vec3::Y);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+    use bevy::input::mouse::MouseWheel;
+
+    #[test]
+    fn test_shift_drag_pans_without_rotating() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<CameraMode>()
+            .init_resource::<CameraModeOverride>()
+            .add_message::<MouseMotion>()
+            .add_message::<MouseWheel>()
+            .add_systems(Update, orbit_camera_input);
+
+        let orbit = OrbitCamera {
+            pitch: 0.0,
+            ..default()
+        };
+        let transform = Transform::from_translation(orbit.calculate_position())
+            .looking_at(orbit.focus, Vec3::Y);
+        let camera = app.world_mut().spawn((orbit, transform)).id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ShiftLeft);
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Right);
+        app.world_mut().write_message(MouseMotion {
+            delta: Vec2::new(10.0, 0.0),
+        });
+        app.update();
+
+        // Dragging right slides the focus left (the camera looks down -Z, so right is +X)
+        let orbit = app.world().get::<OrbitCamera>(camera).unwrap();
+        assert!(orbit.focus.x < 0.0);
+        assert!(orbit.focus.y.abs() < 1e-5 && orbit.focus.z.abs() < 1e-5);
+        assert_eq!((orbit.yaw, orbit.pitch), (0.0, 0.0));
+
+        // The camera moved along with its focus
+        let transform = app.world().get::<Transform>(camera).unwrap();
+        assert!(transform.translation.distance(orbit.calculate_position()) < 1e-5);
+    }
+
+    #[test]
+    fn test_smoothing_eases_toward_targets() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<CameraMode>()
+            .init_resource::<CameraModeOverride>()
+            .add_message::<MouseMotion>()
+            .add_message::<MouseWheel>()
+            .add_systems(Update, orbit_camera_input);
+
+        let camera = app
+            .world_mut()
+            .spawn((
+                OrbitCamera {
+                    smoothing: 0.2,
+                    ..default()
+                },
+                Transform::default(),
+            ))
+            .id();
+        let step = |app: &mut App, seconds: f32| {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(std::time::Duration::from_secs_f32(seconds));
+            app.update();
+            *app.world().get::<Transform>(camera).unwrap()
+        };
+
+        // The first frame starts at the targets
+        let start = step(&mut app, 0.016);
+        let from = app
+            .world()
+            .get::<OrbitCamera>(camera)
+            .unwrap()
+            .calculate_position();
+        assert!(start.translation.distance(from) < 1e-5);
+
+        app.world_mut()
+            .get_mut::<OrbitCamera>(camera)
+            .unwrap()
+            .radius = 20.0;
+        let target = app
+            .world()
+            .get::<OrbitCamera>(camera)
+            .unwrap()
+            .calculate_position();
+
+        // One time constant covers about 63% of the way
+        let eased = step(&mut app, 0.2);
+        let covered = eased.translation.distance(from) / target.distance(from);
+        assert!((covered - 0.632).abs() < 0.01, "covered {covered}");
+
+        let settled = step(&mut app, 2.0);
+        assert!(settled.translation.distance(target) < 1e-3);
+    }
+
+    #[test]
+    fn test_gamepad_orbits_and_zooms() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<CameraMode>()
+            .init_resource::<CameraModeOverride>()
+            .add_message::<MouseMotion>()
+            .add_message::<MouseWheel>()
+            .add_systems(Update, orbit_camera_input);
+
+        let camera = app
+            .world_mut()
+            .spawn((OrbitCamera::default(), Transform::default()))
+            .id();
+        let mut gamepad = Gamepad::default();
+        gamepad.analog_mut().set(GamepadAxis::RightStickX, 1.0);
+        gamepad.analog_mut().set(GamepadButton::RightTrigger2, 0.5);
+        app.world_mut().spawn(gamepad);
+
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(std::time::Duration::from_secs_f32(0.5));
+        app.update();
+
+        // Full right stick turns at the orbit rate, half trigger zooms at half rate
+        let orbit = app.world().get::<OrbitCamera>(camera).unwrap();
+        let defaults = OrbitCamera::default();
+        assert!((orbit.yaw + defaults.gamepad_sensitivity * 0.5).abs() < 1e-5);
+        assert_eq!(orbit.pitch, defaults.pitch);
+        let scroll = 0.5 * defaults.gamepad_zoom_sensitivity * 0.5;
+        let expected = defaults.radius * defaults.zoom_factor.powf(-scroll);
+        assert!((orbit.radius - expected).abs() < 1e-4, "{}", orbit.radius);
+        assert_eq!(orbit.focus, Vec3::ZERO);
+    }
+
+    #[test]
+    fn test_cursor_pivot_prefers_splines_over_plane() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(-2.0, 0.0, 0.0),
+                Vec3::new(-1.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(2.0, 0.0, 0.0),
+            ],
+        );
+        let transform = GlobalTransform::from_translation(Vec3::new(0.0, 2.0, 0.0));
+        let splines = [(&spline, &transform)];
+
+        // Looking down through the raised spline hits it (within the pick distance)
+        // before the plane
+        let ray = Ray3d::new(Vec3::new(0.5, 10.0, 0.0), Dir3::NEG_Y);
+        let pivot = cursor_pivot(ray, splines, 0.1, 0.0).unwrap();
+        assert!(pivot.distance(Vec3::new(0.5, 2.0, 0.0)) <= 0.1, "{pivot}");
+
+        // Missing the spline falls back to the plane
+        let ray = Ray3d::new(Vec3::new(0.5, 10.0, 3.0), Dir3::NEG_Y);
+        let pivot = cursor_pivot(ray, splines, 0.1, -1.0).unwrap();
+        assert!(pivot.distance(Vec3::new(0.5, -1.0, 3.0)) < 1e-5, "{pivot}");
+
+        // Looking away from both leaves the focus alone
+        let ray = Ray3d::new(Vec3::new(0.5, 10.0, 3.0), Dir3::Y);
+        assert_eq!(cursor_pivot(ray, splines, 0.1, -1.0), None);
+
+        // Refocusing keeps the camera where it is
+        let mut orbit = OrbitCamera::default();
+        let position = orbit.calculate_position();
+        orbit.refocus(Vec3::new(1.0, 0.0, -1.0), position);
+        assert!(orbit.calculate_position().distance(position) < 1e-4);
+    }
 }
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+
+use crate::spline::Spline;
+
+use super::smoothing_blend;
+
+/// Coarse samples per spline segment when searching for the closest point.
+const CLOSEST_POINT_SAMPLES_PER_SEGMENT: usize = 32;
+
+/// Component for a camera that rides along a spline rail, tracking a target.
+///
+/// Each frame the camera moves to the point on the rail closest to the target
+/// entity and looks at the target. Works independently of [`CameraMode`](super::CameraMode);
+/// don't combine it with [`OrbitCamera`](super::OrbitCamera) or
+/// [`FlyCamera`](super::FlyCamera) on the same entity.
+#[derive(Component, Debug, Clone)]
+pub struct RailCamera {
+    /// The spline entity used as the rail.
+    pub spline: Entity,
+    /// The entity to track and look at.
+    pub target: Entity,
+    /// Smoothing time in seconds. The camera covers about 63% of the remaining
+    /// distance along the rail in this time. 0.0 snaps to the closest point.
+    pub smoothing: f32,
+    /// Current parametric position on the rail (0.0 to 1.0).
+    pub t: f32,
+}
+
+impl RailCamera {
+    /// Create a rail camera that tracks `target` along `spline`.
+    pub fn new(spline: Entity, target: Entity) -> Self {
+        Self {
+            spline,
+            target,
+            smoothing: 0.2,
+            t: 0.0,
+        }
+    }
+
+    /// Set the smoothing time in seconds.
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+}
+
+/// System to move rail cameras along their splines.
+pub fn rail_camera_follow(
+    mut cameras: Query<(&mut RailCamera, &mut Transform)>,
+    splines: Query<(&Spline, &GlobalTransform)>,
+    targets: Query<&GlobalTransform>,
+    time: Res<Time>,
+) {
+    for (mut rail, mut transform) in &mut cameras {
+        let Ok((spline, spline_transform)) = splines.get(rail.spline) else {
+            continue;
+        };
+        let Ok(target) = targets.get(rail.target) else {
+            continue;
+        };
+
+        // Search in the spline's local space
+        let target_position = target.translation();
+        let local_target = spline_transform
+            .affine()
+            .inverse()
+            .transform_point3(target_position);
+        let samples = spline.segment_count() * CLOSEST_POINT_SAMPLES_PER_SEGMENT;
+        let Some((closest_t, _, _)) = spline.closest_point(local_target, samples) else {
+            continue;
+        };
+
+        // Take the short way around closed rails
+        let mut delta = closest_t - rail.t;
+        if spline.closed {
+            delta -= delta.round();
+        }
+
+        rail.t += delta * smoothing_blend(rail.smoothing, time.delta_secs());
+        rail.t = if spline.closed {
+            rail.t.rem_euclid(1.0)
+        } else {
+            rail.t.clamp(0.0, 1.0)
+        };
+
+        let Some(position) = spline.evaluate(rail.t) else {
+            continue;
+        };
+        transform.translation = spline_transform.transform_point(position);
+        if transform.translation != target_position {
+            transform.look_at(target_position, Vec3::Y);
+        }
+    }
+}
@@ -0,0 +1,293 @@
+//! Axis-aligned snap views for the orbit camera.
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use bevy::{camera::ScalingMode, prelude::*};
+
+use super::{CameraMode, CameraModeOverride, CameraSettings, OrbitCamera};
+
+/// Pitch of the top and bottom views.
+///
+/// Looking exactly along the up axis leaves the camera's roll undefined, so the
+/// views stop just short of it.
+const POLE_PITCH: f32 = FRAC_PI_2 - 1e-3;
+
+/// An axis-aligned view the orbit camera can snap to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapView {
+    /// Looking along -Z, from the +Z side of the focus.
+    Front,
+    /// Looking along +Z, from the -Z side of the focus.
+    Back,
+    /// Looking along -X, from the +X side of the focus.
+    Right,
+    /// Looking along +X, from the -X side of the focus.
+    Left,
+    /// Looking down from above the focus.
+    Top,
+    /// Looking up from below the focus.
+    Bottom,
+}
+
+impl SnapView {
+    /// Get the orbit yaw and pitch that look at the focus from this side.
+    ///
+    /// Snapping stops the top and bottom views at the camera's
+    /// [`min_pitch`](OrbitCamera::min_pitch) and [`max_pitch`](OrbitCamera::max_pitch).
+    pub fn yaw_pitch(&self) -> (f32, f32) {
+        match self {
+            Self::Front => (0.0, 0.0),
+            Self::Back => (PI, 0.0),
+            Self::Right => (FRAC_PI_2, 0.0),
+            Self::Left => (-FRAC_PI_2, 0.0),
+            Self::Top => (0.0, POLE_PITCH),
+            Self::Bottom => (0.0, -POLE_PITCH),
+        }
+    }
+
+    /// Get the view looking from the other side.
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::Front => Self::Back,
+            Self::Back => Self::Front,
+            Self::Right => Self::Left,
+            Self::Left => Self::Right,
+            Self::Top => Self::Bottom,
+            Self::Bottom => Self::Top,
+        }
+    }
+
+    /// Get the view bound to a numpad key: 1 for front, 3 for right, 7 for top.
+    /// `opposite` picks the view from the other side (back, left, bottom).
+    pub fn from_key(key: KeyCode, opposite: bool) -> Option<Self> {
+        let view = match key {
+            KeyCode::Numpad1 => Self::Front,
+            KeyCode::Numpad3 => Self::Right,
+            KeyCode::Numpad7 => Self::Top,
+            _ => return None,
+        };
+        Some(if opposite { view.opposite() } else { view })
+    }
+}
+
+/// Marks an orbit camera that is snapped to, or turning toward, a [`SnapView`].
+///
+/// Removed once the user orbits away, restoring the perspective projection if the
+/// snap switched to orthographic.
+#[derive(Component, Debug, Clone)]
+pub struct SnappedView {
+    /// The view being snapped to.
+    pub view: SnapView,
+    /// Yaw and pitch when the snap started.
+    from: (f32, f32),
+    /// How far the camera has turned toward the view, from 0 to 1.
+    progress: f32,
+    /// Projection to restore when leaving the view.
+    perspective: Option<PerspectiveProjection>,
+}
+
+/// Height of the orthographic view that frames the focus like `perspective` does
+/// from `distance` away.
+fn matching_viewport_height(perspective: &PerspectiveProjection, distance: f32) -> f32 {
+    2.0 * distance * (perspective.fov / 2.0).tan()
+}
+
+/// System to snap the orbit camera to a view when its numpad key is pressed.
+pub fn snap_view_input(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<CameraSettings>,
+    camera_mode: Res<CameraMode>,
+    mode_override: Res<CameraModeOverride>,
+    mut cameras: Query<(Entity, &OrbitCamera, &mut Projection, Option<&SnappedView>)>,
+) {
+    if !settings.snap_views || mode_override.resolve(*camera_mode) != CameraMode::Orbit {
+        return;
+    }
+
+    let opposite = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+    let Some(view) = keyboard
+        .get_just_pressed()
+        .find_map(|&key| SnapView::from_key(key, opposite))
+    else {
+        return;
+    };
+
+    let Ok((entity, orbit, mut projection, snapped)) = cameras.single_mut() else {
+        return;
+    };
+
+    // Snapping again from a snapped view keeps the projection saved the first time
+    let mut perspective = snapped.and_then(|snapped| snapped.perspective.clone());
+    if settings.snap_orthographic && perspective.is_none() {
+        if let Projection::Perspective(current) = &*projection {
+            let viewport_height = matching_viewport_height(current, orbit.radius);
+            perspective = Some(current.clone());
+            *projection = Projection::Orthographic(OrthographicProjection {
+                scaling_mode: ScalingMode::FixedVertical { viewport_height },
+                ..OrthographicProjection::default_3d()
+            });
+        }
+    }
+
+    commands.entity(entity).insert(SnappedView {
+        view,
+        from: (orbit.yaw, orbit.pitch),
+        progress: 0.0,
+        perspective,
+    });
+}
+
+/// System to turn snapping orbit cameras toward their view, and to leave the view
+/// once the user orbits away.
+pub fn update_snapped_views(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<CameraSettings>,
+    camera_mode: Res<CameraMode>,
+    mode_override: Res<CameraModeOverride>,
+    mut cameras: Query<(Entity, &mut OrbitCamera, &mut Projection, &mut SnappedView)>,
+) {
+    let orbiting = mode_override.resolve(*camera_mode) == CameraMode::Orbit;
+
+    for (entity, mut orbit, mut projection, mut snapped) in &mut cameras {
+        let (yaw, pitch) = snapped.view.yaw_pitch();
+        // Stay within the pitch limits, which orbiting would otherwise clamp to,
+        // ending the snap
+        let pitch = pitch.max(orbit.min_pitch).min(orbit.max_pitch);
+
+        // Once the camera has arrived, any change to its angles came from the user
+        let arrived = snapped.progress >= 1.0;
+        if !orbiting || (arrived && (orbit.yaw, orbit.pitch) != (yaw, pitch)) {
+            if let Some(perspective) = snapped.perspective.take() {
+                *projection = Projection::Perspective(perspective);
+            }
+            commands.entity(entity).remove::<SnappedView>();
+            continue;
+        }
+
+        if !arrived {
+            snapped.progress = if settings.snap_duration > 0.0 {
+                (snapped.progress + time.delta_secs() / settings.snap_duration).min(1.0)
+            } else {
+                1.0
+            };
+
+            if snapped.progress >= 1.0 {
+                orbit.yaw = yaw;
+                orbit.pitch = pitch;
+            } else {
+                let s = EaseFunction::SmoothStep.sample_clamped(snapped.progress);
+                let (from_yaw, from_pitch) = snapped.from;
+                // Turn the short way around
+                let yaw_delta = (yaw - from_yaw + PI).rem_euclid(TAU) - PI;
+                orbit.yaw = from_yaw + yaw_delta * s;
+                orbit.pitch = from_pitch.lerp(pitch, s);
+            }
+        }
+
+        // Follow zooming while orthographic
+        if let (Some(perspective), Projection::Orthographic(ortho)) =
+            (&snapped.perspective, &mut *projection)
+        {
+            let viewport_height = matching_viewport_height(perspective, orbit.radius);
+            ortho.scaling_mode = ScalingMode::FixedVertical { viewport_height };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn snap_app(snap_duration: f32) -> App {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<CameraMode>()
+            .init_resource::<CameraModeOverride>()
+            .insert_resource(CameraSettings {
+                snap_duration,
+                ..default()
+            })
+            .add_systems(Update, (snap_view_input, update_snapped_views).chain());
+        app
+    }
+
+    fn press(app: &mut App, key: KeyCode) {
+        let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keyboard.clear();
+        keyboard.press(key);
+    }
+
+    fn advance(app: &mut App, seconds: f32) {
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+    }
+
+    #[test]
+    fn test_snap_view_jumps_and_restores_perspective() {
+        let mut app = snap_app(0.0);
+        let camera = app
+            .world_mut()
+            .spawn((OrbitCamera::default(), Projection::Perspective(default())))
+            .id();
+
+        press(&mut app, KeyCode::Numpad7);
+        advance(&mut app, 0.016);
+        // The top view stops at the camera's pitch limit
+        let orbit = app.world().get::<OrbitCamera>(camera).unwrap();
+        assert_eq!((orbit.yaw, orbit.pitch), (0.0, orbit.max_pitch));
+        assert!(matches!(
+            app.world().get::<Projection>(camera),
+            Some(Projection::Orthographic(_))
+        ));
+
+        // Staying put keeps the view
+        advance(&mut app, 0.016);
+        assert!(app.world().get::<SnappedView>(camera).is_some());
+
+        // Orbiting away returns to perspective
+        app.world_mut().get_mut::<OrbitCamera>(camera).unwrap().yaw += 0.1;
+        advance(&mut app, 0.016);
+        assert!(app.world().get::<SnappedView>(camera).is_none());
+        assert!(matches!(
+            app.world().get::<Projection>(camera),
+            Some(Projection::Perspective(_))
+        ));
+    }
+
+    #[test]
+    fn test_snap_view_animates_short_way_around() {
+        let mut app = snap_app(0.2);
+        let camera = app
+            .world_mut()
+            .spawn((
+                OrbitCamera {
+                    yaw: PI - 0.2,
+                    pitch: 0.5,
+                    ..default()
+                },
+                Projection::Perspective(default()),
+            ))
+            .id();
+
+        // Ctrl + numpad 1 views from the back, at yaw = PI
+        press(&mut app, KeyCode::ControlLeft);
+        press(&mut app, KeyCode::Numpad1);
+        advance(&mut app, 0.1);
+        let orbit = app.world().get::<OrbitCamera>(camera).unwrap();
+        assert!(orbit.yaw > PI - 0.2 && orbit.yaw < PI);
+        assert!(orbit.pitch > 0.0 && orbit.pitch < 0.5);
+
+        advance(&mut app, 0.1);
+        let orbit = app.world().get::<OrbitCamera>(camera).unwrap();
+        assert_eq!((orbit.yaw, orbit.pitch), SnapView::Back.yaw_pitch());
+    }
+}
@@ -0,0 +1,95 @@
+//! Touch screen controls for the orbit camera.
+
+use bevy::{input::touch::Touch, prelude::*};
+
+use super::{CameraMode, CameraModeOverride, OrbitCamera};
+
+/// Factor to multiply the orbit radius by when two touches move from
+/// `previous` to `current` positions.
+///
+/// Spreading the fingers apart zooms in (a factor below 1), pinching them together
+/// zooms out. Returns 1.0 if either pair of touches is on the same spot.
+pub fn pinch_zoom_factor(previous: [Vec2; 2], current: [Vec2; 2]) -> f32 {
+    let previous_span = previous[0].distance(previous[1]);
+    let current_span = current[0].distance(current[1]);
+    if previous_span <= f32::EPSILON || current_span <= f32::EPSILON {
+        return 1.0;
+    }
+    previous_span / current_span
+}
+
+/// System to orbit, pan and zoom the orbit camera with touches.
+///
+/// One finger dragging orbits like the right mouse button. Two fingers pinch to
+/// zoom and move together to pan the focus, at the orbit camera's mouse
+/// sensitivities. Does nothing without a [`Touches`] resource, so it runs
+/// alongside mouse input.
+pub fn orbit_camera_touch(
+    touches: Option<Res<Touches>>,
+    camera_mode: Res<CameraMode>,
+    mode_override: Res<CameraModeOverride>,
+    mut cameras: Query<(&mut OrbitCamera, &Transform)>,
+) {
+    let Some(touches) = touches else {
+        return;
+    };
+    if mode_override.resolve(*camera_mode) != CameraMode::Orbit {
+        return;
+    }
+
+    let active: Vec<&Touch> = touches.iter().collect();
+    if active.is_empty() {
+        return;
+    }
+
+    let Ok((mut orbit, transform)) = cameras.single_mut() else {
+        return;
+    };
+
+    match active.as_slice() {
+        [touch] => {
+            let delta = touch.delta();
+            orbit.yaw -= delta.x * orbit.sensitivity;
+            orbit.pitch += delta.y * orbit.sensitivity;
+            orbit.pitch = orbit.pitch.clamp(orbit.min_pitch, orbit.max_pitch);
+        }
+        [first, second, ..] => {
+            let previous = [first.previous_position(), second.previous_position()];
+            let current = [first.position(), second.position()];
+
+            // Drag the scene along with the midpoint, like mouse panning
+            let delta = (current[0] + current[1] - previous[0] - previous[1]) / 2.0;
+            let scale = orbit.pan_sensitivity * orbit.radius;
+            orbit.focus += (transform.up() * delta.y - transform.right() * delta.x) * scale;
+
+            orbit.radius *= pinch_zoom_factor(previous, current);
+            orbit.radius = orbit.radius.clamp(orbit.min_radius, orbit.max_radius);
+        }
+        [] => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pinch_zoom_factor() {
+        let previous = [Vec2::new(100.0, 100.0), Vec2::new(200.0, 100.0)];
+
+        // Spreading to twice the span halves the radius
+        let spread = [Vec2::new(50.0, 100.0), Vec2::new(250.0, 100.0)];
+        assert_eq!(pinch_zoom_factor(previous, spread), 0.5);
+
+        // Pinching to half the span doubles it
+        let pinched = [Vec2::new(125.0, 100.0), Vec2::new(175.0, 100.0)];
+        assert_eq!(pinch_zoom_factor(previous, pinched), 2.0);
+
+        // Moving or rotating both fingers together doesn't zoom
+        let moved = [Vec2::new(150.0, 50.0), Vec2::new(150.0, 150.0)];
+        assert!((pinch_zoom_factor(previous, moved) - 1.0).abs() < 1e-6);
+
+        // Touches on the same spot can't zoom
+        assert_eq!(pinch_zoom_factor([Vec2::ZERO; 2], spread), 1.0);
+    }
+}
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
 
+use super::random::{instance_random, RandomStream};
+
 /// Component that defines how entities are distributed along a spline.
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component)]
@@ -9,7 +11,17 @@ pub struct SplineDistribution {
     /// The source/template entity to clone.
     /// This entity should have a `DistributionSource` component.
     pub source: Entity,
+    /// Weighted source entities to pick from at random, instead of `source`.
+    ///
+    /// Each instance picks one entity with probability proportional to its weight,
+    /// using [`seed`](Self::seed). When empty, `source` is used for every instance.
+    /// Each entity should have a `DistributionSource` component.
+    #[reflect(default)]
+    pub sources: Vec<(Entity, f32)>,
     /// Number of copies to distribute along the spline.
+    ///
+    /// Ignored with [`DistributionSpacing::FixedDistance`], where the count follows
+    /// from the spline's length.
     pub count: usize,
     /// How to orient the distributed copies.
     pub orientation: DistributionOrientation,
@@ -19,6 +31,53 @@ pub struct SplineDistribution {
     pub offset: Vec3,
     /// Whether distribution is enabled.
     pub enabled: bool,
+    /// Number of samples used for the arc-length table with uniform and fixed distance spacing.
+    ///
+    /// When `None`, the resolution is derived from the spline's segment count
+    /// (see [`Spline::default_arc_length_samples`]).
+    ///
+    /// [`Spline::default_arc_length_samples`]: crate::spline::Spline::default_arc_length_samples
+    #[reflect(default)]
+    pub arc_length_samples: Option<usize>,
+    /// Minimum world-space distance between instance positions.
+    ///
+    /// When set, instances closer than this are nudged apart along the spline
+    /// after their positions are computed. The result is deterministic.
+    #[reflect(default)]
+    pub min_spacing: Option<f32>,
+    /// Road whose edge the instances are placed on.
+    ///
+    /// When set, each instance is moved sideways to the chosen edge of the road's
+    /// cross-section profile, before `offset` is applied, in the same frames the
+    /// road mesh uses (including [`SplineRoad::use_rmf`](crate::road::SplineRoad::use_rmf)).
+    /// The road should follow the same spline as this distribution.
+    #[reflect(default)]
+    pub road_edge: Option<RoadEdge>,
+    /// Source entity used for the first and last instance instead of `source`.
+    ///
+    /// Useful for bookends such as a gate at the start and end of a fence. The
+    /// entity should also have a `DistributionSource` component.
+    #[reflect(default)]
+    pub endpoint_source: Option<Entity>,
+    /// Orientation used for the first and last instance instead of `orientation`.
+    #[reflect(default)]
+    pub endpoint_orientation: Option<DistributionOrientation>,
+    /// Range `(min, max)` of the random uniform scale applied to each instance.
+    #[reflect(default)]
+    pub scale_jitter: Option<(f32, f32)>,
+    /// Maximum random rotation in radians, either way, about each instance's local Y axis.
+    #[reflect(default)]
+    pub yaw_jitter: Option<f32>,
+    /// Start of the stretch of spline to distribute along (0.0 to 1.0).
+    #[reflect(default = "default_start_t")]
+    pub start_t: f32,
+    /// End of the stretch of spline to distribute along (0.0 to 1.0).
+    #[reflect(default = "default_end_t")]
+    pub end_t: f32,
+    /// Seed for the jitter and source selection. Each instance's random choices depend
+    /// only on the seed and its index, so the same seed always gives the same result.
+    #[reflect(default)]
+    pub seed: u64,
 }
 
 impl Default for SplineDistribution {
@@ -26,15 +85,34 @@ impl Default for SplineDistribution {
         Self {
             spline: Entity::PLACEHOLDER,
             source: Entity::PLACEHOLDER,
+            sources: Vec::new(),
             count: 10,
             orientation: DistributionOrientation::default(),
             spacing: DistributionSpacing::default(),
             offset: Vec3::ZERO,
             enabled: true,
+            arc_length_samples: None,
+            min_spacing: None,
+            road_edge: None,
+            endpoint_source: None,
+            endpoint_orientation: None,
+            scale_jitter: None,
+            yaw_jitter: None,
+            seed: 0,
+            start_t: 0.0,
+            end_t: 1.0,
         }
     }
 }
 
+fn default_start_t() -> f32 {
+    0.0
+}
+
+fn default_end_t() -> f32 {
+    1.0
+}
+
 impl SplineDistribution {
     /// Create a new distribution along a spline.
     pub fn new(spline: Entity, source: Entity, count: usize) -> Self {
@@ -64,6 +142,117 @@ impl SplineDistribution {
         self
     }
 
+    /// Set the number of arc-length samples used for uniform spacing.
+    pub fn with_arc_length_samples(mut self, samples: usize) -> Self {
+        self.arc_length_samples = Some(samples);
+        self
+    }
+
+    /// Set the minimum world-space distance between instances.
+    pub fn with_min_spacing(mut self, min_spacing: f32) -> Self {
+        self.min_spacing = Some(min_spacing);
+        self
+    }
+
+    /// Place instances on one edge of a road.
+    pub fn with_road_edge(mut self, road: Entity, side: RoadSide) -> Self {
+        self.road_edge = Some(RoadEdge { road, side });
+        self
+    }
+
+    /// Use a different source entity for the first and last instance.
+    pub fn with_endpoint_source(mut self, source: Entity) -> Self {
+        self.endpoint_source = Some(source);
+        self
+    }
+
+    /// Use a different orientation for the first and last instance.
+    pub fn with_endpoint_orientation(mut self, orientation: DistributionOrientation) -> Self {
+        self.endpoint_orientation = Some(orientation);
+        self
+    }
+
+    /// Pick each instance's source at random from weighted entities.
+    pub fn with_sources(mut self, sources: impl IntoIterator<Item = (Entity, f32)>) -> Self {
+        self.sources = sources.into_iter().collect();
+        self
+    }
+
+    /// Only distribute along the stretch of spline between `start_t` and `end_t`.
+    pub fn with_range(mut self, start_t: f32, end_t: f32) -> Self {
+        self.start_t = start_t;
+        self.end_t = end_t;
+        self
+    }
+
+    /// Get the t range to distribute along, clamped to 0.0 - 1.0.
+    ///
+    /// A reversed range is swapped so the start always comes first.
+    pub fn t_range(&self) -> (f32, f32) {
+        let start = self.start_t.clamp(0.0, 1.0);
+        let end = self.end_t.clamp(0.0, 1.0);
+        (start.min(end), start.max(end))
+    }
+
+    /// Randomly scale each instance by a factor between `min` and `max`.
+    pub fn with_scale_jitter(mut self, min: f32, max: f32) -> Self {
+        self.scale_jitter = Some((min, max));
+        self
+    }
+
+    /// Randomly rotate each instance up to `max_angle` radians about its local Y axis.
+    pub fn with_yaw_jitter(mut self, max_angle: f32) -> Self {
+        self.yaw_jitter = Some(max_angle);
+        self
+    }
+
+    /// Set the seed used for the jitter.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Check whether the instance at `index` is the first or last of the `count`
+    /// instances placed, which differs from [`Self::count`] with
+    /// [`DistributionSpacing::FixedDistance`].
+    pub(crate) fn is_endpoint(index: usize, count: usize) -> bool {
+        index == 0 || index + 1 == count
+    }
+
+    /// Get the source entity for the instance at `index` of `count` placed instances.
+    pub(crate) fn source_for(&self, index: usize, count: usize) -> Entity {
+        self.endpoint_source
+            .filter(|_| Self::is_endpoint(index, count))
+            .or_else(|| self.weighted_source_for(index))
+            .unwrap_or(self.source)
+    }
+
+    /// Get the orientation for the instance at `index` of `count` placed instances.
+    pub(crate) fn orientation_for(&self, index: usize, count: usize) -> DistributionOrientation {
+        self.endpoint_orientation
+            .filter(|_| Self::is_endpoint(index, count))
+            .unwrap_or(self.orientation)
+    }
+
+    /// Pick from the weighted `sources` for the instance at `index`.
+    fn weighted_source_for(&self, index: usize) -> Option<Entity> {
+        let total: f32 = self.sources.iter().map(|(_, weight)| weight.max(0.0)).sum();
+        if total <= 0.0 {
+            return None;
+        }
+
+        let mut remaining = instance_random(self.seed, index, RandomStream::Source) * total;
+        let mut positive = self.sources.iter().filter(|(_, weight)| *weight > 0.0);
+        for &(source, weight) in positive.clone() {
+            if remaining < weight {
+                return Some(source);
+            }
+            remaining -= weight;
+        }
+        // Rounding can leave a sliver past the last weight
+        positive.next_back().map(|&(source, _)| source)
+    }
+
     /// Use uniform arc-length spacing (recommended for even distribution).
     pub fn uniform(mut self) -> Self {
         self.spacing = DistributionSpacing::Uniform;
@@ -71,6 +260,25 @@ impl SplineDistribution {
     }
 }
 
+/// Which edge of a road to place instances on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum RoadSide {
+    /// The profile vertex with the lowest X (left when facing along the spline).
+    #[default]
+    Left,
+    /// The profile vertex with the highest X (right when facing along the spline).
+    Right,
+}
+
+/// Reference to a road edge used by [`SplineDistribution::road_edge`].
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct RoadEdge {
+    /// The entity with the [`SplineRoad`](crate::road::SplineRoad) component.
+    pub road: Entity,
+    /// Which edge of the road to use.
+    pub side: RoadSide,
+}
+
 /// How to orient distributed entities along the spline.
 #[derive(Debug, Clone, Copy, PartialEq, Reflect, Default)]
 pub enum DistributionOrientation {
@@ -83,6 +291,21 @@ pub enum DistributionOrientation {
         /// The up vector to use for orientation (typically `Vec3::Y`).
         up: Vec3,
     },
+    /// Orient the entity's forward direction (-Z) toward a fixed world-space point.
+    LookAt {
+        /// The world-space point to face.
+        target: Vec3,
+        /// The up vector used to determine roll (typically `Vec3::Y`).
+        up: Vec3,
+    },
+    /// Orient the entity's forward direction (-Z) toward another entity.
+    /// Instances are updated when the target entity moves.
+    LookAtEntity {
+        /// The entity to face (uses its `GlobalTransform`).
+        target: Entity,
+        /// The up vector used to determine roll (typically `Vec3::Y`).
+        up: Vec3,
+    },
 }
 
 impl DistributionOrientation {
@@ -95,6 +318,16 @@ impl DistributionOrientation {
     pub fn align_to_tangent_with_up(up: Vec3) -> Self {
         Self::AlignToTangent { up }
     }
+
+    /// Create a LookAt orientation facing a world-space point with Y as up.
+    pub fn look_at(target: Vec3) -> Self {
+        Self::LookAt { target, up: Vec3::Y }
+    }
+
+    /// Create a LookAtEntity orientation facing an entity with Y as up.
+    pub fn look_at_entity(target: Entity) -> Self {
+        Self::LookAtEntity { target, up: Vec3::Y }
+    }
 }
 
 /// How to space distributed entities along the spline.
@@ -109,6 +342,11 @@ pub enum DistributionSpacing {
     /// Faster but entities will bunch up in areas with closely
     /// spaced control points.
     Parametric,
+    /// One instance every given arc-length distance, starting at the
+    /// beginning of the spline.
+    /// The count is `floor(length / distance) + 1`, capped at 10,000, and `count`
+    /// is ignored. Distances are measured in world space, like `min_spacing`.
+    FixedDistance(f32),
 }
 
 /// Marker component for entities that serve as distribution templates.
@@ -141,4 +379,21 @@ pub(crate) struct DistributionState {
     pub cached_count: usize,
     /// Cached source to detect changes.
     pub cached_source: Entity,
+    /// Cached road edge to detect road profile and frame changes.
+    pub cached_road_edge: Option<ResolvedRoadEdge>,
+    /// Instance t values, with `min_spacing` applied, reused until the
+    /// distribution or spline changes.
+    pub cached_t_values: Vec<f32>,
+}
+
+/// A [`RoadEdge`] resolved against its road, placing instances where the road mesh
+/// draws the edge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ResolvedRoadEdge {
+    /// Profile-space position of the edge.
+    pub offset: Vec2,
+    /// Whether the road uses rotation-minimizing frames.
+    pub use_rmf: bool,
+    /// Number of segments the road mesh is extruded with.
+    pub segments: usize,
 }
@@ -1,11 +1,13 @@
 mod components;
 mod projection;
+mod random;
 mod systems;
 
 pub use components::*;
 pub use projection::NeedsInstanceProjection;
 
 use bevy::prelude::*;
+#[cfg(any(feature = "avian3d", feature = "mesh_raycast"))]
 use bevy::transform::TransformSystems;
 
 use crate::spline::SplinePlugin;
@@ -56,6 +58,7 @@ use crate::spline::SplinePlugin;
 ///
 /// - `Uniform`: Even arc-length spacing (default, recommended)
 /// - `Parametric`: Based on spline t parameter (faster but uneven)
+/// - `FixedDistance`: One instance every given distance; the count follows the spline length
 pub struct SplineDistributionPlugin;
 
 impl Plugin for SplineDistributionPlugin {
@@ -68,6 +71,8 @@ impl Plugin for SplineDistributionPlugin {
         app.register_type::<SplineDistribution>()
             .register_type::<DistributionOrientation>()
             .register_type::<DistributionSpacing>()
+            .register_type::<RoadSide>()
+            .register_type::<RoadEdge>()
             .register_type::<DistributionSource>()
             .register_type::<DistributedInstance>()
             .add_systems(
@@ -82,11 +87,22 @@ impl Plugin for SplineDistributionPlugin {
 
         // Run projection in PostUpdate after transform propagation.
         // Only runs when avian3d physics is available.
+        #[cfg(feature = "avian3d")]
         app.add_systems(
             PostUpdate,
             projection::project_distributed_instances
                 .after(TransformSystems::Propagate)
                 .run_if(projection::physics_available),
         );
+
+        // Without physics, fall back to ray casting against surface meshes
+        #[cfg(feature = "mesh_raycast")]
+        {
+            let project = projection::project_distributed_instances_with_mesh_raycast
+                .after(TransformSystems::Propagate);
+            #[cfg(feature = "avian3d")]
+            let project = project.run_if(not(projection::physics_available));
+            app.add_systems(PostUpdate, project);
+        }
     }
 }
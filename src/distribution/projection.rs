@@ -1,14 +1,27 @@
 //! Surface projection for distributed instances.
+// Without a ray casting backend nothing is projected, leaving the shared code unused
+#![cfg_attr(
+    not(any(feature = "avian3d", feature = "mesh_raycast")),
+    allow(dead_code, unused_imports)
+)]
 
+#[cfg(feature = "avian3d")]
 use avian3d::prelude::*;
+#[cfg(feature = "mesh_raycast")]
+use bevy::picking::mesh_picking::ray_cast::MeshRayCast;
 use bevy::prelude::*;
 
-use crate::surface::{cast_projection_ray, SplineMeshProjection};
+#[cfg(feature = "avian3d")]
+use crate::surface::cast_projection_ray;
+use crate::surface::{RawProjectionHit, SplineMeshProjection};
+#[cfg(feature = "mesh_raycast")]
+use crate::surface::{cast_projection_ray_mesh, ProjectionSurface};
 
 use super::{DistributedInstance, SplineDistribution};
 
 /// Run condition that checks if avian3d physics is available.
 /// We check for the Gravity resource which is always present when PhysicsPlugins is added.
+#[cfg(feature = "avian3d")]
 pub fn physics_available(gravity: Option<Res<Gravity>>) -> bool {
     gravity.is_some()
 }
@@ -19,6 +32,7 @@ pub fn physics_available(gravity: Option<Res<Gravity>>) -> bool {
 pub struct NeedsInstanceProjection;
 
 /// System to project distributed instances onto surfaces below.
+#[cfg(feature = "avian3d")]
 pub fn project_distributed_instances(
     mut commands: Commands,
     spatial_query: SpatialQuery,
@@ -26,30 +40,13 @@ pub fn project_distributed_instances(
     mut instances: Query<(Entity, &DistributedInstance, &mut Transform), With<NeedsInstanceProjection>>,
 ) {
     for (instance_entity, instance, mut transform) in &mut instances {
-        let Ok(config) = distributions.get(instance.distribution) else {
+        let Some(config) = enabled_projection(&distributions, instance.distribution) else {
             commands.entity(instance_entity).remove::<NeedsInstanceProjection>();
             continue;
         };
 
-        if !config.enabled {
-            commands.entity(instance_entity).remove::<NeedsInstanceProjection>();
-            continue;
-        }
-
         if let Some(hit) = cast_projection_ray(&spatial_query, transform.translation, config) {
-            transform.translation = hit.with_normal_offset(config.normal_offset);
-
-            // Optionally align rotation to surface normal
-            if config.align_to_normal {
-                let normal = hit.normal;
-                let forward = transform.forward();
-                let right = normal.cross(*forward).normalize_or_zero();
-                if right.length_squared() > 0.001 {
-                    let corrected_forward = right.cross(normal).normalize();
-                    transform.rotation =
-                        Quat::from_mat3(&Mat3::from_cols(right, normal, corrected_forward));
-                }
-            }
+            apply_projection_hit(&mut transform, &hit, config);
 
             // Projection succeeded - remove marker
             commands.entity(instance_entity).remove::<NeedsInstanceProjection>();
@@ -57,3 +54,92 @@ pub fn project_distributed_instances(
         // If no hit, keep marker to retry next frame (physics might not be ready)
     }
 }
+
+/// System to project distributed instances onto [`ProjectionSurface`] meshes below,
+/// for when avian3d physics isn't available.
+#[cfg(feature = "mesh_raycast")]
+pub fn project_distributed_instances_with_mesh_raycast(
+    mut commands: Commands,
+    mut mesh_ray_cast: MeshRayCast,
+    surfaces: Query<(), With<ProjectionSurface>>,
+    distributions: Query<&SplineMeshProjection, With<SplineDistribution>>,
+    mut instances: Query<(Entity, &DistributedInstance, &mut Transform), With<NeedsInstanceProjection>>,
+) {
+    for (instance_entity, instance, mut transform) in &mut instances {
+        let Some(config) = enabled_projection(&distributions, instance.distribution) else {
+            commands.entity(instance_entity).remove::<NeedsInstanceProjection>();
+            continue;
+        };
+
+        let point = transform.translation;
+        if let Some(hit) = cast_projection_ray_mesh(&mut mesh_ray_cast, &surfaces, point, config) {
+            apply_projection_hit(&mut transform, &hit, config);
+            commands.entity(instance_entity).remove::<NeedsInstanceProjection>();
+        }
+        // If no hit, keep marker to retry next frame (the surface might still be loading)
+    }
+}
+
+/// The projection settings of `distribution`, if it has them and they're enabled.
+fn enabled_projection<'a>(
+    distributions: &'a Query<&SplineMeshProjection, With<SplineDistribution>>,
+    distribution: Entity,
+) -> Option<&'a SplineMeshProjection> {
+    distributions.get(distribution).ok().filter(|config| config.enabled)
+}
+
+/// Move an instance onto a projection hit, optionally aligning it to the surface.
+fn apply_projection_hit(
+    transform: &mut Transform,
+    hit: &RawProjectionHit,
+    config: &SplineMeshProjection,
+) {
+    transform.translation = hit.with_normal_offset(config.normal_offset);
+
+    // Optionally align rotation to surface normal. Tilting by the shortest arc from
+    // the current up keeps the instance's orientation and jitter about that axis.
+    if config.align_to_normal {
+        let normal = hit.normal.normalize_or_zero();
+        if normal != Vec3::ZERO {
+            transform.rotation =
+                Quat::from_rotation_arc(*transform.up(), normal) * transform.rotation;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_to_normal_keeps_yaw_jitter() {
+        let config = SplineMeshProjection {
+            align_to_normal: true,
+            ..default()
+        };
+        let hit = RawProjectionHit {
+            position: Vec3::ZERO,
+            normal: Vec3::new(0.3, 1.0, 0.2).normalize(),
+            distance: 1.0,
+        };
+
+        // Two instances facing along the spline, one with extra yaw jitter
+        let base = Transform::from_rotation(Quat::from_rotation_y(0.4));
+        let yaw = 0.7;
+        let mut plain = base;
+        let mut jittered = base;
+        jittered.rotation *= Quat::from_rotation_y(yaw);
+
+        apply_projection_hit(&mut plain, &hit, &config);
+        apply_projection_hit(&mut jittered, &hit, &config);
+
+        // Both stand on the surface, still turned `yaw` apart about its normal
+        for transform in [plain, jittered] {
+            assert!(transform.up().dot(hit.normal) > 0.9999);
+        }
+        let difference = plain.rotation.inverse() * jittered.rotation;
+        let (axis, angle) = difference.to_axis_angle();
+        assert!((angle - yaw).abs() < 1e-4, "angle {angle}");
+        assert!(axis.dot(Vec3::Y) > 0.9999, "axis {axis}");
+    }
+}
@@ -0,0 +1,27 @@
+//! Deterministic per-instance randomness for distributions.
+
+/// Independent random values drawn for the same instance.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum RandomStream {
+    Yaw,
+    Scale,
+    Source,
+}
+
+/// Deterministic random value in 0.0 - 1.0 for an instance.
+///
+/// Depends only on the seed, the instance index and the stream, so layouts are
+/// reproduced exactly when a scene is reloaded.
+pub(super) fn instance_random(seed: u64, index: usize, stream: RandomStream) -> f32 {
+    let key = splitmix64(seed ^ splitmix64(index as u64)).wrapping_add(stream as u64);
+    // The top 24 bits fill an f32 mantissa exactly
+    (splitmix64(key) >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// One step of the SplitMix64 generator, used as a hash.
+fn splitmix64(state: u64) -> u64 {
+    let mut z = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -1,18 +1,28 @@
+#[cfg(feature = "avian3d")]
 use avian3d::prelude::Collider;
 use bevy::prelude::*;
 
 use crate::geometry::CoordinateFrame;
+use crate::road::{
+    extract_mesh_profile, road_samples, road_segments, transport_road_frame, RoadSample,
+    SplineRoad,
+};
 use crate::spline::{ArcLengthTable, Spline};
 use crate::surface::SplineMeshProjection;
 
 use super::{
     DistributedInstance, DistributionOrientation, DistributionSource, DistributionSpacing,
-    DistributionState, SplineDistribution,
+    DistributionState, ResolvedRoadEdge, RoadEdge, RoadSide, SplineDistribution,
 };
 use super::projection::NeedsInstanceProjection;
+use super::random::{instance_random, RandomStream};
+
+/// Maximum relaxation passes when enforcing `min_spacing`.
+const MIN_SPACING_ITERATIONS: usize = 16;
 
-/// Number of samples used to compute arc length lookup table.
-const ARC_LENGTH_SAMPLES: usize = 256;
+/// Upper bound on the instances placed with [`DistributionSpacing::FixedDistance`],
+/// so a tiny distance can't spawn an unbounded number of entities.
+const MAX_FIXED_DISTANCE_INSTANCES: usize = 10_000;
 
 /// Hide entities marked as distribution sources.
 pub fn hide_source_entities(
@@ -25,20 +35,24 @@ pub fn hide_source_entities(
 
 /// Update distributed instances when distribution or spline changes.
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 pub fn update_distributions(
     mut commands: Commands,
-    distributions: Query<(Entity, &SplineDistribution, Option<&DistributionState>)>,
+    mut distributions: Query<(Entity, &SplineDistribution, Option<&mut DistributionState>)>,
     splines: Query<(&Spline, &GlobalTransform)>,
     sources: Query<(
         Option<&Mesh3d>,
         Option<&MeshMaterial3d<StandardMaterial>>,
-        Option<&Collider>,
         Option<&Children>,
     )>,
+    #[cfg(feature = "avian3d")] source_colliders: Query<&Collider>,
     mut instances: Query<(&mut Transform, &DistributedInstance)>,
     changed_splines: Query<Entity, Or<(Changed<Spline>, Changed<GlobalTransform>)>>,
     changed_distributions: Query<Entity, Changed<SplineDistribution>>,
     projection_query: Query<(), With<SplineMeshProjection>>,
+    look_targets: Query<Ref<GlobalTransform>>,
+    roads: Query<&SplineRoad>,
+    meshes: Res<Assets<Mesh>>,
 ) {
     // Collect changed spline entities for quick lookup
     let changed_spline_set: std::collections::HashSet<Entity> =
@@ -46,7 +60,7 @@ pub fn update_distributions(
     let changed_dist_set: std::collections::HashSet<Entity> =
         changed_distributions.iter().collect();
 
-    for (dist_entity, distribution, state) in &distributions {
+    for (dist_entity, distribution, state) in &mut distributions {
         if !distribution.enabled {
             continue;
         }
@@ -59,62 +73,135 @@ pub fn update_distributions(
             continue;
         }
 
+        let dist_changed = changed_dist_set.contains(&dist_entity);
+        let spline_changed = changed_spline_set.contains(&distribution.spline);
+
+        // Resolve the world-space look-at targets, if any, for the middle
+        // instances and the endpoints
+        let resolve_look_target = |orientation| match orientation {
+            DistributionOrientation::LookAt { target, .. } => Some((target, false)),
+            DistributionOrientation::LookAtEntity { target, .. } => look_targets
+                .get(target)
+                .ok()
+                .map(|transform| (transform.translation(), transform.is_changed())),
+            _ => None,
+        };
+        let look_target = resolve_look_target(distribution.orientation);
+        let endpoint_look_target = distribution
+            .endpoint_orientation
+            .map_or(look_target, resolve_look_target);
+        let look_target_changed = [look_target, endpoint_look_target]
+            .iter()
+            .any(|target| target.is_some_and(|(_, changed)| changed));
+
+        // Resolve the road edge, which moves when the road's profile changes
+        let road_edge = distribution
+            .road_edge
+            .and_then(|edge| resolve_road_edge(edge, spline, &roads, &meshes));
+        let road_edge_changed = state.as_ref().is_some_and(|s| s.cached_road_edge != road_edge);
+
+        // The t values only move with the distribution or the spline; reuse the
+        // cached ones otherwise, and skip the distribution when nothing changed
+        let t_values = match state.as_ref() {
+            Some(state) if !dist_changed && !spline_changed => {
+                if !look_target_changed && !road_edge_changed {
+                    continue;
+                }
+                state.cached_t_values.clone()
+            }
+            _ => compute_t_values(spline, spline_transform, distribution),
+        };
+
+        // With fixed distance spacing the count depends on the spline's length
+        let count = t_values.len();
+
         // Check if we need to rebuild instances
         let needs_rebuild = state.is_none()
-            || changed_dist_set.contains(&dist_entity)
-            || state.as_ref().is_some_and(|s| {
-                s.cached_count != distribution.count || s.cached_source != distribution.source
-            });
+            || dist_changed
+            || state
+                .as_ref()
+                .is_some_and(|s| s.cached_count != count || s.cached_source != distribution.source);
+
+        let look_target_for = |index: usize| {
+            let target = if SplineDistribution::is_endpoint(index, count) {
+                endpoint_look_target
+            } else {
+                look_target
+            };
+            target.map(|(position, _)| position)
+        };
 
         // Check if we need to update transforms
-        let needs_transform_update =
-            needs_rebuild || changed_spline_set.contains(&distribution.spline);
+        let needs_transform_update = needs_rebuild
+            || spline_changed
+            || look_target_changed
+            || road_edge_changed;
 
-        // Compute t values based on spacing mode
-        let t_values = match distribution.spacing {
-            DistributionSpacing::Uniform => compute_uniform_t_values(spline, distribution.count),
-            DistributionSpacing::Parametric => compute_parametric_t_values(distribution.count),
-        };
+        let road_edge_frames = road_edge.map(|edge| RoadEdgeFrames::new(edge, spline));
 
         if needs_rebuild {
-            // Despawn old instances
-            if let Some(state) = state {
-                for &instance_entity in &state.instances {
-                    if let Ok(mut entity_commands) = commands.get_entity(instance_entity) {
-                        entity_commands.despawn();
-                    }
+            // Keep existing instances where possible; only the difference in count is
+            // spawned or despawned, so instances keep their entity and index
+            let mut reused = state.map(|s| s.instances.clone()).unwrap_or_default();
+            for instance_entity in reused.split_off(count.min(reused.len())) {
+                if let Ok(mut entity_commands) = commands.get_entity(instance_entity) {
+                    entity_commands.despawn();
                 }
             }
 
-            // Spawn new instances
-            let mut new_instances = Vec::with_capacity(distribution.count);
-
-            // Get source components to clone
-            let source_data = sources.get(distribution.source).ok();
+            let mut new_instances = Vec::with_capacity(count);
 
             for (i, &t) in t_values.iter().enumerate() {
-                let transform = calculate_transform(spline, spline_transform, t, distribution);
-
-                let mut entity_commands = commands.spawn((
-                    transform,
-                    DistributedInstance {
-                        distribution: dist_entity,
-                        index: i,
-                    },
-                    Visibility::default(),
-                ));
-
-                // Clone visual and physics components from source
-                if let Some((mesh, material, collider, _children)) = source_data {
-                    if let Some(mesh) = mesh {
-                        entity_commands.insert(mesh.clone());
-                    }
-                    if let Some(material) = material {
-                        entity_commands.insert(material.clone());
-                    }
-                    if let Some(collider) = collider {
-                        entity_commands.insert(collider.clone());
-                    }
+                let transform = apply_jitter(
+                    calculate_transform(
+                        spline,
+                        spline_transform,
+                        t,
+                        distribution,
+                        distribution.orientation_for(i, count),
+                        look_target_for(i),
+                        road_edge_frames.as_ref(),
+                    ),
+                    distribution,
+                    i,
+                );
+
+                // Get source components to clone
+                let source = distribution.source_for(i, count);
+                let source_data = sources.get(source).ok();
+
+                let existing = reused
+                    .get(i)
+                    .copied()
+                    .filter(|&instance_entity| instances.contains(instance_entity));
+                let mut entity_commands = match existing {
+                    Some(instance_entity) => commands.entity(instance_entity),
+                    None => commands.spawn((
+                        DistributedInstance {
+                            distribution: dist_entity,
+                            index: i,
+                        },
+                        Visibility::default(),
+                    )),
+                };
+                entity_commands.insert(transform);
+
+                // Clone visual and physics components from source, replacing those
+                // of a previous source on reused instances
+                if let Some((mesh, material, _children)) = source_data {
+                    match mesh {
+                        Some(mesh) => entity_commands.insert(mesh.clone()),
+                        None => entity_commands.remove::<Mesh3d>(),
+                    };
+                    match material {
+                        Some(material) => entity_commands.insert(material.clone()),
+                        None => entity_commands.remove::<MeshMaterial3d<StandardMaterial>>(),
+                    };
+                    #[cfg(feature = "avian3d")]
+                    match source_colliders.get(source) {
+                        Ok(collider) => entity_commands.insert(collider.clone()),
+                        Err(_) => entity_commands.remove::<Collider>(),
+                    };
                 }
 
                 // Mark for surface projection if enabled
@@ -128,16 +215,31 @@ pub fn update_distributions(
             // Update state
             commands.entity(dist_entity).insert(DistributionState {
                 instances: new_instances,
-                cached_count: distribution.count,
+                cached_count: count,
                 cached_source: distribution.source,
+                cached_road_edge: road_edge,
+                cached_t_values: t_values,
             });
         } else if needs_transform_update {
             // Just update transforms on existing instances
-            if let Some(state) = state {
+            if let Some(mut state) = state {
+                state.cached_road_edge = road_edge;
                 for (i, &instance_entity) in state.instances.iter().enumerate() {
                     if let Ok((mut transform, _)) = instances.get_mut(instance_entity) {
                         let t = t_values.get(i).copied().unwrap_or(0.5);
-                        *transform = calculate_transform(spline, spline_transform, t, distribution);
+                        *transform = apply_jitter(
+                            calculate_transform(
+                                spline,
+                                spline_transform,
+                                t,
+                                distribution,
+                                distribution.orientation_for(i, count),
+                                look_target_for(i),
+                                road_edge_frames.as_ref(),
+                            ),
+                            distribution,
+                            i,
+                        );
 
                         // Mark for surface projection if enabled
                         if projection_query.get(dist_entity).is_ok() {
@@ -145,57 +247,285 @@ pub fn update_distributions(
                         }
                     }
                 }
+                state.cached_t_values = t_values;
             }
         }
     }
 }
 
-/// Compute t values for uniform distribution.
-fn compute_uniform_t_values(spline: &Spline, count: usize) -> Vec<f32> {
-    let table = ArcLengthTable::compute(spline, ARC_LENGTH_SAMPLES);
-    table.uniform_t_values(count)
+/// Resolve a road's edge, if the road and its mesh are loaded.
+fn resolve_road_edge(
+    edge: RoadEdge,
+    spline: &Spline,
+    roads: &Query<&SplineRoad>,
+    meshes: &Assets<Mesh>,
+) -> Option<ResolvedRoadEdge> {
+    let road = roads.get(edge.road).ok()?;
+    let profile = extract_mesh_profile(meshes.get(&road.segment_mesh)?, false)?;
+    // The profile is sorted by X
+    let vertex = match edge.side {
+        RoadSide::Left => profile.first(),
+        RoadSide::Right => profile.last(),
+    }?;
+    Some(ResolvedRoadEdge {
+        offset: vertex.position.xy(),
+        use_rmf: road.use_rmf,
+        segments: road_segments(spline, road.segments_per_curve),
+    })
+}
+
+/// A road edge with the frames the road mesh places its profile in.
+struct RoadEdgeFrames {
+    offset: Vec2,
+    /// Rows of the road, when it uses rotation-minimizing frames.
+    rmf_samples: Option<Vec<RoadSample>>,
+}
+
+impl RoadEdgeFrames {
+    fn new(edge: ResolvedRoadEdge, spline: &Spline) -> Self {
+        Self {
+            offset: edge.offset,
+            rmf_samples: edge
+                .use_rmf
+                .then(|| road_samples(spline, edge.segments, true))
+                .flatten(),
+        }
+    }
+
+    /// Offset from the spline to the edge at `t`, in the spline's local space.
+    fn offset_at(&self, t: f32, position: Vec3, tangent: Vec3) -> Vec3 {
+        let frame = self
+            .rmf_samples
+            .as_deref()
+            .and_then(|samples| transport_road_frame(samples, t, position, tangent))
+            .unwrap_or_else(|| CoordinateFrame::from_tangent(tangent.normalize_or_zero()));
+        frame.transform_profile_point(self.offset.x, self.offset.y)
+    }
+}
+
+/// Compute the instance t values for the distribution's spacing mode, with
+/// `min_spacing` enforced.
+fn compute_t_values(
+    spline: &Spline,
+    spline_transform: &GlobalTransform,
+    distribution: &SplineDistribution,
+) -> Vec<f32> {
+    let samples = distribution
+        .arc_length_samples
+        .unwrap_or_else(|| spline.default_arc_length_samples());
+    let range = distribution.t_range();
+    let mut t_values = match distribution.spacing {
+        DistributionSpacing::Uniform => {
+            compute_uniform_t_values(spline, distribution.count, samples, range)
+        }
+        DistributionSpacing::Parametric => compute_parametric_t_values(distribution.count, range),
+        DistributionSpacing::FixedDistance(distance) => {
+            compute_fixed_distance_t_values(spline, spline_transform, distance, samples, range)
+        }
+    };
+
+    if let Some(min_spacing) = distribution.min_spacing {
+        enforce_min_spacing(spline, spline_transform, &mut t_values, min_spacing);
+        // Keep nudged instances within the range
+        for t in &mut t_values {
+            *t = t.clamp(range.0, range.1);
+        }
+    }
+    t_values
+}
+
+/// Compute t values for uniform distribution within the t `range`.
+fn compute_uniform_t_values(
+    spline: &Spline,
+    count: usize,
+    samples: usize,
+    (start, end): (f32, f32),
+) -> Vec<f32> {
+    let table = ArcLengthTable::compute(spline, samples);
+    let start_length = table.t_to_length(start);
+    let length = table.length_between(start, end);
+    if length <= 0.0 {
+        // Fall back to parametric spacing
+        return compute_parametric_t_values(count, (start, end));
+    }
+
+    let fraction = |i: usize| match count {
+        1 => 0.5,
+        _ => i as f32 / (count - 1) as f32,
+    };
+    (0..count)
+        .map(|i| table.length_to_t(start_length + length * fraction(i)))
+        .collect()
 }
 
-/// Compute t values for parametric distribution.
-fn compute_parametric_t_values(count: usize) -> Vec<f32> {
+/// Compute t values one world-space `distance` apart along the arc length, starting
+/// at the start of the t `range`.
+///
+/// Always returns at least one value, even when `distance` exceeds the length of the
+/// range, and at most [`MAX_FIXED_DISTANCE_INSTANCES`].
+fn compute_fixed_distance_t_values(
+    spline: &Spline,
+    spline_transform: &GlobalTransform,
+    distance: f32,
+    samples: usize,
+    (start, end): (f32, f32),
+) -> Vec<f32> {
+    // Splines are affine invariant, so the world-space curve keeps its parameters
+    let mut world_spline = spline.clone();
+    for point in &mut world_spline.control_points {
+        *point = spline_transform.transform_point(*point);
+    }
+    let table = ArcLengthTable::compute(&world_spline, samples);
+    let start_length = table.t_to_length(start);
+    let length = table.length_between(start, end);
+    if distance <= 0.0 || length <= 0.0 {
+        return vec![start];
+    }
+
+    let count = ((length / distance).floor() as usize + 1).min(MAX_FIXED_DISTANCE_INSTANCES);
+    (0..count)
+        .map(|i| table.length_to_t(start_length + i as f32 * distance))
+        .collect()
+}
+
+/// Compute t values for parametric distribution within the t `range`.
+fn compute_parametric_t_values(count: usize, (start, end): (f32, f32)) -> Vec<f32> {
     if count == 0 {
         return Vec::new();
     }
     if count == 1 {
-        return vec![0.5];
+        return vec![(start + end) * 0.5];
     }
 
     (0..count)
-        .map(|i| i as f32 / (count - 1) as f32)
+        .map(|i| start + (end - start) * i as f32 / (count - 1) as f32)
         .collect()
 }
 
+/// Nudge t values apart until no two instance positions are closer than `min_spacing`.
+///
+/// Each pass pushes overlapping pairs apart along the spline by half their overlap,
+/// converted to t using the local world-space speed of the curve. Instances keep
+/// their order, and t values stay within 0.0 - 1.0 (open) or wrap (closed).
+fn enforce_min_spacing(
+    spline: &Spline,
+    spline_transform: &GlobalTransform,
+    t_values: &mut [f32],
+    min_spacing: f32,
+) {
+    if min_spacing <= 0.0 || t_values.len() < 2 {
+        return;
+    }
+    // Tangents are per segment, scale them to the whole-spline t range
+    let segments = spline.segment_count() as f32;
+
+    for _ in 0..MIN_SPACING_ITERATIONS {
+        let samples: Vec<(Vec3, f32)> = t_values
+            .iter()
+            .map(|&t| {
+                let (position, tangent) = spline
+                    .evaluate_pos_tan(t)
+                    .unwrap_or((Vec3::ZERO, Vec3::ZERO));
+                let world_tangent = spline_transform.affine().transform_vector3(tangent);
+                let speed = world_tangent.length() * segments;
+                (spline_transform.transform_point(position), speed)
+            })
+            .collect();
+
+        let mut nudges = vec![0.0; t_values.len()];
+        let mut overlapping = false;
+        for i in 0..samples.len() {
+            for j in (i + 1)..samples.len() {
+                let overlap = min_spacing - samples[i].0.distance(samples[j].0);
+                if overlap <= 0.0 {
+                    continue;
+                }
+                overlapping = true;
+                // Push the earlier instance back and the later one forward
+                let push = overlap * 0.5;
+                nudges[i] -= push / samples[i].1.max(1e-4);
+                nudges[j] += push / samples[j].1.max(1e-4);
+            }
+        }
+        if !overlapping {
+            break;
+        }
+
+        for (t, nudge) in t_values.iter_mut().zip(nudges) {
+            *t = if spline.closed {
+                (*t + nudge).rem_euclid(1.0)
+            } else {
+                (*t + nudge).clamp(0.0, 1.0)
+            };
+        }
+        // Wrapping past the seam moves an instance to the other end of the range;
+        // sort so instances stay in order along the spline
+        if spline.closed {
+            t_values.sort_by(f32::total_cmp);
+        }
+    }
+}
+
 /// Calculate transform for a distributed instance at parameter t.
 /// The transform is computed in world space using the spline's GlobalTransform.
+///
+/// `orientation` is the instance's orientation, with the endpoint override applied.
+/// `look_target` is the resolved world-space target for the look-at orientation modes.
 fn calculate_transform(
     spline: &Spline,
     spline_transform: &GlobalTransform,
     t: f32,
     distribution: &SplineDistribution,
+    orientation: DistributionOrientation,
+    look_target: Option<Vec3>,
+    road_edge: Option<&RoadEdgeFrames>,
 ) -> Transform {
-    // Get position in local spline space
-    let local_position = spline.evaluate(t).unwrap_or(Vec3::ZERO);
+    // Get position and tangent in local spline space
+    let (mut local_position, tangent) = spline
+        .evaluate_pos_tan(t)
+        .unwrap_or((Vec3::ZERO, Vec3::ZERO));
+
+    // Move onto the road edge using the same frame as the road mesh
+    if let Some(edge) = road_edge {
+        local_position += edge.offset_at(t, local_position, tangent);
+    }
+
+    // Look-at modes are resolved in world space
+    let look_up = match orientation {
+        DistributionOrientation::LookAt { up, .. }
+        | DistributionOrientation::LookAtEntity { up, .. } => Some(up),
+        _ => None,
+    };
+    if let Some(up) = look_up {
+        let world_position = spline_transform.transform_point(local_position);
+        let world_rotation = look_target.map_or(Quat::IDENTITY, |target| {
+            Transform::from_translation(world_position)
+                .looking_at(target, up)
+                .rotation
+        });
+
+        return Transform {
+            translation: world_position + world_rotation * distribution.offset,
+            rotation: world_rotation,
+            scale: Vec3::ONE,
+        };
+    }
 
     // Calculate local rotation based on orientation mode
-    let local_rotation = match distribution.orientation {
+    let local_rotation = match orientation {
         DistributionOrientation::PositionOnly => Quat::IDENTITY,
         DistributionOrientation::AlignToTangent { up } => {
-            if let Some(tangent) = spline.evaluate_tangent(t) {
-                let frame = CoordinateFrame::from_tangent_with_up(tangent, up);
-                if frame.is_valid() {
-                    frame.to_rotation()
-                } else {
-                    Quat::IDENTITY
-                }
+            let frame = CoordinateFrame::from_tangent_with_up(tangent, up);
+            if frame.is_valid() {
+                frame.to_rotation()
             } else {
                 Quat::IDENTITY
             }
         }
+        // Handled above
+        DistributionOrientation::LookAt { .. } | DistributionOrientation::LookAtEntity { .. } => {
+            Quat::IDENTITY
+        }
     };
 
     // Apply offset in local space
@@ -213,6 +543,28 @@ fn calculate_transform(
     }
 }
 
+/// Apply the distribution's random scale and yaw jitter to the instance at `index`.
+///
+/// The yaw is applied about the instance's own Y axis, after orientation, so it
+/// composes with any orientation mode.
+fn apply_jitter(
+    mut transform: Transform,
+    distribution: &SplineDistribution,
+    index: usize,
+) -> Transform {
+    if let Some(max_angle) = distribution.yaw_jitter {
+        let random = instance_random(distribution.seed, index, RandomStream::Yaw);
+        let yaw = (random * 2.0 - 1.0) * max_angle;
+        transform.rotation *= Quat::from_rotation_y(yaw);
+    }
+    if let Some((min, max)) = distribution.scale_jitter {
+        let random = instance_random(distribution.seed, index, RandomStream::Scale);
+        let scale = min + (max - min) * random;
+        transform.scale = Vec3::splat(scale);
+    }
+    transform
+}
+
 /// Cleanup instances when distribution is removed.
 pub fn cleanup_distributions(
     mut commands: Commands,
@@ -239,3 +591,413 @@ pub fn cleanup_distributions(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_enforce_min_spacing_separates_bunched_instances() {
+        // Parametric spacing bunches instances on the short outer segments
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 0.0, 1.0, 10.0, 11.0, 11.0]
+                .map(|x| Vec3::new(x, 0.0, 0.0))
+                .to_vec(),
+        );
+        let transform = GlobalTransform::IDENTITY;
+        let mut t_values = compute_parametric_t_values(7, (0.0, 1.0));
+        let before = t_values.clone();
+
+        enforce_min_spacing(&spline, &transform, &mut t_values, 1.5);
+
+        let positions: Vec<Vec3> = t_values
+            .iter()
+            .map(|&t| spline.evaluate(t).unwrap())
+            .collect();
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                assert!(positions[i].distance(positions[j]) > 1.45);
+            }
+        }
+
+        // Same input gives the same result
+        let mut again = before;
+        enforce_min_spacing(&spline, &transform, &mut again, 1.5);
+        assert_eq!(again, t_values);
+    }
+
+    #[test]
+    fn test_enforce_min_spacing_keeps_order_across_closed_seam() {
+        // A square loop with instances bunched just before the seam
+        let mut spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::ZERO,
+                Vec3::new(10.0, 0.0, 0.0),
+                Vec3::new(10.0, 0.0, 10.0),
+                Vec3::new(0.0, 0.0, 10.0),
+            ],
+        );
+        spline.closed = true;
+        let transform = GlobalTransform::IDENTITY;
+        let mut t_values = vec![0.3, 0.6, 0.97, 0.98, 0.99];
+
+        enforce_min_spacing(&spline, &transform, &mut t_values, 2.0);
+
+        // The last instances are pushed past the seam but stay in t order
+        assert!(t_values[0] < 0.3, "{t_values:?}");
+        assert!(t_values.is_sorted(), "{t_values:?}");
+        let positions: Vec<Vec3> = t_values
+            .iter()
+            .map(|&t| spline.evaluate(t).unwrap())
+            .collect();
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                assert!(positions[i].distance(positions[j]) > 1.9, "{t_values:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_road_edge_follows_rmf_road_frames() {
+        // Climbs straight up and comes back down the other side
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, -4.0),
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 4.0, 2.0),
+                Vec3::new(0.0, 8.0, 0.0),
+                Vec3::new(0.0, 8.0, -4.0),
+            ],
+        );
+        let profile =
+            [-1.0, 1.0].map(|x| crate::road::ProfileVertex::new(Vec3::new(x, 0.0, 0.0), None));
+        let options = crate::road::RoadGeometryOptions {
+            use_rmf: true,
+            ..default()
+        };
+        let geometry = crate::road::road_geometry(&spline, &profile, 64, &options).unwrap();
+
+        let edge = RoadEdgeFrames::new(
+            ResolvedRoadEdge {
+                offset: Vec2::X,
+                use_rmf: true,
+                segments: 64,
+            },
+            &spline,
+        );
+        let mut flipped = false;
+        for (row, sample) in geometry.samples.iter().enumerate() {
+            let (position, tangent) = spline.evaluate_pos_tan(sample.t).unwrap();
+            let placed = position + edge.offset_at(sample.t, position, tangent);
+            let drawn = Vec3::from_array(geometry.positions[row * 2 + 1]);
+            assert!(placed.distance(drawn) < 1e-3, "row {row}: {placed} vs {drawn}");
+
+            let world_up = CoordinateFrame::from_tangent(tangent.normalize_or_zero());
+            let world_up_edge = position + world_up.transform_profile_point(1.0, 0.0);
+            flipped |= world_up_edge.distance(drawn) > 0.5;
+        }
+        // The world-up frames would have put the edge elsewhere
+        assert!(flipped);
+    }
+
+    #[test]
+    fn test_fixed_distance_t_values() {
+        // Straight line from x = 1 to x = 10 with t proportional to x
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [-2.0, 1.0, 4.0, 7.0, 10.0, 13.0]
+                .map(|x| Vec3::new(x, 0.0, 0.0))
+                .to_vec(),
+        );
+
+        let identity = GlobalTransform::IDENTITY;
+
+        let t_values = compute_fixed_distance_t_values(&spline, &identity, 2.0, 256, (0.0, 1.0));
+        assert_eq!(t_values.len(), 5);
+        for (i, &t) in t_values.iter().enumerate() {
+            let x = spline.evaluate(t).unwrap().x;
+            assert!((x - (1.0 + 2.0 * i as f32)).abs() < 0.01);
+        }
+
+        // Spacing longer than the spline still places one instance at the start
+        assert_eq!(
+            compute_fixed_distance_t_values(&spline, &identity, 20.0, 256, (0.0, 1.0)),
+            vec![0.0]
+        );
+
+        // A tiny spacing is capped instead of spawning millions of instances
+        let t_values = compute_fixed_distance_t_values(&spline, &identity, 1e-6, 256, (0.0, 1.0));
+        assert_eq!(t_values.len(), MAX_FIXED_DISTANCE_INSTANCES);
+    }
+
+    #[test]
+    fn test_fixed_distance_is_measured_in_world_space() {
+        // 9 units long locally, 18 in the world
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [-2.0, 1.0, 4.0, 7.0, 10.0, 13.0]
+                .map(|x| Vec3::new(x, 0.0, 0.0))
+                .to_vec(),
+        );
+        let transform = GlobalTransform::from_scale(Vec3::new(2.0, 1.0, 1.0));
+
+        let t_values = compute_fixed_distance_t_values(&spline, &transform, 2.0, 256, (0.0, 1.0));
+        assert_eq!(t_values.len(), 10);
+        for pair in t_values.windows(2) {
+            let [a, b] = [pair[0], pair[1]]
+                .map(|t| transform.transform_point(spline.evaluate(t).unwrap()));
+            assert!((a.distance(b) - 2.0).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_jitter_is_deterministic_and_in_range() {
+        let distribution = SplineDistribution::default()
+            .with_orientation(DistributionOrientation::align_to_tangent())
+            .with_scale_jitter(0.5, 1.5)
+            .with_yaw_jitter(0.3)
+            .with_seed(7);
+        let base = Transform::from_rotation(Quat::from_rotation_x(0.4));
+
+        let jittered: Vec<Transform> = (0..32)
+            .map(|i| apply_jitter(base, &distribution, i))
+            .collect();
+        for (i, transform) in jittered.iter().enumerate() {
+            assert_eq!(*transform, apply_jitter(base, &distribution, i));
+            assert!((0.5..=1.5).contains(&transform.scale.x));
+            // Yaw turns about the instance's own up axis, so that axis is unchanged
+            assert!(transform.up().dot(*base.up()) > 0.9999);
+            assert!(transform.forward().angle_between(*base.forward()) <= 0.3 + 1e-4);
+        }
+        assert_ne!(jittered[0], jittered[1]);
+
+        // Another seed gives another layout
+        let reseeded = distribution.clone().with_seed(8);
+        assert_ne!(apply_jitter(base, &reseeded, 0), jittered[0]);
+    }
+
+    #[test]
+    fn test_t_values_stay_within_range() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(2.0, 3.0, 0.0),
+                Vec3::new(6.0, 0.0, 1.0),
+                Vec3::new(7.0, 0.0, 0.0),
+            ],
+        );
+        let distribution = SplineDistribution::default().with_range(0.7, 0.3);
+        let range = distribution.t_range();
+        assert_eq!(range, (0.3, 0.7));
+
+        let table = ArcLengthTable::compute(&spline, 256);
+        let uniform = compute_uniform_t_values(&spline, 9, 256, range);
+        let parametric = compute_parametric_t_values(9, range);
+        let fixed =
+            compute_fixed_distance_t_values(&spline, &GlobalTransform::IDENTITY, 0.5, 256, range);
+        for t_values in [&uniform, &parametric, &fixed] {
+            assert_eq!(t_values[0], 0.3);
+            assert!(t_values.iter().all(|t| (0.3..=0.7).contains(t)));
+        }
+        assert!((uniform[8] - 0.7).abs() < 1e-4);
+        assert_eq!(parametric[8], 0.7);
+
+        // Uniform spacing measures arc length within the range only
+        let step = table.length_between(0.3, 0.7) / 8.0;
+        for pair in uniform.windows(2) {
+            assert!((table.length_between(pair[0], pair[1]) - step).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_growing_distribution_reuses_instances() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .add_systems(Update, update_distributions);
+
+        let spline = app
+            .world_mut()
+            .spawn((
+                Spline::new(
+                    SplineType::CatmullRom,
+                    vec![
+                        Vec3::ZERO,
+                        Vec3::X,
+                        Vec3::new(2.0, 1.0, 0.0),
+                        Vec3::new(3.0, 0.0, 0.0),
+                    ],
+                ),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let source = app.world_mut().spawn(DistributionSource).id();
+        let distribution = app
+            .world_mut()
+            .spawn(SplineDistribution::new(spline, source, 5))
+            .id();
+
+        let instances = |app: &App| {
+            app.world()
+                .get::<DistributionState>(distribution)
+                .unwrap()
+                .instances
+                .clone()
+        };
+
+        app.update();
+        let before = instances(&app);
+        assert_eq!(before.len(), 5);
+
+        app.world_mut()
+            .get_mut::<SplineDistribution>(distribution)
+            .unwrap()
+            .count = 8;
+        app.update();
+        let after = instances(&app);
+        assert_eq!(after.len(), 8);
+        assert_eq!(after[..5], before[..]);
+        for (i, &entity) in after.iter().enumerate() {
+            let instance = app.world().get::<DistributedInstance>(entity).unwrap();
+            assert_eq!(instance.index, i);
+        }
+
+        // Shrinking despawns only the tail
+        app.world_mut()
+            .get_mut::<SplineDistribution>(distribution)
+            .unwrap()
+            .count = 3;
+        app.update();
+        assert_eq!(instances(&app)[..], before[..3]);
+        assert!(app.world().get_entity(after[7]).is_err());
+    }
+
+    #[test]
+    fn test_t_values_are_cached_until_the_spline_changes() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .add_systems(Update, update_distributions);
+
+        let spline = app
+            .world_mut()
+            .spawn((
+                Spline::new(
+                    SplineType::CatmullRom,
+                    [0.0, 0.0, 1.0, 10.0, 11.0, 11.0]
+                        .map(|x| Vec3::new(x, 0.0, 0.0))
+                        .to_vec(),
+                ),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let source = app.world_mut().spawn(DistributionSource).id();
+        let mut settings = SplineDistribution::new(spline, source, 7);
+        settings.spacing = DistributionSpacing::Parametric;
+        settings.min_spacing = Some(1.5);
+        let distribution = app.world_mut().spawn(settings).id();
+
+        let cached_t_values = |app: &App| {
+            app.world()
+                .get::<DistributionState>(distribution)
+                .unwrap()
+                .cached_t_values
+                .clone()
+        };
+
+        app.update();
+        let relaxed = cached_t_values(&app);
+        assert_eq!(relaxed.len(), 7);
+        assert_ne!(relaxed, compute_parametric_t_values(7, (0.0, 1.0)));
+
+        // Nothing changed, so the cached values are left alone
+        app.world_mut()
+            .get_mut::<DistributionState>(distribution)
+            .unwrap()
+            .cached_t_values = vec![0.5; 7];
+        app.update();
+        assert_eq!(cached_t_values(&app), vec![0.5; 7]);
+
+        // Editing the spline recomputes them
+        app.world_mut().get_mut::<Spline>(spline).unwrap().set_changed();
+        app.update();
+        assert_eq!(cached_t_values(&app), relaxed);
+    }
+
+    #[test]
+    fn test_weighted_source_selection() {
+        let mut world = World::new();
+        let [single, a, b, gate] = [(); 4].map(|_| world.spawn(DistributionSource).id());
+
+        // Without weighted sources every instance uses `source`
+        let distribution = SplineDistribution::new(Entity::PLACEHOLDER, single, 100);
+        assert!((0..100).all(|i| distribution.source_for(i, 100) == single));
+
+        let distribution = distribution
+            .with_sources([(a, 3.0), (b, 1.0), (single, 0.0)])
+            .with_endpoint_source(gate)
+            .with_seed(3);
+        let picks: Vec<Entity> = (0..1000)
+            .map(|i| distribution.source_for(i, 1000))
+            .collect();
+        assert_eq!(picks[0], gate);
+        assert_eq!(picks[999], gate);
+        assert!(!picks.contains(&single));
+
+        // Roughly 3:1, and the same every time
+        let a_count = picks.iter().filter(|&&source| source == a).count();
+        assert!((650..850).contains(&a_count));
+        assert!((0..1000).all(|i| distribution.source_for(i, 1000) == picks[i]));
+    }
+
+    #[test]
+    fn test_fixed_distance_endpoints_use_placed_count() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .add_systems(Update, update_distributions);
+
+        // Straight line from x = 0 to x = 10, so spacing 2 places 6 instances
+        let spline = app
+            .world_mut()
+            .spawn((
+                Spline::new(
+                    SplineType::CatmullRom,
+                    [-1.0, 0.0, 10.0, 11.0]
+                        .map(|x| Vec3::new(x, 0.0, 0.0))
+                        .to_vec(),
+                ),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let source = app.world_mut().spawn(DistributionSource).id();
+        let gate_mesh = Handle::default();
+        let gate = app
+            .world_mut()
+            .spawn((DistributionSource, Mesh3d(gate_mesh)))
+            .id();
+        let distribution = app
+            .world_mut()
+            .spawn(
+                SplineDistribution::new(spline, source, 3)
+                    .with_spacing(DistributionSpacing::FixedDistance(2.0))
+                    .with_endpoint_source(gate),
+            )
+            .id();
+        app.update();
+
+        let instances = app
+            .world()
+            .get::<DistributionState>(distribution)
+            .unwrap()
+            .instances
+            .clone();
+        assert_eq!(instances.len(), 6);
+        let is_gate = |entity| app.world().get::<Mesh3d>(entity).is_some();
+        let gates: Vec<bool> = instances.iter().map(|&entity| is_gate(entity)).collect();
+        assert_eq!(gates, [true, false, false, false, false, true]);
+    }
+}
@@ -1,37 +1,55 @@
 //! Spline gizmo rendering and projection systems.
 
+#[cfg(feature = "avian3d")]
 use avian3d::prelude::*;
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemChangeTick, prelude::*};
 
-use crate::distribution::SplineDistribution;
-use crate::road::SplineRoad;
+use crate::geometry::CoordinateFrame;
 use crate::spline::{
-    get_effective_control_points, get_effective_curve_points, CachedSplineCurve,
-    ControlPointMarker, ProjectedSplineCache, SelectedControlPoint, SelectedSpline, Spline,
-    SplineProjectionConfig, SplineType, project_spline_point,
+    get_effective_control_points, get_effective_curve_points, ArcLengthTable, CachedArcLength,
+    CachedSplineCurve, ContinuityLevel, ControlPointMarker, ProjectedSplineCache,
+    SelectedControlPoint, SelectedSpline, Spline, SplineEvaluator, SplinePreview, SplineType,
+};
+#[cfg(feature = "avian3d")]
+use crate::{
+    distribution::SplineDistribution,
+    road::SplineRoad,
+    spline::{project_spline_point, SplineProjectionConfig},
+    surface::SplineMeshProjection,
 };
-use crate::surface::SplineMeshProjection;
 
-use super::{EditorSettings, SplineXRayGizmos};
+use super::{sample_screen_space, EditorSettings, SelectionState, SplineXRayGizmos};
 
 /// Run condition that checks if avian3d physics is available.
 /// We check for the Gravity resource which is always present when PhysicsPlugins is added.
+#[cfg(feature = "avian3d")]
 pub fn physics_available(gravity: Option<Res<Gravity>>) -> bool {
     gravity.is_some()
 }
 
 /// System to update cached spline curves when splines change.
+///
+/// Splines are sampled at their [`Spline::resolution_override`] if set, and at the
+/// global curve resolution otherwise.
+///
+/// Also keeps each spline's [`CachedArcLength`] up to date for spacing direction
+/// arrows and distance ticks. Tables already rebuilt since the spline last changed
+/// (for example by [`cache_arc_lengths`](crate::path_follow::cache_arc_lengths))
+/// are left alone.
 pub fn update_spline_cache(
     mut commands: Commands,
     settings: Res<EditorSettings>,
+    ticks: SystemChangeTick,
     changed_splines: Query<(Entity, &Spline), Changed<Spline>>,
     mut cached: Query<(Entity, &Spline, &mut CachedSplineCurve)>,
     uncached: Query<(Entity, &Spline), Without<CachedSplineCurve>>,
+    mut arc_lengths: Query<(Entity, Ref<Spline>, Option<&mut CachedArcLength>)>,
 ) {
-    let resolution = settings.visuals.curve_resolution;
+    let global_resolution = settings.visuals.curve_resolution;
 
     // Add cache to splines that don't have one
     for (entity, spline) in &uncached {
+        let resolution = spline.resolution_or(global_resolution);
         let points = if spline.is_valid() {
             spline.sample(resolution)
         } else {
@@ -46,6 +64,7 @@ pub fn update_spline_cache(
     // Update cache for changed splines
     for (entity, spline) in &changed_splines {
         if let Ok((_, _, mut cache)) = cached.get_mut(entity) {
+            let resolution = spline.resolution_or(global_resolution);
             cache.points = if spline.is_valid() {
                 spline.sample(resolution)
             } else {
@@ -58,6 +77,7 @@ pub fn update_spline_cache(
     // Update cache if resolution changed
     if settings.is_changed() {
         for (_, spline, mut cache) in &mut cached {
+            let resolution = spline.resolution_or(global_resolution);
             if cache.resolution != resolution {
                 cache.points = if spline.is_valid() {
                     spline.sample(resolution)
@@ -68,12 +88,33 @@ pub fn update_spline_cache(
             }
         }
     }
+
+    // Rebuild arc length tables that are missing or older than their spline
+    for (entity, spline, arc_length) in &mut arc_lengths {
+        match arc_length {
+            Some(mut arc_length) => {
+                let stale = spline
+                    .last_changed()
+                    .is_newer_than(arc_length.last_changed(), ticks.this_run());
+                if stale {
+                    *arc_length = CachedArcLength::compute(&spline);
+                }
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(CachedArcLength::compute(&spline));
+            }
+        }
+    }
 }
 
 /// System to project spline visualization onto terrain when surface projection is enabled.
 ///
 /// Uses the centralized projection code from [`crate::spline::projection`].
+#[cfg(feature = "avian3d")]
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 pub fn project_spline_visualization(
     mut commands: Commands,
     settings: Res<EditorSettings>,
@@ -183,6 +224,7 @@ pub fn project_spline_visualization(
 
 /// Check if a spline's projection cache appears invalid and needs re-projection.
 /// This catches cases where projection failed initially (e.g., physics not ready).
+#[cfg(feature = "avian3d")]
 fn needs_reprojection(spline: &Spline, projected: Option<&ProjectedSplineCache>) -> bool {
     let Some(projected) = projected else {
         return true;
@@ -215,16 +257,27 @@ fn needs_reprojection(spline: &Spline, projected: Option<&ProjectedSplineCache>)
 /// resampling splines every frame. When surface projection is enabled,
 /// uses projected points from [`ProjectedSplineCache`].
 ///
+/// When [`GizmoVisuals::screen_space_tolerance`](super::GizmoVisuals::screen_space_tolerance)
+/// is set, unprojected curves are adaptively resampled against the active camera instead.
+///
 /// When x-ray is enabled, renders an additional faded pass that shows through geometry.
+///
+/// While the editor is enabled, the spline under the cursor is drawn in
+/// [`GizmoColors::spline_hovered`](super::GizmoColors::spline_hovered).
+#[allow(clippy::type_complexity)]
 pub fn render_spline_curves(
     settings: Res<EditorSettings>,
+    selection_state: Res<SelectionState>,
     splines: Query<(
+        Entity,
         &Spline,
         &GlobalTransform,
         Option<&SelectedSpline>,
         Option<&CachedSplineCurve>,
         Option<&ProjectedSplineCache>,
+        Option<&SplinePreview>,
     )>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
     mut gizmos: Gizmos,
     mut xray_gizmos: Gizmos<SplineXRayGizmos>,
 ) {
@@ -232,31 +285,66 @@ pub fn render_spline_curves(
         return;
     }
 
-    for (spline, global_transform, selected, cache, projected) in &splines {
+    let active_camera = cameras.iter().find(|(c, _)| c.is_active);
+
+    let hovered_spline = selection_state.hovered_spline.filter(|_| settings.enabled);
+
+    for (entity, spline, global_transform, selected, cache, projected, preview) in &splines {
+        if let Some(preview) = preview {
+            render_spline_preview(
+                &preview.spline,
+                global_transform,
+                &settings,
+                &mut gizmos,
+                &mut xray_gizmos,
+            );
+        }
+
         if !spline.is_valid() {
             continue;
         }
 
         let color = if selected.is_some() {
             settings.colors.spline_selected
+        } else if hovered_spline == Some(entity) {
+            settings.colors.spline_hovered
         } else {
             settings.colors.spline
         };
 
-        // Use the centralized helper to get effective curve points
-        let fallback_points;
-        let points_ref = if let Some(pts) = get_effective_curve_points(cache, projected) {
-            pts
-        } else {
-            fallback_points = spline.sample(settings.visuals.curve_resolution);
-            &fallback_points
-        };
+        let is_projected = projected.is_some_and(|p| !p.curve_points.is_empty());
+        let world_points: Vec<Vec3> = match (settings.visuals.screen_space_tolerance, active_camera)
+        {
+            (Some(tolerance), Some((camera, camera_transform))) if !is_projected => {
+                // Sample in render space, where the camera is, so the origin is
+                // subtracted before anything is projected
+                let render_transform = render_space_transform(global_transform, &settings);
+                sample_screen_space(
+                    spline,
+                    &render_transform,
+                    camera,
+                    camera_transform,
+                    tolerance,
+                )
+            }
+            _ => {
+                // Use the centralized helper to get effective curve points
+                let fallback_points;
+                let points_ref = if let Some(pts) = get_effective_curve_points(cache, projected) {
+                    pts
+                } else {
+                    fallback_points =
+                        spline.sample(spline.resolution_or(settings.visuals.curve_resolution));
+                    &fallback_points
+                };
 
-        // Transform points from local to world space
-        let world_points: Vec<Vec3> = points_ref
-            .iter()
-            .map(|&p| global_transform.transform_point(p))
-            .collect();
+                // Transform points from local to world (render) space
+                points_ref
+                    .iter()
+                    .map(|&p| to_render_space(global_transform, p, &settings))
+                    .collect()
+            }
+        };
 
         // X-ray pass (faded, renders through geometry)
         if settings.xray_enabled {
@@ -284,15 +372,319 @@ pub fn render_spline_curves(
             let handle_points = get_effective_control_points(spline, projected);
             let world_handles: Vec<Vec3> = handle_points
                 .iter()
-                .map(|&p| global_transform.transform_point(p))
+                .map(|&p| to_render_space(global_transform, p, &settings))
                 .collect();
-            render_bezier_handles(&world_handles, &settings, &mut gizmos, &mut xray_gizmos);
+            render_bezier_handles(
+                &world_handles,
+                spline.closed,
+                &settings,
+                &mut gizmos,
+                &mut xray_gizmos,
+            );
+        }
+
+        if selected.is_some() && settings.show_continuity_breaks {
+            render_continuity_breaks(
+                spline,
+                global_transform,
+                &settings,
+                &mut gizmos,
+                &mut xray_gizmos,
+            );
+        }
+    }
+}
+
+/// Transform a local spline point into the space gizmos are drawn in.
+///
+/// With a [`floating_origin`](super::GizmoVisuals::floating_origin), the origin is
+/// subtracted from the transform's translation before the point is added, so large
+/// coordinates cancel out before precision is lost.
+fn to_render_space(transform: &GlobalTransform, point: Vec3, settings: &EditorSettings) -> Vec3 {
+    render_space_transform(transform, settings).transform_point(point)
+}
+
+/// Move a spline's transform into the space gizmos are drawn in, by subtracting the
+/// [`floating_origin`](super::GizmoVisuals::floating_origin) from its translation.
+fn render_space_transform(
+    transform: &GlobalTransform,
+    settings: &EditorSettings,
+) -> GlobalTransform {
+    let Some(origin) = settings.visuals.floating_origin else {
+        return *transform;
+    };
+    let mut affine = transform.affine();
+    affine.translation -= Vec3A::from(origin);
+    GlobalTransform::from(affine)
+}
+
+/// Render a translucent "ghost" of a previewed spline edit.
+fn render_spline_preview(
+    spline: &Spline,
+    global_transform: &GlobalTransform,
+    settings: &EditorSettings,
+    gizmos: &mut Gizmos,
+    xray_gizmos: &mut Gizmos<SplineXRayGizmos>,
+) {
+    let world_points: Vec<Vec3> = spline
+        .sample(spline.resolution_or(settings.visuals.curve_resolution))
+        .iter()
+        .map(|&p| to_render_space(global_transform, p, settings))
+        .collect();
+
+    let color = settings.colors.preview;
+    if settings.xray_enabled {
+        let xray_color = color.with_alpha(color.alpha() * settings.xray_opacity);
+        xray_gizmos.linestrip(world_points.iter().copied(), xray_color);
+    }
+    gizmos.linestrip(world_points, color);
+}
+
+/// Mark segment joins that are less than C2 continuous.
+/// Marker size grows as continuity drops (C1 joins smallest, C0 kinks larger, gaps largest).
+fn render_continuity_breaks(
+    spline: &Spline,
+    global_transform: &GlobalTransform,
+    settings: &EditorSettings,
+    gizmos: &mut Gizmos,
+    xray_gizmos: &mut Gizmos<SplineXRayGizmos>,
+) {
+    let color = settings.colors.continuity_break;
+    for (t, level) in spline.continuity_breaks() {
+        let Some(position) = spline.evaluate(t) else {
+            continue;
+        };
+        let position = to_render_space(global_transform, position, settings);
+        let radius = settings.sizes.point_radius
+            * match level {
+                ContinuityLevel::C1 | ContinuityLevel::C2 => 0.6,
+                ContinuityLevel::C0 => 0.9,
+                ContinuityLevel::Discontinuous => 1.2,
+            };
+
+        if settings.xray_enabled {
+            let xray_color = color.with_alpha(settings.xray_opacity);
+            xray_gizmos.sphere(Isometry3d::from_translation(position), radius, xray_color);
+        }
+        gizmos.sphere(Isometry3d::from_translation(position), radius, color);
+    }
+}
+
+/// Number of direction arrows drawn per spline segment.
+const DIRECTION_ARROWS_PER_SEGMENT: usize = 2;
+
+/// System that draws arrowheads along spline curves pointing in their direction of
+/// travel (increasing `t`), while [`EditorSettings::show_direction_arrows`] is set.
+///
+/// Arrows are spaced evenly by arc length, using the spline's [`CachedArcLength`]
+/// kept by [`update_spline_cache`]. Like the distance ticks, they sit on the
+/// projected curve when the spline is projected onto a surface. When x-ray is
+/// enabled, they also show through geometry.
+#[allow(clippy::type_complexity)]
+pub fn render_direction_arrows(
+    settings: Res<EditorSettings>,
+    splines: Query<(
+        &Spline,
+        &GlobalTransform,
+        &CachedArcLength,
+        Option<&ProjectedSplineCache>,
+    )>,
+    mut gizmos: Gizmos,
+    mut xray_gizmos: Gizmos<SplineXRayGizmos>,
+) {
+    if !settings.show_gizmos || !settings.show_direction_arrows {
+        return;
+    }
+
+    let color = settings.colors.direction_arrow;
+    let xray_color = color.with_alpha(settings.xray_opacity);
+    let length = settings.sizes.point_radius * 3.0;
+
+    for (spline, global_transform, cached, projected) in &splines {
+        let table = &cached.table;
+        let count = spline.segment_count() * DIRECTION_ARROWS_PER_SEGMENT;
+        let projected_points = projected
+            .map(|p| p.curve_points.as_slice())
+            .filter(|points| points.len() >= 2);
+
+        for (position, tangent) in direction_arrow_samples(spline, table, count, projected_points) {
+            let position = to_render_space(global_transform, position, &settings);
+            let Ok(direction) = Dir3::new(global_transform.affine().transform_vector3(tangent))
+            else {
+                continue;
+            };
+            // Lay the arrowhead flat in the horizontal plane unless the curve is vertical
+            let side = direction.cross(Vec3::Y).try_normalize().unwrap_or(Vec3::X);
+            let tip = position + direction * (length * 0.5);
+            let back = position - direction * (length * 0.5);
+            let left = back + side * (length * 0.4);
+            let right = back - side * (length * 0.4);
+
+            if settings.xray_enabled {
+                xray_gizmos.line(left, tip, xray_color);
+                xray_gizmos.line(right, tip, xray_color);
+            }
+            gizmos.line(left, tip, color);
+            gizmos.line(right, tip, color);
         }
     }
 }
 
+/// Local positions and unit tangents of `count` direction arrows, evenly spaced by
+/// arc length with half a spacing before the first and after the last arrow.
+///
+/// With `curve_points`, arrows are placed on that polyline as in
+/// [`distance_tick_samples`].
+fn direction_arrow_samples(
+    spline: &Spline,
+    table: &ArcLengthTable,
+    count: usize,
+    curve_points: Option<&[Vec3]>,
+) -> Vec<(Vec3, Vec3)> {
+    let total = table.total_length();
+    (0..count)
+        .filter_map(|i| {
+            let t = table.length_to_t(total * (i as f32 + 0.5) / count as f32);
+            let (position, tangent) = curve_pos_tan(spline, t, curve_points)?;
+            Some((position, tangent.try_normalize()?))
+        })
+        .collect()
+}
+
+/// Upper bound on the number of distance ticks drawn per spline, so a tiny
+/// [`EditorSettings::distance_tick_interval`] can't stall the frame.
+const MAX_DISTANCE_TICKS: usize = 1000;
+
+/// System that draws tick marks across selected spline curves every
+/// [`EditorSettings::distance_tick_interval`] world units of arc length, starting
+/// at the first point, while [`EditorSettings::show_distance_ticks`] is set.
+///
+/// Distances are measured along the spline itself. When the spline is projected
+/// onto a surface, the ticks are placed on the projected curve at the same
+/// parameters, so they follow the terrain.
+#[allow(clippy::type_complexity)]
+pub fn render_distance_ticks(
+    settings: Res<EditorSettings>,
+    splines: Query<
+        (
+            &Spline,
+            &GlobalTransform,
+            &CachedArcLength,
+            Option<&ProjectedSplineCache>,
+        ),
+        With<SelectedSpline>,
+    >,
+    mut gizmos: Gizmos,
+    mut xray_gizmos: Gizmos<SplineXRayGizmos>,
+) {
+    if !settings.show_gizmos || !settings.show_distance_ticks {
+        return;
+    }
+
+    let color = settings.colors.distance_tick;
+    let xray_color = color.with_alpha(settings.xray_opacity);
+    let half_length = settings.sizes.point_radius * 2.0;
+
+    for (spline, global_transform, cached, projected) in &splines {
+        let table = &cached.table;
+        let projected_points = projected
+            .map(|p| p.curve_points.as_slice())
+            .filter(|points| points.len() >= 2);
+
+        for (_, position, tangent) in distance_tick_samples(
+            spline,
+            global_transform,
+            table,
+            settings.distance_tick_interval,
+            projected_points,
+        ) {
+            let position = to_render_space(global_transform, position, &settings);
+            let tangent = global_transform.affine().transform_vector3(tangent);
+            let right = CoordinateFrame::from_tangent(tangent).right * half_length;
+
+            if settings.xray_enabled {
+                xray_gizmos.line(position - right, position + right, xray_color);
+            }
+            gizmos.line(position - right, position + right, color);
+        }
+    }
+}
+
+/// World arc length, local position and tangent of ticks every `interval` world
+/// units along a spline placed by `transform`.
+///
+/// `table` is the spline's arc length table in local units. It's reused when
+/// `transform` scales uniformly; other scales measure the transformed curve.
+///
+/// With `curve_points` (curve samples at evenly spaced `t`, such as a
+/// [`ProjectedSplineCache`]), ticks are placed on that polyline instead of the
+/// spline, at the `t` of their arc length along the spline.
+fn distance_tick_samples(
+    spline: &Spline,
+    transform: &GlobalTransform,
+    table: &ArcLengthTable,
+    interval: f32,
+    curve_points: Option<&[Vec3]>,
+) -> Vec<(f32, Vec3, Vec3)> {
+    if interval <= 0.0 {
+        return Vec::new();
+    }
+
+    // Splines are affine invariant, so the transformed curve keeps its parameters
+    // and a uniform scale only scales its arc lengths
+    let world_table;
+    let (table, scale) = match uniform_scale(transform) {
+        Some(scale) => (table, scale),
+        None => {
+            let mut world_spline = spline.clone();
+            for point in &mut world_spline.control_points {
+                *point = transform.transform_point(*point);
+            }
+            world_table =
+                ArcLengthTable::compute(&world_spline, spline.default_arc_length_samples());
+            (&world_table, 1.0)
+        }
+    };
+    let world_length = table.total_length() * scale;
+    let count = ((world_length / interval) as usize + 1).min(MAX_DISTANCE_TICKS);
+
+    (0..count)
+        .filter_map(|i| {
+            let distance = i as f32 * interval;
+            let t = table.length_to_t(distance / scale);
+            let (position, tangent) = curve_pos_tan(spline, t, curve_points)?;
+            Some((distance, position, tangent))
+        })
+        .collect()
+}
+
+/// The factor `transform` scales lengths by, if it's the same in every direction.
+fn uniform_scale(transform: &GlobalTransform) -> Option<f32> {
+    let matrix = transform.affine().matrix3;
+    let scale_squared = matrix.x_axis.length_squared();
+    let gram = matrix.transpose() * matrix;
+    let uniform = Mat3A::from_diagonal(Vec3::splat(scale_squared));
+    (scale_squared > 0.0 && gram.abs_diff_eq(uniform, scale_squared * 1e-4))
+        .then(|| scale_squared.sqrt())
+}
+
+/// Local position and (unnormalized) tangent at `t`, read off `curve_points` (curve
+/// samples at evenly spaced `t`) when given and evaluated on the spline otherwise.
+fn curve_pos_tan(spline: &Spline, t: f32, curve_points: Option<&[Vec3]>) -> Option<(Vec3, Vec3)> {
+    match curve_points {
+        Some(points) => {
+            let scaled = t * (points.len() - 1) as f32;
+            let index = (scaled as usize).min(points.len() - 2);
+            let (start, end) = (points[index], points[index + 1]);
+            Some((start.lerp(end, scaled - index as f32), end - start))
+        }
+        None => spline.evaluate_pos_tan(t),
+    }
+}
+
 fn render_bezier_handles(
     points: &[Vec3],
+    closed: bool,
     settings: &EditorSettings,
     gizmos: &mut Gizmos,
     xray_gizmos: &mut Gizmos<SplineXRayGizmos>,
@@ -301,19 +693,23 @@ fn render_bezier_handles(
         return;
     }
 
-    let num_segments = (points.len() - 1) / 3;
+    let n = points.len();
+    let num_segments = SplineType::CubicBezier.segment_count(points, closed);
     for seg in 0..num_segments {
         let i = seg * 3;
-        if i + 3 < points.len() {
+        // The closing segment of a closed spline wraps to the first anchor, and has
+        // no handles to draw when they mirror the neighbouring ones
+        if i + 2 < n {
+            let end = points[(i + 3) % n];
             // X-ray pass
             if settings.xray_enabled {
                 let xray_color = settings.colors.handle_line.with_alpha(settings.xray_opacity);
                 xray_gizmos.line(points[i], points[i + 1], xray_color);
-                xray_gizmos.line(points[i + 3], points[i + 2], xray_color);
+                xray_gizmos.line(end, points[i + 2], xray_color);
             }
             // Normal pass - line from anchor to handle
             gizmos.line(points[i], points[i + 1], settings.colors.handle_line);
-            gizmos.line(points[i + 3], points[i + 2], settings.colors.handle_line);
+            gizmos.line(end, points[i + 2], settings.colors.handle_line);
         }
     }
 }
@@ -321,10 +717,15 @@ fn render_bezier_handles(
 /// System to render control point spheres.
 /// Uses the centralized helper to get effective positions.
 /// When x-ray is enabled, renders an additional faded pass that shows through geometry.
+///
+/// With [`GizmoSizes::constant_screen_size`](super::GizmoSizes::constant_screen_size),
+/// radii are scaled for the active camera.
+#[allow(clippy::type_complexity)]
 pub fn render_control_points(
     settings: Res<EditorSettings>,
     splines: Query<(Entity, &Spline, &GlobalTransform, Option<&SelectedSpline>, Option<&ProjectedSplineCache>)>,
     selected_points: Query<&ControlPointMarker, With<SelectedControlPoint>>,
+    cameras: Query<(&Camera, &GlobalTransform, &Projection), With<Camera3d>>,
     mut gizmos: Gizmos,
     mut xray_gizmos: Gizmos<SplineXRayGizmos>,
 ) {
@@ -344,6 +745,7 @@ pub fn render_control_points(
 
     let sizes = &settings.sizes;
     let colors = &settings.colors;
+    let active_camera = cameras.iter().find(|(c, _, _)| c.is_active);
 
     for (entity, spline, global_transform, spline_selected, projected) in &splines {
         let entity_selected = selected_indices.get(&entity);
@@ -360,12 +762,12 @@ pub fn render_control_points(
         // Transform control points to world space
         let world_points: Vec<Vec3> = control_points
             .iter()
-            .map(|&p| global_transform.transform_point(p))
+            .map(|&p| to_render_space(global_transform, p, &settings))
             .collect();
 
-        // For CatmullRom splines, draw lines connecting adjacent control points
+        // For CatmullRom and TCB splines, draw lines connecting adjacent control points
         // to show what each control point is attached to
-        if spline.spline_type == SplineType::CatmullRom
+        if matches!(spline.spline_type, SplineType::CatmullRom | SplineType::Tcb)
             && world_points.len() >= 2
             && settings.show_handle_lines
         {
@@ -408,6 +810,13 @@ pub fn render_control_points(
             } else {
                 sizes.point_radius
             };
+            let radius = match active_camera {
+                Some((_, camera_transform, projection)) => {
+                    let distance = camera_transform.translation().distance(point);
+                    radius * sizes.point_scale(projection, distance)
+                }
+                None => radius,
+            };
 
             // X-ray pass (faded, renders through geometry)
             if settings.xray_enabled {
@@ -505,3 +914,258 @@ pub fn cleanup_orphaned_markers(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_spline_cache_uses_resolution_override() {
+        let mut world = World::new();
+        world.insert_resource(EditorSettings::default());
+        let global_resolution = world.resource::<EditorSettings>().visuals.curve_resolution;
+
+        let points = vec![
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::new(2.0, 1.0, 0.0),
+            Vec3::new(3.0, 0.0, 0.0),
+        ];
+        let default = world
+            .spawn(Spline::new(SplineType::CatmullRom, points.clone()))
+            .id();
+        let detailed = world
+            .spawn(Spline::new(SplineType::BSpline, points).with_resolution(4))
+            .id();
+
+        world.run_system_once(update_spline_cache).unwrap();
+
+        // One segment either way, sampled at the override or the global resolution
+        let cache = world.get::<CachedSplineCurve>(default).unwrap();
+        assert_eq!(cache.resolution, global_resolution);
+        assert_eq!(cache.points.len(), global_resolution + 1);
+        let cache = world.get::<CachedSplineCurve>(detailed).unwrap();
+        assert_eq!(cache.resolution, 4);
+        assert_eq!(cache.points.len(), 5);
+
+        // Changing the override resamples the spline
+        let mut spline = world.get_mut::<Spline>(detailed).unwrap();
+        spline.resolution_override = Some(10);
+        world.run_system_once(update_spline_cache).unwrap();
+        let cache = world.get::<CachedSplineCurve>(detailed).unwrap();
+        assert_eq!(cache.points.len(), 11);
+
+        // A zero override still samples each segment once
+        let mut spline = world.get_mut::<Spline>(detailed).unwrap();
+        spline.resolution_override = Some(0);
+        world.run_system_once(update_spline_cache).unwrap();
+        let cache = world.get::<CachedSplineCurve>(detailed).unwrap();
+        assert_eq!(cache.resolution, 1);
+        assert_eq!(cache.points.len(), 2);
+    }
+
+    #[test]
+    fn test_spline_cache_keeps_arc_lengths_current() {
+        let mut world = World::new();
+        world.insert_resource(EditorSettings::default());
+        let points = vec![
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::new(2.0, 1.0, 0.0),
+            Vec3::new(3.0, 0.0, 0.0),
+        ];
+        let spline = Spline::new(SplineType::CatmullRom, points);
+        let samples = spline.default_arc_length_samples();
+        let entity = world.spawn(spline.clone()).id();
+
+        world.run_system_once(update_spline_cache).unwrap();
+        let cached = world.get::<CachedArcLength>(entity).unwrap();
+        assert_eq!(cached.samples, samples);
+
+        // A table rebuilt after the last spline change is left alone
+        world.entity_mut(entity).insert(CachedArcLength {
+            table: ArcLengthTable::compute(&spline, 4),
+            samples: 4,
+        });
+        world.run_system_once(update_spline_cache).unwrap();
+        assert_eq!(world.get::<CachedArcLength>(entity).unwrap().samples, 4);
+
+        // Changing the spline rebuilds it
+        world.get_mut::<Spline>(entity).unwrap().control_points[2] = Vec3::new(2.0, 5.0, 0.0);
+        world.run_system_once(update_spline_cache).unwrap();
+        let changed = world.get::<Spline>(entity).unwrap();
+        let expected = ArcLengthTable::compute(changed, samples).total_length();
+        let cached = world.get::<CachedArcLength>(entity).unwrap();
+        assert_eq!(cached.samples, samples);
+        assert_eq!(cached.table.total_length(), expected);
+        assert!(expected > ArcLengthTable::compute(&spline, samples).total_length());
+    }
+
+    #[test]
+    fn test_direction_arrows_are_evenly_spaced_along_curve() {
+        // Unevenly spaced points on a line, so uniform t would bunch the arrows up
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::ZERO,
+                Vec3::X,
+                Vec3::new(1.5, 0.0, 0.0),
+                Vec3::new(5.0, 0.0, 0.0),
+                Vec3::new(6.0, 0.0, 0.0),
+            ],
+        );
+        let table = ArcLengthTable::compute(&spline, 256);
+        let arrows = direction_arrow_samples(&spline, &table, 4, None);
+        assert_eq!(arrows.len(), 4);
+
+        // Half a spacing from each end, a full spacing between arrows
+        let spacing = table.total_length() / 4.0;
+        for (i, (position, tangent)) in arrows.iter().enumerate() {
+            let expected = 1.0 + spacing * (i as f32 + 0.5);
+            assert!(
+                (position.x - expected).abs() < 0.02,
+                "arrow {i} at {position}, expected x = {expected}"
+            );
+            assert!(tangent.distance(Vec3::X) < 1e-4);
+        }
+
+        // Projected curves keep the spacing but follow the projected points
+        let projected: Vec<Vec3> = spline
+            .sample(32)
+            .into_iter()
+            .map(|p| p + Vec3::Y * 2.0)
+            .collect();
+        let arrows = direction_arrow_samples(&spline, &table, 4, Some(&projected));
+        assert_eq!(arrows.len(), 4);
+        for (i, (position, tangent)) in arrows.iter().enumerate() {
+            let expected = Vec3::new(1.0 + spacing * (i as f32 + 0.5), 2.0, 0.0);
+            assert!(
+                position.distance(expected) < 0.02,
+                "projected arrow {i} at {position}"
+            );
+            assert!(tangent.distance(Vec3::X) < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_distance_ticks_are_at_arc_length_intervals() {
+        // Spans x = 1..5 with uneven point spacing, so ticks aren't at uniform t
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::ZERO,
+                Vec3::X,
+                Vec3::new(1.5, 0.0, 0.0),
+                Vec3::new(5.0, 0.0, 0.0),
+                Vec3::new(6.0, 0.0, 0.0),
+            ],
+        );
+        let table = ArcLengthTable::compute(&spline, 256);
+        let identity = GlobalTransform::IDENTITY;
+        let ticks = distance_tick_samples(&spline, &identity, &table, 1.0, None);
+        assert_eq!(ticks.len(), 5);
+        for (i, &(distance, position, tangent)) in ticks.iter().enumerate() {
+            assert_eq!(distance, i as f32);
+            assert!(
+                (position.x - (1.0 + distance)).abs() < 0.02,
+                "tick {i} at {position}"
+            );
+            assert!(tangent.normalize().distance(Vec3::X) < 1e-4);
+        }
+
+        // Projected curves keep the spacing but follow the projected points
+        let projected: Vec<Vec3> = spline
+            .sample(32)
+            .into_iter()
+            .map(|p| p + Vec3::Y * 2.0)
+            .collect();
+        let ticks = distance_tick_samples(&spline, &identity, &table, 1.0, Some(&projected));
+        assert_eq!(ticks.len(), 5);
+        for (i, &(distance, position, _)) in ticks.iter().enumerate() {
+            assert!(
+                position.distance(Vec3::new(1.0 + distance, 2.0, 0.0)) < 0.02,
+                "projected tick {i} at {position}"
+            );
+        }
+
+        assert!(distance_tick_samples(&spline, &identity, &table, 0.0, None).is_empty());
+
+        // Scaled splines keep their ticks a world unit apart, whether scaled
+        // uniformly or only along the curve
+        for scale in [Vec3::splat(2.0), Vec3::new(3.0, 1.0, 0.5)] {
+            let transform = GlobalTransform::from(
+                Transform::from_scale(scale).with_translation(Vec3::new(0.0, 4.0, 1.0)),
+            );
+            let ticks = distance_tick_samples(&spline, &transform, &table, 1.0, None);
+            assert_eq!(ticks.len(), 4 * scale.x as usize + 1, "scale {scale}");
+            for (i, &(distance, position, _)) in ticks.iter().enumerate() {
+                let world = transform.transform_point(position);
+                assert_eq!(distance, i as f32);
+                assert!(
+                    (world.x - (scale.x + distance)).abs() < 0.03,
+                    "tick {i} at {world} with scale {scale}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_screen_space_sampling_past_floating_origin() {
+        use bevy::camera::{ComputedCameraValues, RenderTargetInfo};
+
+        let size = UVec2::new(1280, 720);
+        let camera = Camera {
+            computed: ComputedCameraValues {
+                clip_from_view: Mat4::perspective_infinite_reverse_rh(
+                    std::f32::consts::FRAC_PI_4,
+                    size.x as f32 / size.y as f32,
+                    0.1,
+                ),
+                target_info: Some(RenderTargetInfo {
+                    physical_size: size,
+                    scale_factor: 1.0,
+                }),
+                ..default()
+            },
+            ..default()
+        };
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(-2.0, 0.0, -10.0),
+                Vec3::new(-1.0, 1.0, -10.0),
+                Vec3::new(1.0, -1.0, -10.0),
+                Vec3::new(2.0, 0.0, -10.0),
+            ],
+        );
+
+        // Far from the world origin, with the camera at the floating origin
+        let origin = Vec3::new(3_000_000.0, 0.0, -1_000_000.0);
+        let mut settings = EditorSettings::default();
+        settings.visuals.floating_origin = Some(origin);
+        let transform = GlobalTransform::from_translation(origin);
+        let render_transform = render_space_transform(&transform, &settings);
+        let far = sample_screen_space(
+            &spline,
+            &render_transform,
+            &camera,
+            &GlobalTransform::IDENTITY,
+            0.5,
+        );
+
+        // Samples the same points as the spline does near the world origin
+        let near = sample_screen_space(
+            &spline,
+            &GlobalTransform::IDENTITY,
+            &camera,
+            &GlobalTransform::IDENTITY,
+            0.5,
+        );
+        assert!(near.len() > 4);
+        assert_eq!(far.len(), near.len());
+        for (far, near) in far.iter().zip(&near) {
+            assert!(far.distance(*near) < 1e-5, "{far} vs {near}");
+        }
+    }
+}
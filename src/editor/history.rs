@@ -0,0 +1,244 @@
+//! Undo and redo of spline edits.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::spline::{SelectedSpline, Spline};
+
+use super::{EditorKeyBindings, EditorSettings, ModifierKey};
+
+/// The states of the splines touched by one edit. `None` means the entity had
+/// no spline, because the edit created it.
+type EditStep = Vec<(Entity, Option<Spline>)>;
+
+/// Resource recording spline states before each edit, so edits can be undone.
+///
+/// The editor records its own edits (dragging, adding and deleting points,
+/// changing the type, opening/closing, applying previews, duplicating, joining).
+/// Record edits made elsewhere by calling [`EditHistory::record`] before changing
+/// the splines, or [`EditHistory::record_created`] when adding new ones:
+/// ```ignore
+/// fn straighten(mut history: ResMut<EditHistory>, mut splines: Query<(Entity, &mut Spline)>) {
+///     history.record(splines.iter().map(|(entity, spline)| (entity, spline.clone())));
+///     for (_, mut spline) in &mut splines {
+///         spline.spline_type = SplineType::CatmullRom;
+///     }
+/// }
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct EditHistory {
+    /// Maximum number of edits kept for undo. The oldest edits are dropped first.
+    pub max_steps: usize,
+    undo_stack: VecDeque<EditStep>,
+    redo_stack: Vec<EditStep>,
+}
+
+impl Default for EditHistory {
+    fn default() -> Self {
+        Self {
+            max_steps: 100,
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+        }
+    }
+}
+
+impl EditHistory {
+    /// Record the states of splines about to be edited as one undo step.
+    ///
+    /// Clears the redo stack. Does nothing if `splines` is empty.
+    pub fn record(&mut self, splines: impl IntoIterator<Item = (Entity, Spline)>) {
+        self.push(
+            splines
+                .into_iter()
+                .map(|(entity, spline)| (entity, Some(spline)))
+                .collect(),
+        );
+    }
+
+    /// Record that splines are about to be added to `entities` as one undo step.
+    ///
+    /// Undoing removes the [`Spline`] (and [`SelectedSpline`]) from the entities
+    /// again, and redoing puts the spline back. Does nothing if `entities` is empty.
+    pub fn record_created(&mut self, entities: impl IntoIterator<Item = Entity>) {
+        self.push(entities.into_iter().map(|entity| (entity, None)).collect());
+    }
+
+    fn push(&mut self, step: EditStep) {
+        if step.is_empty() {
+            return;
+        }
+        self.redo_stack.clear();
+        self.undo_stack.push_back(step);
+        while self.undo_stack.len() > self.max_steps {
+            self.undo_stack.pop_front();
+        }
+    }
+
+    /// Restore the splines to their state before the last recorded edit.
+    ///
+    /// Returns `false` if there is nothing to undo. Splines whose entities no
+    /// longer exist are skipped. Created splines are removed through `commands`.
+    pub fn undo(&mut self, commands: &mut Commands, splines: &mut Query<&mut Spline>) -> bool {
+        let Some(step) = self.undo_stack.pop_back() else {
+            return false;
+        };
+        self.redo_stack.push(swap_step(step, commands, splines));
+        true
+    }
+
+    /// Reapply the last undone edit.
+    ///
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self, commands: &mut Commands, splines: &mut Query<&mut Spline>) -> bool {
+        let Some(step) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack
+            .push_back(swap_step(step, commands, splines));
+        true
+    }
+
+    /// Check if there is an edit to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    /// Check if there is an undone edit to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Forget all recorded edits.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+}
+
+/// Write the recorded states into the splines, returning the states they replaced.
+fn swap_step(
+    step: EditStep,
+    commands: &mut Commands,
+    splines: &mut Query<&mut Spline>,
+) -> EditStep {
+    step.into_iter()
+        .filter_map(|(entity, recorded)| {
+            match (splines.get_mut(entity), recorded) {
+                (Ok(mut spline), Some(recorded)) => {
+                    Some((entity, Some(std::mem::replace(&mut *spline, recorded))))
+                }
+                // The edit created this spline, so undoing it removes the spline
+                (Ok(spline), None) => {
+                    commands.entity(entity).remove::<(Spline, SelectedSpline)>();
+                    Some((entity, Some(spline.clone())))
+                }
+                (Err(_), Some(recorded)) => {
+                    commands.get_entity(entity).ok()?.insert(recorded);
+                    Some((entity, None))
+                }
+                (Err(_), None) => None,
+            }
+        })
+        .collect()
+}
+
+/// System to undo with `Ctrl + Z` and redo with `Ctrl + Shift + Z`.
+pub fn handle_undo_hotkeys(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    bindings: Res<EditorKeyBindings>,
+    mut history: ResMut<EditHistory>,
+    mut splines: Query<&mut Spline>,
+) {
+    if !settings.enabled || !settings.hotkeys_enabled {
+        return;
+    }
+
+    if !keyboard.just_pressed(bindings.undo) || !ModifierKey::Control.pressed(&keyboard) {
+        return;
+    }
+
+    if ModifierKey::Shift.pressed(&keyboard) {
+        history.redo(&mut commands, &mut splines);
+    } else {
+        history.undo(&mut commands, &mut splines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_undo_restores_previous_control_points() {
+        let mut world = World::new();
+        world.insert_resource(EditHistory {
+            max_steps: 3,
+            ..default()
+        });
+        let points = |x: f32| vec![Vec3::ZERO, Vec3::new(x, 0.0, 0.0)];
+        let entity = world
+            .spawn(Spline::new(SplineType::CatmullRom, points(0.0)))
+            .id();
+
+        // Each edit records the spline before moving its last point
+        for x in 1..=4 {
+            let spline = world.get::<Spline>(entity).unwrap().clone();
+            world
+                .resource_mut::<EditHistory>()
+                .record([(entity, spline)]);
+            world.get_mut::<Spline>(entity).unwrap().control_points = points(x as f32);
+        }
+
+        let undo = |world: &mut World| {
+            world
+                .run_system_once(
+                    |mut commands: Commands,
+                     mut history: ResMut<EditHistory>,
+                     mut splines: Query<&mut Spline>| {
+                        history.undo(&mut commands, &mut splines)
+                    },
+                )
+                .unwrap()
+        };
+        let redo = |world: &mut World| {
+            world
+                .run_system_once(
+                    |mut commands: Commands,
+                     mut history: ResMut<EditHistory>,
+                     mut splines: Query<&mut Spline>| {
+                        history.redo(&mut commands, &mut splines)
+                    },
+                )
+                .unwrap()
+        };
+        let current = |world: &World| world.get::<Spline>(entity).unwrap().control_points.clone();
+
+        assert!(undo(&mut world));
+        assert_eq!(current(&world), points(3.0));
+        assert!(undo(&mut world));
+        assert_eq!(current(&world), points(2.0));
+
+        assert!(redo(&mut world));
+        assert_eq!(current(&world), points(3.0));
+
+        // Only the last three edits are kept, so the oldest state is gone
+        assert!(undo(&mut world));
+        assert!(undo(&mut world));
+        assert_eq!(current(&world), points(1.0));
+        assert!(!undo(&mut world));
+
+        // A new edit drops the undone ones
+        let spline = world.get::<Spline>(entity).unwrap().clone();
+        world
+            .resource_mut::<EditHistory>()
+            .record([(entity, spline)]);
+        assert!(!world.resource::<EditHistory>().can_redo());
+        assert!(!redo(&mut world));
+    }
+}
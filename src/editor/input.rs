@@ -1,49 +1,105 @@
+#[cfg(feature = "avian3d")]
+use avian3d::prelude::SpatialQuery;
 use bevy::prelude::*;
 
-use crate::spline::{ControlPointMarker, SelectedControlPoint, SelectedSpline, Spline, SplineType};
+use crate::camera::{CameraMode, CameraModeOverride};
+use crate::distribution::SplineDistribution;
+use crate::road::SplineRoad;
+#[cfg(feature = "avian3d")]
+use crate::spline::project_spline;
+use crate::spline::{
+    ControlPointMarker, SelectedControlPoint, SelectedSpline, Spline, SplinePreview, SplineType,
+};
+use crate::surface::SplineMeshProjection;
 
-use super::{selection::{clear_all_selections, SelectionState}, EditorSettings};
+use super::{
+    camera_is_flying,
+    selection::{clear_all_selections, SelectionState},
+    snap_to_grid, EditHistory, EditorKeyBindings, EditorSettings, ModifierKey,
+};
 
 /// System to handle keyboard shortcuts for spline editing.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_hotkeys(
     mut commands: Commands,
     keyboard: Res<ButtonInput<KeyCode>>,
     settings: Res<EditorSettings>,
+    bindings: Res<EditorKeyBindings>,
     _selection_state: Res<SelectionState>,
+    mut history: ResMut<EditHistory>,
     mut splines: Query<(Entity, &mut Spline), With<SelectedSpline>>,
     selected_points: Query<(Entity, &ControlPointMarker), With<SelectedControlPoint>>,
     all_markers: Query<(Entity, &ControlPointMarker)>,
+    transforms: Query<&GlobalTransform>,
 ) {
     if !settings.enabled || !settings.hotkeys_enabled {
         return;
     }
 
-    // A - Add control point after selection
-    if keyboard.just_pressed(KeyCode::KeyA) {
-        handle_add_point(&mut commands, &mut splines, &selected_points);
+    // Keep the selected splines as they were before any edit so it can be undone
+    let edits = [
+        bindings.add_point,
+        bindings.delete_point,
+        bindings.cycle_type,
+        bindings.toggle_closed,
+    ];
+    let before: Vec<(Entity, Spline)> = if keyboard.any_just_pressed(edits) {
+        splines.iter().map(|(entity, spline)| (entity, spline.clone())).collect()
+    } else {
+        Vec::new()
+    };
+
+    // A - Add control point after selection (Shift+A: before selection)
+    if keyboard.just_pressed(bindings.add_point) {
+        // Snap new points to the grid in world space
+        let snap_increment = settings.active_snap_increment(&keyboard);
+        let snap = |entity: Entity, position: Vec3| {
+            let (Some(increment), Ok(transform)) = (snap_increment, transforms.get(entity)) else {
+                return position;
+            };
+            let world_pos = snap_to_grid(transform.transform_point(position), increment);
+            transform.affine().inverse().transform_point3(world_pos)
+        };
+
+        let shift_held = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        if shift_held {
+            handle_prepend_point(&mut splines, &selected_points, snap);
+        } else {
+            handle_add_point(&mut commands, &mut splines, &selected_points, snap);
+        }
     }
 
     // X - Delete selected control points
-    if keyboard.just_pressed(KeyCode::KeyX) {
+    if keyboard.just_pressed(bindings.delete_point) {
         handle_delete_points(&mut commands, &mut splines, &selected_points, &all_markers);
     }
 
     // Tab - Cycle spline type
-    if keyboard.just_pressed(KeyCode::Tab) {
+    if keyboard.just_pressed(bindings.cycle_type) {
         for (_, mut spline) in &mut splines {
             spline.cycle_type();
         }
     }
 
     // C - Toggle closed/open
-    if keyboard.just_pressed(KeyCode::KeyC) {
+    if keyboard.just_pressed(bindings.toggle_closed) {
         for (_, mut spline) in &mut splines {
             spline.toggle_closed();
         }
     }
 
+    // Only splines that actually changed are recorded, so a key press that does
+    // nothing (like deleting with no points selected) leaves no empty undo step
+    history.record(before.into_iter().filter(|(entity, before)| {
+        splines.get(*entity).is_ok_and(|(_, spline)| {
+            spline.control_points != before.control_points
+                || spline.spline_type != before.spline_type
+                || spline.closed != before.closed
+        })
+    }));
+
     // Escape - Deselect all
-    if keyboard.just_pressed(KeyCode::Escape) {
+    if keyboard.just_pressed(bindings.deselect) {
         clear_all_selections(
             &mut commands,
             splines.iter().map(|(e, _)| e),
@@ -52,10 +108,308 @@ pub fn handle_hotkeys(
     }
 }
 
+/// Smoothing iterations added per [`EditorKeyBindings::smooth`] press when previewing.
+const PREVIEW_SMOOTH_ITERATIONS: usize = 1;
+/// Smoothing strength used when previewing.
+const PREVIEW_SMOOTH_STRENGTH: f32 = 0.5;
+
+/// System to create, commit and discard [`SplinePreview`]s.
+///
+/// - [`EditorKeyBindings::smooth`] (`M`) previews smoothing the selected splines
+///   (repeated presses smooth the preview further)
+/// - [`EditorKeyBindings::confirm`] (`Enter`) replaces each previewed spline with its
+///   preview, recording an undo step
+/// - [`EditorKeyBindings::deselect`] (`Escape`) discards all previews
+///
+/// The confirm and deselect keys are consumed when previews exist so that other hotkeys
+/// (such as deselecting) don't also react to them.
+///
+/// Committing a preview with [`SplinePreview::remove_projection`] set also removes
+/// [`SplineMeshProjection`] from the roads and distributions using the spline.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_preview_hotkeys(
+    mut commands: Commands,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    bindings: Res<EditorKeyBindings>,
+    mut history: ResMut<EditHistory>,
+    selected: Query<(Entity, &Spline, Option<&SplinePreview>), With<SelectedSpline>>,
+    mut previews: Query<(Entity, &mut Spline, &SplinePreview)>,
+    roads: Query<(Entity, &SplineRoad), With<SplineMeshProjection>>,
+    distributions: Query<(Entity, &SplineDistribution), With<SplineMeshProjection>>,
+) {
+    if !settings.enabled || !settings.hotkeys_enabled {
+        return;
+    }
+
+    if keyboard.just_pressed(bindings.smooth) {
+        for (entity, spline, preview) in &selected {
+            let mut smoothed = preview.map_or_else(|| spline.clone(), |p| p.spline.clone());
+            smoothed.smooth(PREVIEW_SMOOTH_ITERATIONS, PREVIEW_SMOOTH_STRENGTH);
+            commands.entity(entity).insert(SplinePreview::new(smoothed));
+        }
+    }
+
+    if previews.is_empty() {
+        return;
+    }
+
+    if keyboard.just_pressed(bindings.confirm) {
+        keyboard.clear_just_pressed(bindings.confirm);
+        history.record(
+            previews
+                .iter()
+                .map(|(entity, spline, _)| (entity, spline.clone())),
+        );
+        for (entity, mut spline, preview) in &mut previews {
+            *spline = preview.spline.clone();
+            commands.entity(entity).remove::<SplinePreview>();
+
+            if preview.remove_projection {
+                let users = roads
+                    .iter()
+                    .filter(|(_, road)| road.spline == entity)
+                    .map(|(e, _)| e)
+                    .chain(
+                        distributions
+                            .iter()
+                            .filter(|(_, distribution)| distribution.spline == entity)
+                            .map(|(e, _)| e),
+                    );
+                for user in users {
+                    commands.entity(user).remove::<SplineMeshProjection>();
+                }
+            }
+        }
+    } else if keyboard.just_pressed(bindings.deselect) {
+        keyboard.clear_just_pressed(bindings.deselect);
+        for (entity, _, _) in &previews {
+            commands.entity(entity).remove::<SplinePreview>();
+        }
+    }
+}
+
+/// System to preview baking surface projection into the selected splines with the
+/// [`EditorKeyBindings::bake_projection`] hotkey (`B` by default).
+///
+/// Control points are projected onto the surface using the projection settings of
+/// the roads or distributions that use the spline. The result is shown as a
+/// [`SplinePreview`]; committing it rewrites the control points and removes the
+/// surface projection, so no runtime raycasting is needed afterwards.
+///
+/// Requires avian3d physics.
+#[cfg(feature = "avian3d")]
+#[allow(clippy::too_many_arguments)]
+pub fn handle_bake_projection_hotkey(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    bindings: Res<EditorKeyBindings>,
+    spatial_query: SpatialQuery,
+    splines: Query<(Entity, &Spline, &GlobalTransform), With<SelectedSpline>>,
+    roads: Query<(&SplineRoad, &SplineMeshProjection)>,
+    distributions: Query<(&SplineDistribution, &SplineMeshProjection)>,
+) {
+    if !settings.enabled
+        || !settings.hotkeys_enabled
+        || !keyboard.just_pressed(bindings.bake_projection)
+    {
+        return;
+    }
+
+    for (entity, spline, spline_transform) in &splines {
+        let projection = roads
+            .iter()
+            .filter(|(road, _)| road.spline == entity)
+            .map(|(_, projection)| projection)
+            .chain(
+                distributions
+                    .iter()
+                    .filter(|(distribution, _)| distribution.spline == entity)
+                    .map(|(_, projection)| projection),
+            )
+            .find(|projection| projection.enabled);
+
+        let Some(projection) = projection else {
+            continue;
+        };
+
+        let baked = project_spline(&spatial_query, spline, spline_transform, projection);
+        commands
+            .entity(entity)
+            .insert(SplinePreview::baked_projection(baked));
+    }
+}
+
+/// System to join two selected splines with the [`EditorKeyBindings::join`] hotkey
+/// (`J` by default).
+///
+/// The splines are connected at their nearest endpoints. The first spline keeps
+/// the joined result, and the [`Spline`] is removed from the second entity so
+/// undoing the join can put it back.
+pub fn handle_join_hotkey(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    bindings: Res<EditorKeyBindings>,
+    mut history: ResMut<EditHistory>,
+    mut splines: Query<(Entity, &mut Spline, &GlobalTransform), With<SelectedSpline>>,
+    selected_points: Query<Entity, With<SelectedControlPoint>>,
+) {
+    if !settings.enabled || !settings.hotkeys_enabled || !keyboard.just_pressed(bindings.join) {
+        return;
+    }
+
+    // Exactly two splines must be selected
+    let mut selected = splines.iter().map(|(e, s, t)| (e, s.clone(), *t));
+    let (
+        Some((keep_entity, mut keep_spline, keep_transform)),
+        Some((other_entity, mut other_spline, other_transform)),
+        None,
+    ) = (selected.next(), selected.next(), selected.next())
+    else {
+        return;
+    };
+    let keep_before = keep_spline.clone();
+    let other_before = other_spline.clone();
+
+    // Bring the other spline into the local space of the kept spline
+    let to_local = keep_transform.affine().inverse();
+    for point in &mut other_spline.control_points {
+        *point = to_local.transform_point3(other_transform.transform_point(*point));
+    }
+
+    // Orient both splines so the nearest endpoints meet
+    let (Some(&keep_start), Some(&keep_end), Some(&other_start), Some(&other_end)) = (
+        keep_spline.control_points.first(),
+        keep_spline.control_points.last(),
+        other_spline.control_points.first(),
+        other_spline.control_points.last(),
+    ) else {
+        return;
+    };
+    let pairings = [
+        (keep_end.distance(other_start), false, false),
+        (keep_end.distance(other_end), false, true),
+        (keep_start.distance(other_start), true, false),
+        (keep_start.distance(other_end), true, true),
+    ];
+    let (_, reverse_keep, reverse_other) = pairings
+        .into_iter()
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .unwrap();
+
+    if reverse_keep {
+        keep_spline.reverse();
+    }
+    if reverse_other {
+        other_spline.reverse();
+    }
+
+    let Some(joined) = keep_spline.join(&other_spline) else {
+        return;
+    };
+
+    history.record([(keep_entity, keep_before), (other_entity, other_before)]);
+    if let Ok((_, mut spline, _)) = splines.get_mut(keep_entity) {
+        *spline = joined;
+    }
+    commands
+        .entity(other_entity)
+        .remove::<(Spline, SelectedSpline)>();
+
+    // Point indices no longer match after joining
+    for entity in &selected_points {
+        commands.entity(entity).remove::<SelectedControlPoint>();
+    }
+}
+
+/// How far duplicated splines are moved from the originals, in world space.
+const DUPLICATE_OFFSET: Vec3 = Vec3::new(0.5, 0.0, 0.5);
+
+/// System to duplicate the selected splines with `Shift` and [`EditorKeyBindings::duplicate`].
+///
+/// Each copy is moved by a small offset so it doesn't hide the original, and the
+/// selection moves to the copies. Undoing removes the copies' splines again.
+/// Ignored while the camera is flying, where `Shift` with the movement keys
+/// moves fast.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_duplicate_hotkey(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    bindings: Res<EditorKeyBindings>,
+    mut history: ResMut<EditHistory>,
+    camera_mode: Option<Res<CameraMode>>,
+    mode_override: Option<Res<CameraModeOverride>>,
+    splines: Query<Entity, With<SelectedSpline>>,
+    selected_points: Query<Entity, With<SelectedControlPoint>>,
+) {
+    if !settings.enabled
+        || !settings.hotkeys_enabled
+        || !ModifierKey::Shift.pressed(&keyboard)
+        || !keyboard.just_pressed(bindings.duplicate)
+        || camera_is_flying(camera_mode.as_deref(), mode_override.as_deref())
+    {
+        return;
+    }
+
+    let copies: Vec<Entity> = splines
+        .iter()
+        .map(|entity| duplicate_spline(&mut commands, entity, DUPLICATE_OFFSET))
+        .collect();
+    for &copy in &copies {
+        commands.entity(copy).insert(SelectedSpline);
+    }
+    history.record_created(copies);
+    clear_all_selections(&mut commands, splines.iter(), selected_points.iter());
+}
+
+/// Spawn a copy of the spline on `entity`, moved by `offset` in world space, and
+/// return the new entity.
+///
+/// Only the [`Spline`], the entity's [`Transform`] and its parent ([`ChildOf`]) are
+/// copied, so the copy sits in the same space as the original. Control point
+/// markers aren't: the editor creates them for the copy like for any new spline.
+/// Roads, distributions and other components using the original stay with it.
+/// Nothing is spawned if `entity` has no spline when the commands are applied.
+pub fn duplicate_spline(commands: &mut Commands, entity: Entity, offset: Vec3) -> Entity {
+    let copy = commands.spawn_empty().id();
+    commands.queue(move |world: &mut World| {
+        let Some(mut spline) = world.get::<Spline>(entity).cloned() else {
+            if let Ok(copy) = world.get_entity_mut(copy) {
+                copy.despawn();
+            }
+            return;
+        };
+        // Move the points by the offset as seen in the spline's local space
+        let global_transform = world
+            .get::<GlobalTransform>(entity)
+            .copied()
+            .unwrap_or_default();
+        let local_offset = global_transform.affine().inverse().transform_vector3(offset);
+        spline.translate(local_offset);
+        let transform = world.get::<Transform>(entity).copied();
+        let parent = world.get::<ChildOf>(entity).map(ChildOf::parent);
+
+        if let Ok(mut copy) = world.get_entity_mut(copy) {
+            copy.insert(spline);
+            if let Some(transform) = transform {
+                copy.insert((transform, global_transform));
+            }
+            if let Some(parent) = parent {
+                copy.insert(ChildOf(parent));
+            }
+        }
+    });
+    copy
+}
+
 fn handle_add_point(
     _commands: &mut Commands,
     splines: &mut Query<(Entity, &mut Spline), With<SelectedSpline>>,
     selected_points: &Query<(Entity, &ControlPointMarker), With<SelectedControlPoint>>,
+    snap: impl Fn(Entity, Vec3) -> Vec3,
 ) {
     // Find the highest selected index per spline
     let mut insert_after: std::collections::HashMap<Entity, usize> = std::collections::HashMap::new();
@@ -86,6 +440,7 @@ fn handle_add_point(
                 last + Vec3::X
             }
         };
+        let new_pos = snap(entity, new_pos);
 
         // For Bézier splines, we need to add 3 points (handle, anchor, handle)
         if spline.spline_type == SplineType::CubicBezier {
@@ -100,6 +455,82 @@ fn handle_add_point(
     }
 }
 
+/// Insert a control point before the lowest selected index (or the start of the spline).
+/// At the first point, the spline is extended backward, opposite to its starting direction.
+/// Bézier splines get a new anchor at the nearest anchor boundary instead.
+fn handle_prepend_point(
+    splines: &mut Query<(Entity, &mut Spline), With<SelectedSpline>>,
+    selected_points: &Query<(Entity, &ControlPointMarker), With<SelectedControlPoint>>,
+    snap: impl Fn(Entity, Vec3) -> Vec3,
+) {
+    // Find the lowest selected index per spline
+    let mut insert_before: std::collections::HashMap<Entity, usize> = std::collections::HashMap::new();
+
+    for (_, marker) in selected_points.iter() {
+        let entry = insert_before.entry(marker.spline_entity).or_insert(marker.index);
+        *entry = (*entry).min(marker.index);
+    }
+
+    for (entity, mut spline) in splines.iter_mut() {
+        let insert_index = insert_before.get(&entity).copied().unwrap_or(0);
+        let is_bezier = spline.spline_type == SplineType::CubicBezier;
+
+        if spline.control_points.is_empty() {
+            spline.insert_point(0, snap(entity, Vec3::ZERO));
+            continue;
+        }
+
+        // Bézier anchors sit at multiples of 3; insert at the nearest one so the
+        // new anchor and handles don't swap roles with the existing points
+        let insert_index = if is_bezier {
+            let last_anchor = (spline.control_points.len() - 1) / 3 * 3;
+            ((insert_index + 1) / 3 * 3).min(last_anchor)
+        } else {
+            insert_index
+        };
+
+        if is_bezier && insert_index > 0 {
+            // Split the segment ending at this anchor: its handles stay with the new
+            // anchor, and the new anchor gets fresh handles towards the next one
+            let next = spline.control_points[insert_index];
+            let midpoint = (spline.control_points[insert_index - 3] + next) / 2.0;
+            let new_pos = snap(entity, midpoint);
+            let step = (next - new_pos) / 3.0;
+            spline.insert_point(insert_index, new_pos + step * 2.0); // Handle
+            spline.insert_point(insert_index, new_pos + step); // Handle
+            spline.insert_point(insert_index, new_pos); // Anchor
+            continue;
+        }
+
+        if insert_index > 0 && insert_index < spline.control_points.len() {
+            // Midpoint between previous and current
+            let midpoint =
+                (spline.control_points[insert_index - 1] + spline.control_points[insert_index]) / 2.0;
+            let new_pos = snap(entity, midpoint);
+            spline.insert_point(insert_index, new_pos);
+            continue;
+        }
+
+        // Extend backward from the first point, opposite to the first segment's direction
+        let first = spline.control_points[0];
+        let backward = spline
+            .control_points
+            .get(1)
+            .and_then(|&next| (first - next).try_normalize())
+            .unwrap_or(Vec3::NEG_X);
+        let new_pos = snap(entity, first + backward * 1.0);
+
+        // For Bézier splines, prepend a full anchor + handles triple
+        if is_bezier {
+            spline.insert_point(0, first + backward * 0.3); // Handle of the old first anchor
+            spline.insert_point(0, new_pos - backward * 0.3); // Handle of the new anchor
+            spline.insert_point(0, new_pos); // Anchor
+        } else {
+            spline.insert_point(0, new_pos);
+        }
+    }
+}
+
 fn handle_delete_points(
     commands: &mut Commands,
     splines: &mut Query<(Entity, &mut Spline), With<SelectedSpline>>,
@@ -136,3 +567,313 @@ fn handle_delete_points(
         commands.entity(marker_entity).remove::<SelectedControlPoint>();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::editor::history::handle_undo_hotkeys;
+    use crate::spline::{SplineChangeKind, SplineChanged};
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn test_hotkeys_only_record_changed_splines() {
+        let mut world = World::new();
+        world.insert_resource(EditorSettings::default());
+        world.insert_resource(EditorKeyBindings::default());
+        world.init_resource::<SelectionState>();
+        world.init_resource::<EditHistory>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        let points = vec![Vec3::ZERO, Vec3::X, Vec3::new(2.0, 1.0, 0.0), Vec3::new(3.0, 0.0, 0.0)];
+        world.spawn((Spline::new(SplineType::CatmullRom, points), SelectedSpline));
+
+        let press = |world: &mut World, key: KeyCode| {
+            let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.clear();
+            keyboard.release_all();
+            keyboard.press(key);
+            world.run_system_once(handle_hotkeys).unwrap();
+        };
+
+        // Deleting without selected points changes nothing
+        press(&mut world, KeyCode::KeyX);
+        assert!(!world.resource::<EditHistory>().can_undo());
+
+        press(&mut world, KeyCode::KeyC);
+        assert!(world.resource::<EditHistory>().can_undo());
+    }
+
+    #[test]
+    fn test_hotkey_edits_emit_spline_changed() {
+        let mut app = App::new();
+        app.add_plugins(crate::spline::SplinePlugin)
+            .init_resource::<EditorSettings>()
+            .init_resource::<EditorKeyBindings>()
+            .init_resource::<SelectionState>()
+            .init_resource::<EditHistory>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .add_systems(Update, handle_hotkeys);
+
+        let points = vec![
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::new(2.0, 1.0, 0.0),
+            Vec3::new(3.0, 0.0, 0.0),
+        ];
+        let spline = app
+            .world_mut()
+            .spawn((
+                Spline::new(SplineType::CatmullRom, [points.clone(), points].concat()),
+                SelectedSpline,
+            ))
+            .id();
+        app.world_mut().spawn((
+            ControlPointMarker {
+                spline_entity: spline,
+                index: 1,
+            },
+            SelectedControlPoint,
+        ));
+        app.update();
+
+        let mut press = |key: KeyCode| {
+            let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.clear();
+            keyboard.release_all();
+            keyboard.press(key);
+            app.update();
+            let messages = app.world().resource::<Messages<SplineChanged>>();
+            messages
+                .iter_current_update_messages()
+                .map(|message| (message.entity, message.kind))
+                .collect::<Vec<_>>()
+        };
+
+        let bindings = EditorKeyBindings::default();
+        assert_eq!(
+            press(bindings.delete_point),
+            [(spline, SplineChangeKind::PointsRemoved)]
+        );
+        assert_eq!(
+            press(bindings.toggle_closed),
+            [(spline, SplineChangeKind::ClosedToggled)]
+        );
+    }
+
+    #[test]
+    fn test_insert_before_bezier_handle_keeps_anchors_aligned() {
+        let mut world = World::new();
+        // Anchors on the X axis, handles lifted above them
+        let anchors = [Vec3::ZERO, Vec3::new(3.0, 0.0, 0.0), Vec3::new(6.0, 0.0, 0.0)];
+        let points = vec![
+            anchors[0],
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(2.0, 1.0, 0.0),
+            anchors[1],
+            Vec3::new(4.0, 1.0, 0.0),
+            Vec3::new(5.0, 1.0, 0.0),
+            anchors[2],
+        ];
+        let spline = world
+            .spawn((Spline::new(SplineType::CubicBezier, points), SelectedSpline))
+            .id();
+        // Select the out-handle of the middle anchor, off an anchor boundary
+        world.spawn((
+            ControlPointMarker {
+                spline_entity: spline,
+                index: 4,
+            },
+            SelectedControlPoint,
+        ));
+
+        world
+            .run_system_once(
+                |mut splines: Query<(Entity, &mut Spline), With<SelectedSpline>>,
+                 selected: Query<(Entity, &ControlPointMarker), With<SelectedControlPoint>>| {
+                    handle_prepend_point(&mut splines, &selected, |_, position| position);
+                },
+            )
+            .unwrap();
+
+        let points = &world.get::<Spline>(spline).unwrap().control_points;
+        assert_eq!(points.len(), 10);
+        // The new anchor splits the segment before the selected handle
+        for anchor in [anchors[0], Vec3::new(1.5, 0.0, 0.0), anchors[1], anchors[2]] {
+            let index = points.iter().position(|&p| p == anchor).unwrap();
+            assert_eq!(index % 3, 0, "anchor {anchor} at {index} in {points:?}");
+        }
+        // The original handles are all off the anchor boundaries
+        for (i, point) in points.iter().enumerate() {
+            if point.y != 0.0 {
+                assert_ne!(i % 3, 0, "handle {point} at {i} in {points:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_duplicate_spline_copies_spline_with_offset() {
+        let mut world = World::new();
+        let transform = Transform::from_xyz(1.0, 2.0, 3.0).with_scale(Vec3::splat(2.0));
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::ZERO,
+                Vec3::X,
+                Vec3::new(2.0, 1.0, 0.0),
+                Vec3::new(3.0, 0.0, 0.0),
+            ],
+        )
+        .with_tension(0.3);
+        let parent = world.spawn(Transform::default()).id();
+        let original = world
+            .spawn((
+                spline.clone(),
+                transform,
+                GlobalTransform::from(transform),
+                ChildOf(parent),
+            ))
+            .id();
+
+        let copy = world
+            .run_system_once(move |mut commands: Commands| {
+                duplicate_spline(&mut commands, original, Vec3::new(1.0, 0.0, 0.0))
+            })
+            .unwrap();
+
+        // The original is untouched
+        let unchanged = &world.get::<Spline>(original).unwrap().control_points;
+        assert_eq!(*unchanged, spline.control_points);
+
+        // The copy keeps everything but the position, moved by the world offset
+        let copied = world.get::<Spline>(copy).unwrap();
+        assert_eq!(copied.spline_type, spline.spline_type);
+        assert_eq!(copied.tension, spline.tension);
+        for (copied, point) in copied.control_points.iter().zip(&spline.control_points) {
+            assert_eq!(*copied - *point, Vec3::new(0.5, 0.0, 0.0));
+        }
+        assert_eq!(world.get::<Transform>(copy), Some(&transform));
+        assert_eq!(world.get::<ChildOf>(copy), Some(&ChildOf(parent)));
+
+        // Nothing is spawned for entities without a spline
+        let empty = world.spawn_empty().id();
+        let spawned = world
+            .run_system_once(move |mut commands: Commands| {
+                duplicate_spline(&mut commands, empty, Vec3::ONE)
+            })
+            .unwrap();
+        assert!(world.get_entity(spawned).is_err());
+    }
+
+    #[test]
+    fn test_undo_restores_joined_splines() {
+        let mut world = World::new();
+        world.insert_resource(EditorSettings::default());
+        world.insert_resource(EditorKeyBindings::default());
+        world.init_resource::<EditHistory>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        let first = Spline::new(
+            SplineType::CatmullRom,
+            vec![Vec3::ZERO, Vec3::X, Vec3::new(2.0, 0.0, 0.0), Vec3::new(3.0, 0.0, 0.0)],
+        );
+        let second = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(4.0, 0.0, 0.0),
+                Vec3::new(5.0, 0.0, 0.0),
+                Vec3::new(6.0, 0.0, 0.0),
+                Vec3::new(7.0, 0.0, 0.0),
+            ],
+        );
+        let entities = [first.clone(), second.clone()].map(|spline| {
+            world
+                .spawn((spline, GlobalTransform::default(), SelectedSpline))
+                .id()
+        });
+
+        let press = |world: &mut World, keys: &[KeyCode]| {
+            let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.clear();
+            keyboard.release_all();
+            for &key in keys {
+                keyboard.press(key);
+            }
+            world.run_system_once(handle_join_hotkey).unwrap();
+            world.run_system_once(handle_undo_hotkeys).unwrap();
+        };
+        let splines = |world: &World| {
+            entities.map(|entity| world.get::<Spline>(entity).map(|s| s.control_points.len()))
+        };
+
+        // One entity keeps the joined spline, the other loses its spline
+        press(&mut world, &[KeyCode::KeyJ]);
+        let joined = splines(&world);
+        assert!(matches!(joined, [Some(8), None] | [None, Some(8)]), "{joined:?}");
+        assert!(world.resource::<EditHistory>().can_undo());
+
+        // Undo brings both splines back unchanged
+        press(&mut world, &[KeyCode::ControlLeft, KeyCode::KeyZ]);
+        let mut restored: Vec<Spline> = entities
+            .iter()
+            .map(|&entity| world.get::<Spline>(entity).unwrap().clone())
+            .collect();
+        restored.sort_by(|a, b| a.control_points[0].x.total_cmp(&b.control_points[0].x));
+        assert_eq!(restored[0].control_points, first.control_points);
+        assert_eq!(restored[1].control_points, second.control_points);
+
+        // Redo joins them again
+        press(&mut world, &[KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::KeyZ]);
+        assert_eq!(splines(&world), joined);
+    }
+
+    #[test]
+    fn test_undo_removes_duplicated_spline() {
+        let mut world = World::new();
+        world.insert_resource(EditorSettings::default());
+        world.insert_resource(EditorKeyBindings::default());
+        world.init_resource::<EditHistory>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        let points = vec![
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::new(2.0, 1.0, 0.0),
+            Vec3::new(3.0, 0.0, 0.0),
+        ];
+        let original = world
+            .spawn((Spline::new(SplineType::CatmullRom, points), SelectedSpline))
+            .id();
+
+        let press = |world: &mut World, keys: &[KeyCode]| {
+            let mut keyboard = world.resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.clear();
+            keyboard.release_all();
+            for &key in keys {
+                keyboard.press(key);
+            }
+            world.run_system_once(handle_duplicate_hotkey).unwrap();
+            world.run_system_once(handle_undo_hotkeys).unwrap();
+        };
+        let copies = |world: &mut World| {
+            world
+                .query::<(Entity, &Spline)>()
+                .iter(world)
+                .map(|(entity, _)| entity)
+                .filter(|&entity| entity != original)
+                .collect::<Vec<_>>()
+        };
+
+        press(&mut world, &[KeyCode::ShiftLeft, KeyCode::KeyD]);
+        let copy = copies(&mut world);
+        assert_eq!(copy.len(), 1);
+        assert!(world.get::<SelectedSpline>(copy[0]).is_some());
+
+        // Undo takes the copy's spline away, redo brings it back
+        press(&mut world, &[KeyCode::ControlLeft, KeyCode::KeyZ]);
+        assert!(copies(&mut world).is_empty());
+        assert!(world.get::<SelectedSpline>(copy[0]).is_none());
+        assert!(world.get::<Spline>(original).is_some());
+
+        let redo = [KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::KeyZ];
+        press(&mut world, &redo);
+        assert_eq!(copies(&mut world), copy);
+        assert!(!world.resource::<EditHistory>().can_redo());
+    }
+}
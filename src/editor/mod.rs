@@ -1,11 +1,25 @@
 mod gizmos;
+mod history;
 mod input;
+mod numeric;
+mod sampling;
 mod selection;
+mod transform;
 
-pub use selection::SelectionState;
+pub use history::EditHistory;
+pub use input::duplicate_spline;
+pub use numeric::{NumericEntry, SetControlPointPosition};
+pub use sampling::sample_screen_space;
+pub use selection::{DragAxis, SelectionState};
+pub use transform::SplineTransformKind;
+
+use std::f32::consts::FRAC_PI_4;
 
 use bevy::{gizmos::config::GizmoConfigStore, prelude::*};
 
+use crate::camera::{CameraInputSystems, CameraMode, CameraModeOverride};
+use crate::spline::{HandleMirror, SplinePlugin};
+
 /// Custom gizmo config group for spline x-ray rendering (shows through geometry).
 #[derive(Default, Reflect, GizmoConfigGroup)]
 pub struct SplineXRayGizmos;
@@ -15,7 +29,8 @@ pub struct SplineXRayGizmos;
 pub struct EditorSettings {
     /// Whether the editor is enabled (responds to mouse input for picking/dragging).
     pub enabled: bool,
-    /// Whether keyboard hotkeys are enabled (A, X, Tab, C, Escape).
+    /// Whether keyboard hotkeys are enabled (A, X, Tab, C, J, M, B, N, G, R, S, Shift+D,
+    /// Enter, Escape, Ctrl+Z).
     /// Set to false if you want to handle hotkeys yourself.
     pub hotkeys_enabled: bool,
     /// Whether clicking on empty space clears the current selection.
@@ -24,6 +39,19 @@ pub struct EditorSettings {
     /// Whether box selection is enabled (click and drag to select multiple points).
     /// Set to false to disable box selection entirely.
     pub box_selection_enabled: bool,
+    /// Modifier key that must be held to start a box selection.
+    /// When set, a plain click on empty space only clears the selection.
+    pub box_selection_modifier: Option<ModifierKey>,
+    /// How close the cursor must come to a spline's curve, in logical pixels, to
+    /// hover it and select the whole spline on click. Control points take priority.
+    pub curve_pick_distance: f32,
+    /// Grid size that dragged and added control points snap to, in world units.
+    /// `None` disables snapping.
+    pub snap_increment: Option<f32>,
+    /// Modifier key that temporarily disables grid snapping while held.
+    pub snap_bypass_modifier: Option<ModifierKey>,
+    /// How dragging a Bézier handle moves the paired handle across its anchor.
+    pub handle_mirror: HandleMirror,
     /// Whether to show gizmos (spline curves and control points).
     pub show_gizmos: bool,
     /// Whether to show Bézier handle lines and CatmullRom connections.
@@ -32,12 +60,29 @@ pub struct EditorSettings {
     /// When true, control points are hidden for unselected splines.
     /// When false, all splines show their control points (with different colors).
     pub show_control_points_only_for_selected: bool,
+    /// Whether to mark segment joins of selected splines that are less than C2 continuous.
+    /// See [`Spline::continuity_breaks`](crate::spline::Spline::continuity_breaks).
+    pub show_continuity_breaks: bool,
+    /// Whether to draw arrowheads along every spline pointing in its direction of travel.
+    pub show_direction_arrows: bool,
+    /// Whether to draw tick marks across selected splines at every
+    /// [`distance_tick_interval`](Self::distance_tick_interval) of arc length.
+    pub show_distance_ticks: bool,
+    /// Arc length between distance ticks, in world units.
+    pub distance_tick_interval: f32,
     /// Whether to show spline gizmos through geometry (x-ray mode).
     /// When true, splines are rendered twice: once normally and once with
     /// depth bias to show through occluding geometry with faded colors.
     pub xray_enabled: bool,
     /// Opacity multiplier for x-ray (occluded) gizmo pass (0.0 - 1.0).
     pub xray_opacity: f32,
+    /// Depth bias of the x-ray gizmo pass (-1.0 - 1.0).
+    ///
+    /// -1.0 draws x-ray lines in front of all geometry, 0.0 uses normal depth
+    /// testing. Gizmo groups are depth tested against each other as well, so a
+    /// strongly negative bias also draws the x-ray lines over other gizmo groups
+    /// with a higher bias; raise it if they cover your own always-on-top gizmos.
+    pub xray_depth_bias: f32,
     /// Visual appearance settings for gizmos.
     pub visuals: GizmoVisuals,
     /// Color settings for editor gizmos.
@@ -46,6 +91,159 @@ pub struct EditorSettings {
     pub sizes: GizmoSizes,
 }
 
+/// Keys bound to the editor hotkeys handled by [`SplineEditorPlugin`].
+///
+/// Insert a customized resource before adding the plugin to remap them:
+/// ```ignore
+/// app.insert_resource(EditorKeyBindings {
+///     delete_point: KeyCode::Delete,
+///     ..default()
+/// })
+/// .add_plugins(SplineEditorPlugin);
+/// ```
+#[derive(Resource, Debug, Clone)]
+pub struct EditorKeyBindings {
+    /// Add a control point after the selection (with Shift: before it).
+    pub add_point: KeyCode,
+    /// Delete the selected control points.
+    pub delete_point: KeyCode,
+    /// Cycle the type of the selected splines.
+    pub cycle_type: KeyCode,
+    /// Toggle the selected splines between closed and open.
+    pub toggle_closed: KeyCode,
+    /// Discard previewed edits, or deselect all when there are none.
+    pub deselect: KeyCode,
+    /// Undo the last edit while Ctrl is held (with Shift: redo).
+    pub undo: KeyCode,
+    /// Start typing the position of the selected control point (see [`NumericEntry`]).
+    pub numeric_entry: KeyCode,
+    /// Grab the selected splines, moving them with the cursor.
+    ///
+    /// Like [`rotate`](Self::rotate) and [`scale`](Self::scale), ignored while the
+    /// camera is in [`CameraMode::Fly`], whose movement keys overlap them.
+    pub grab: KeyCode,
+    /// Rotate the selected splines around their centroid.
+    pub rotate: KeyCode,
+    /// Scale the selected splines about their centroid.
+    pub scale: KeyCode,
+    /// Duplicate the selected splines while Shift is held (not while flying).
+    pub duplicate: KeyCode,
+    /// Join two selected splines at their nearest endpoints.
+    pub join: KeyCode,
+    /// Preview smoothing the selected splines (press again to smooth further).
+    pub smooth: KeyCode,
+    /// Preview baking surface projection into the selected splines.
+    pub bake_projection: KeyCode,
+    /// Apply previewed edits, or finish a grab, rotate or scale.
+    pub confirm: KeyCode,
+}
+
+impl Default for EditorKeyBindings {
+    fn default() -> Self {
+        Self {
+            add_point: KeyCode::KeyA,
+            delete_point: KeyCode::KeyX,
+            cycle_type: KeyCode::Tab,
+            toggle_closed: KeyCode::KeyC,
+            deselect: KeyCode::Escape,
+            undo: KeyCode::KeyZ,
+            numeric_entry: KeyCode::KeyN,
+            grab: KeyCode::KeyG,
+            rotate: KeyCode::KeyR,
+            scale: KeyCode::KeyS,
+            duplicate: KeyCode::KeyD,
+            join: KeyCode::KeyJ,
+            smooth: KeyCode::KeyM,
+            bake_projection: KeyCode::KeyB,
+            confirm: KeyCode::Enter,
+        }
+    }
+}
+
+/// A modifier key, matching either the left or right physical key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModifierKey {
+    /// Either Shift key.
+    Shift,
+    /// Either Control key.
+    Control,
+    /// Either Alt (Option) key.
+    Alt,
+    /// Either Super (Windows/Command) key.
+    Super,
+}
+
+impl ModifierKey {
+    /// Check whether either the left or right variant of this modifier is held.
+    pub fn pressed(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        let (left, right) = match self {
+            Self::Shift => (KeyCode::ShiftLeft, KeyCode::ShiftRight),
+            Self::Control => (KeyCode::ControlLeft, KeyCode::ControlRight),
+            Self::Alt => (KeyCode::AltLeft, KeyCode::AltRight),
+            Self::Super => (KeyCode::SuperLeft, KeyCode::SuperRight),
+        };
+        keyboard.any_pressed([left, right])
+    }
+}
+
+/// Round each axis of `position` to the nearest multiple of `increment`.
+///
+/// Returns `position` unchanged if `increment` is not positive.
+pub fn snap_to_grid(position: Vec3, increment: f32) -> Vec3 {
+    if increment <= 0.0 {
+        return position;
+    }
+    (position / increment).round() * increment
+}
+
+/// Check whether the camera is in fly mode, where its movement keys take
+/// precedence over editor hotkeys bound to the same keys.
+///
+/// Always `false` without the [`CameraPlugin`](crate::CameraPlugin) resources.
+pub(crate) fn camera_is_flying(
+    mode: Option<&CameraMode>,
+    mode_override: Option<&CameraModeOverride>,
+) -> bool {
+    mode.is_some_and(|&mode| {
+        mode_override.copied().unwrap_or_default().resolve(mode) == CameraMode::Fly
+    })
+}
+
+/// Distance from a camera with the default 45° field of view at which
+/// [`GizmoSizes::constant_screen_size`] leaves radii unchanged.
+pub const SCREEN_SIZE_REFERENCE_DISTANCE: f32 = 10.0;
+
+/// Factor to scale a gizmo `distance` away from the camera by so it keeps the
+/// same size on screen.
+///
+/// Perspective cameras scale with the distance and the field of view, relative to
+/// [`SCREEN_SIZE_REFERENCE_DISTANCE`] at 45°. Orthographic cameras scale with the
+/// visible height instead, and custom projections aren't scaled.
+pub fn screen_size_scale(projection: &Projection, distance: f32) -> f32 {
+    let reference_half_height = SCREEN_SIZE_REFERENCE_DISTANCE * (FRAC_PI_4 / 2.0).tan();
+    match projection {
+        Projection::Perspective(perspective) => {
+            distance * (perspective.fov / 2.0).tan() / reference_half_height
+        }
+        Projection::Orthographic(orthographic) => {
+            orthographic.area.height() / 2.0 / reference_half_height
+        }
+        Projection::Custom(_) => 1.0,
+    }
+}
+
+impl GizmoSizes {
+    /// Factor to scale control point radii by for a point `distance` away from a
+    /// camera with `projection`: 1.0 unless [`GizmoSizes::constant_screen_size`] is set.
+    pub fn point_scale(&self, projection: &Projection, distance: f32) -> f32 {
+        if self.constant_screen_size {
+            screen_size_scale(projection, distance)
+        } else {
+            1.0
+        }
+    }
+}
+
 /// Visual appearance settings for spline gizmos.
 #[derive(Debug, Clone)]
 pub struct GizmoVisuals {
@@ -54,6 +252,17 @@ pub struct GizmoVisuals {
     /// Height offset for projected spline visualization above the terrain surface.
     /// This prevents the spline gizmos from clipping into the terrain.
     pub projection_visual_offset: f32,
+    /// Screen-space error tolerance in pixels for adaptive curve rendering.
+    /// When set, curves are resampled each frame based on the active camera
+    /// instead of using `curve_resolution`. Projected splines are unaffected.
+    pub screen_space_tolerance: Option<f32>,
+    /// World-space floating origin subtracted from gizmo positions before drawing.
+    /// Use this when rendering relative to a floating origin so that splines far
+    /// from the world origin don't jitter due to `f32` precision loss.
+    ///
+    /// The active camera is expected to be placed relative to the same origin;
+    /// picking and dragging undo the shift through [`Self::world_camera_transform`].
+    pub floating_origin: Option<Vec3>,
 }
 
 /// Color settings for spline editor gizmos.
@@ -63,6 +272,8 @@ pub struct GizmoColors {
     pub spline: Color,
     /// Color of selected spline curves.
     pub spline_selected: Color,
+    /// Color of the unselected spline curve under the cursor.
+    pub spline_hovered: Color,
     /// Color of control points on unselected splines.
     pub point: Color,
     /// Color of control points on selected splines.
@@ -75,6 +286,14 @@ pub struct GizmoColors {
     pub endpoint_active: Color,
     /// Color of Bézier handle lines and CatmullRom connection lines.
     pub handle_line: Color,
+    /// Color of continuity break markers.
+    pub continuity_break: Color,
+    /// Color of direction arrows along spline curves.
+    pub direction_arrow: Color,
+    /// Color of distance tick marks along selected spline curves.
+    pub distance_tick: Color,
+    /// Color of preview ("ghost") splines showing a pending edit.
+    pub preview: Color,
 }
 
 /// Size settings for spline editor gizmos.
@@ -92,6 +311,12 @@ pub struct GizmoSizes {
     pub endpoint_scale: f32,
     /// Scale multiplier for endpoint control points when spline is selected.
     pub endpoint_selected_spline_scale: f32,
+    /// Whether control points keep roughly the same size on screen at any zoom.
+    ///
+    /// Radii are scaled with [`screen_size_scale`], so points appear as large as
+    /// they would [`SCREEN_SIZE_REFERENCE_DISTANCE`] away from the camera. Picking
+    /// uses the same scaled radii.
+    pub constant_screen_size: bool,
 }
 
 impl Default for GizmoVisuals {
@@ -99,6 +324,21 @@ impl Default for GizmoVisuals {
         Self {
             curve_resolution: 32,
             projection_visual_offset: 0.3,
+            screen_space_tolerance: None,
+            floating_origin: None,
+        }
+    }
+}
+
+impl GizmoVisuals {
+    /// Get the world-space transform of a camera placed relative to the
+    /// [`floating_origin`](Self::floating_origin).
+    ///
+    /// Rays cast from this transform hit splines where their gizmos are drawn.
+    pub fn world_camera_transform(&self, camera_transform: &GlobalTransform) -> GlobalTransform {
+        match self.floating_origin {
+            Some(origin) => GlobalTransform::from_translation(origin) * *camera_transform,
+            None => *camera_transform,
         }
     }
 }
@@ -108,12 +348,17 @@ impl Default for GizmoColors {
         Self {
             spline: Color::srgb(0.5, 0.5, 0.5),
             spline_selected: Color::srgb(1.0, 0.8, 0.2),
+            spline_hovered: Color::srgb(0.85, 0.85, 0.85),
             point: Color::srgb(0.3, 0.3, 0.8),
             point_active: Color::srgb(0.5, 0.5, 1.0),
             point_selected: Color::srgb(1.0, 0.4, 0.4),
             endpoint: Color::srgb(0.8, 0.2, 0.8),
             endpoint_active: Color::srgb(1.0, 0.4, 1.0),
             handle_line: Color::srgba(0.6, 0.6, 0.6, 0.5),
+            continuity_break: Color::srgb(1.0, 0.3, 0.1),
+            direction_arrow: Color::srgb(0.3, 0.9, 0.4),
+            distance_tick: Color::srgb(0.9, 0.9, 0.9),
+            preview: Color::srgba(0.3, 0.9, 1.0, 0.5),
         }
     }
 }
@@ -127,6 +372,7 @@ impl Default for GizmoSizes {
             point_selected_scale: 1.5,
             endpoint_scale: 1.2,
             endpoint_selected_spline_scale: 1.4,
+            constant_screen_size: false,
         }
     }
 }
@@ -138,11 +384,21 @@ impl Default for EditorSettings {
             hotkeys_enabled: true,
             clear_selection_on_empty_click: true,
             box_selection_enabled: true,
+            box_selection_modifier: None,
+            curve_pick_distance: 8.0,
+            snap_increment: None,
+            snap_bypass_modifier: Some(ModifierKey::Control),
+            handle_mirror: HandleMirror::Free,
             show_gizmos: true,
             show_handle_lines: true,
             show_control_points_only_for_selected: false,
+            show_continuity_breaks: false,
+            show_direction_arrows: false,
+            show_distance_ticks: false,
+            distance_tick_interval: 10.0,
             xray_enabled: true,
             xray_opacity: 0.25,
+            xray_depth_bias: -1.0,
             visuals: GizmoVisuals::default(),
             colors: GizmoColors::default(),
             sizes: GizmoSizes::default(),
@@ -151,6 +407,18 @@ impl Default for EditorSettings {
 }
 
 impl EditorSettings {
+    /// Get the grid increment to snap to, or `None` when snapping is off or the
+    /// [`snap_bypass_modifier`](Self::snap_bypass_modifier) is held.
+    pub fn active_snap_increment(&self, keyboard: &ButtonInput<KeyCode>) -> Option<f32> {
+        if self
+            .snap_bypass_modifier
+            .is_some_and(|modifier| modifier.pressed(keyboard))
+        {
+            return None;
+        }
+        self.snap_increment.filter(|&increment| increment > 0.0)
+    }
+
     /// Toggle the editor on/off.
     pub fn toggle(&mut self) {
         self.enabled = !self.enabled;
@@ -176,27 +444,50 @@ fn sync_gizmo_config(
     let (config, _) = config_store.config_mut::<DefaultGizmoConfigGroup>();
     config.line.width = settings.sizes.line_width;
 
-    // Configure x-ray gizmos (render through geometry)
+    // Configure x-ray gizmos (render through geometry), leaving the group
+    // disabled entirely when x-ray is off
     let (xray_config, _) = config_store.config_mut::<SplineXRayGizmos>();
-    xray_config.line.width = settings.sizes.line_width;
-    xray_config.depth_bias = -1.0; // Always render in front (through geometry)
+    xray_config.enabled = settings.xray_enabled;
+    if settings.xray_enabled {
+        xray_config.line.width = settings.sizes.line_width;
+        xray_config.depth_bias = settings.xray_depth_bias.clamp(-1.0, 1.0);
+    }
 }
 
 /// Plugin that adds interactive spline editing functionality.
 ///
-/// This plugin requires `SplinePlugin` to be added first.
+/// Adds `SplinePlugin` if it isn't added yet, so edits made here are reported as
+/// [`SplineChanged`](crate::spline::SplineChanged) messages.
 ///
 /// # Features
 /// - Visual gizmos for spline curves and control points
 /// - Mouse picking and dragging of control points
+/// - Click the curve body to select a whole spline, or Ctrl-click it to insert a point there
+/// - Translucent previews of pending edits ([`SplinePreview`](crate::spline::SplinePreview))
 /// - Hotkeys for adding/removing points, changing spline type, etc.
 ///
 /// # Hotkeys
+/// - `Ctrl + Click` on the curve: Insert a control point there
+/// - `X` / `Y` / `Z` while dragging: Lock the drag to that world axis (press again to unlock)
 /// - `A`: Add control point after selection
+/// - `Shift + A`: Add control point before selection (extends backward from the first point)
 /// - `X`: Delete selected control point(s)
 /// - `Tab`: Cycle spline type
 /// - `C`: Toggle closed/open spline
-/// - `Escape`: Deselect all
+/// - `Shift + D`: Duplicate the selected splines
+/// - `J`: Join two selected splines at their nearest endpoints
+/// - `M`: Preview smoothing the selected spline (press again to smooth further)
+/// - `B`: Preview baking surface projection into the selected spline (requires physics)
+/// - `Enter`: Apply the previewed edit
+/// - `Escape`: Discard the previewed edit, or deselect all
+/// - `Ctrl + Z` / `Ctrl + Shift + Z`: Undo / redo (see [`EditHistory`])
+/// - `N`: Type the position of the selected control point (see [`NumericEntry`])
+/// - `G` / `R` / `S`: Grab, rotate or scale the selected splines with the mouse
+///   (click or `Enter` to confirm, `Escape` to cancel)
+///
+/// The `A`, `X`, `Tab`, `C`, `Escape`, `Z`, `N`, `G`, `R`, `S`, `D`, `J`, `M`, `B` and
+/// `Enter` bindings can be changed with [`EditorKeyBindings`]. Send
+/// [`SetControlPointPosition`] to move points from UI code.
 ///
 /// # Disabling
 /// Use the `EditorSettings` resource to enable/disable the editor:
@@ -209,9 +500,17 @@ pub struct SplineEditorPlugin;
 
 impl Plugin for SplineEditorPlugin {
     fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<SplinePlugin>() {
+            app.add_plugins(SplinePlugin);
+        }
+
         app.init_gizmo_group::<SplineXRayGizmos>()
             .init_resource::<EditorSettings>()
+            .init_resource::<EditorKeyBindings>()
             .init_resource::<SelectionState>()
+            .init_resource::<EditHistory>()
+            .init_resource::<NumericEntry>()
+            .add_message::<SetControlPointPosition>()
             .add_systems(
                 Update,
                 (
@@ -224,27 +523,107 @@ impl Plugin for SplineEditorPlugin {
                     gizmos::render_control_points,
                     gizmos::sync_control_point_entities,
                     gizmos::cleanup_orphaned_markers,
+                    // Whole-spline transforms (consume their confirming click)
+                    transform::handle_spline_transform,
                     // Selection
                     selection::pick_control_points,
+                    selection::handle_curve_insert,
                     selection::handle_selection_click,
                     selection::handle_point_drag,
                     selection::handle_box_selection,
                     selection::render_box_selection,
-                    // Input
+                    selection::render_drag_axis,
+                    // Input (typed text is consumed before the camera sees it)
+                    numeric::handle_numeric_entry.before(CameraInputSystems),
+                    numeric::apply_control_point_positions,
+                    input::handle_preview_hotkeys,
                     input::handle_hotkeys,
+                    history::handle_undo_hotkeys,
+                    input::handle_join_hotkey,
                 )
                     .chain(),
             );
 
+        app.add_systems(
+            Update,
+            (
+                gizmos::render_direction_arrows,
+                gizmos::render_distance_ticks,
+            )
+                .after(gizmos::render_spline_curves),
+        );
+
+        app.add_systems(
+            Update,
+            input::handle_duplicate_hotkey.after(input::handle_join_hotkey),
+        );
+
+        // Projection baking needs spatial queries, so it only runs with physics
+        #[cfg(feature = "avian3d")]
+        app.add_systems(
+            Update,
+            input::handle_bake_projection_hotkey
+                .before(input::handle_preview_hotkeys)
+                .run_if(gizmos::physics_available),
+        );
+
         // Add spline projection visualization
-        use bevy::transform::TransformSystems;
         // Run projection after physics and transform propagation.
         // Only runs when avian3d physics is available.
+        #[cfg(feature = "avian3d")]
         app.add_systems(
             PostUpdate,
             gizmos::project_spline_visualization
-                .after(TransformSystems::Propagate)
+                .after(bevy::transform::TransformSystems::Propagate)
                 .run_if(gizmos::physics_available),
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    #[test]
+    fn test_snap_to_grid_rounds_negative_coordinates() {
+        let snapped = snap_to_grid(Vec3::new(-1.26, -0.24, -0.76), 0.5);
+        assert!(snapped.distance(Vec3::new(-1.5, 0.0, -1.0)) < 1e-6);
+
+        // Halfway points round away from zero on both sides
+        let snapped = snap_to_grid(Vec3::new(-0.25, 0.25, -2.0), 0.5);
+        assert!(snapped.distance(Vec3::new(-0.5, 0.5, -2.0)) < 1e-6);
+
+        let position = Vec3::new(-0.3, 1.7, 2.2);
+        assert_eq!(snap_to_grid(position, 0.0), position);
+    }
+
+    #[test]
+    fn test_screen_size_scale_grows_with_distance() {
+        let perspective = Projection::Perspective(PerspectiveProjection {
+            fov: FRAC_PI_4,
+            ..default()
+        });
+        let near = screen_size_scale(&perspective, 5.0);
+        let far = screen_size_scale(&perspective, 20.0);
+        assert!((near - 0.5).abs() < 1e-5);
+        assert!((far - 2.0).abs() < 1e-5);
+        let reference = screen_size_scale(&perspective, SCREEN_SIZE_REFERENCE_DISTANCE);
+        assert!((reference - 1.0).abs() < 1e-5);
+
+        // A wider field of view shows more, so points grow to keep their size
+        let wide = Projection::Perspective(PerspectiveProjection {
+            fov: FRAC_PI_2,
+            ..default()
+        });
+        assert!(screen_size_scale(&wide, 5.0) > near);
+
+        let sizes = GizmoSizes::default();
+        assert_eq!(sizes.point_scale(&perspective, 20.0), 1.0);
+        let constant = GizmoSizes {
+            constant_screen_size: true,
+            ..default()
+        };
+        assert_eq!(constant.point_scale(&perspective, 20.0), far);
+    }
+}
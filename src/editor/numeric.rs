@@ -0,0 +1,273 @@
+//! Setting exact control point positions.
+
+use bevy::{input::keyboard::KeyboardInput, prelude::*};
+
+use crate::spline::{ControlPointMarker, SelectedControlPoint, Spline};
+
+use super::{EditHistory, EditorKeyBindings, EditorSettings};
+
+/// Message requesting that a control point be moved to an exact position.
+///
+/// Applied by [`SplineEditorPlugin`](super::SplineEditorPlugin), which records the
+/// change in [`EditHistory`]. Requests for missing splines or out-of-range indices
+/// are ignored with a warning. UI code can send it to drive numeric fields:
+/// ```ignore
+/// fn apply_field(mut writer: MessageWriter<SetControlPointPosition>, field: Res<PointField>) {
+///     writer.write(SetControlPointPosition {
+///         spline: field.spline,
+///         index: field.index,
+///         position: field.value,
+///     });
+/// }
+/// ```
+#[derive(Message, Debug, Clone)]
+pub struct SetControlPointPosition {
+    /// The entity with the Spline component.
+    pub spline: Entity,
+    /// Index of the point in [`Spline::control_points`].
+    pub index: usize,
+    /// The new position, in the spline's local space.
+    pub position: Vec3,
+}
+
+/// System to apply [`SetControlPointPosition`] messages.
+///
+/// Writing the point marks the [`Spline`] as changed, so cached curves, markers and
+/// generated meshes refresh as they do for dragged points.
+pub fn apply_control_point_positions(
+    mut messages: MessageReader<SetControlPointPosition>,
+    mut history: ResMut<EditHistory>,
+    mut splines: Query<&mut Spline>,
+) {
+    for message in messages.read() {
+        let Ok(mut spline) = splines.get_mut(message.spline) else {
+            warn!(
+                "SetControlPointPosition: {:?} has no Spline component",
+                message.spline
+            );
+            continue;
+        };
+        if message.index >= spline.control_points.len() {
+            warn!(
+                "SetControlPointPosition: index {} is out of bounds for {:?} with {} points",
+                message.index,
+                message.spline,
+                spline.control_points.len()
+            );
+            continue;
+        }
+
+        history.record([(message.spline, spline.clone())]);
+        spline.control_points[message.index] = message.position;
+    }
+}
+
+/// Resource holding the position being typed for the selected control point.
+///
+/// Entry starts when [`EditorKeyBindings::numeric_entry`] is pressed with exactly
+/// one control point selected. Coordinates are typed as `x, y, z` in the spline's
+/// local space; empty fields keep the current value. `Enter` applies the position,
+/// `Escape` cancels and `Backspace` deletes the last character.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct NumericEntry {
+    /// The spline entity and point index being edited, while entry is active.
+    pub target: Option<(Entity, usize)>,
+    /// The text typed so far.
+    pub text: String,
+}
+
+impl NumericEntry {
+    /// Parse `text` as comma-separated coordinates, filling empty fields from
+    /// `current`. Returns `None` if a field isn't a number or there are more than three.
+    pub fn parse(text: &str, current: Vec3) -> Option<Vec3> {
+        let fields: Vec<&str> = text.split(',').map(str::trim).collect();
+        if fields.len() > 3 {
+            return None;
+        }
+        let mut position = current;
+        for (axis, field) in fields.into_iter().enumerate() {
+            if !field.is_empty() {
+                position[axis] = field.parse().ok()?;
+            }
+        }
+        Some(position)
+    }
+}
+
+/// System to type an exact position for the selected control point.
+///
+/// While entry is active, key presses are consumed so other hotkeys don't react
+/// to them. Progress is logged, and readable from [`NumericEntry`] for display.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_numeric_entry(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut key_events: MessageReader<KeyboardInput>,
+    settings: Res<EditorSettings>,
+    bindings: Res<EditorKeyBindings>,
+    mut entry: ResMut<NumericEntry>,
+    selected_points: Query<&ControlPointMarker, With<SelectedControlPoint>>,
+    splines: Query<&Spline>,
+    mut writer: MessageWriter<SetControlPointPosition>,
+) {
+    if !settings.enabled || !settings.hotkeys_enabled {
+        entry.target = None;
+        key_events.clear();
+        return;
+    }
+
+    let Some((spline_entity, index)) = entry.target else {
+        key_events.clear();
+        if !keyboard.just_pressed(bindings.numeric_entry) {
+            return;
+        }
+        let mut selected = selected_points.iter();
+        let (Some(marker), None) = (selected.next(), selected.next()) else {
+            return;
+        };
+        keyboard.clear_just_pressed(bindings.numeric_entry);
+        entry.target = Some((marker.spline_entity, marker.index));
+        entry.text.clear();
+        info!("Position of point {} (x, y, z):", marker.index);
+        return;
+    };
+
+    let current = splines
+        .get(spline_entity)
+        .ok()
+        .and_then(|spline| spline.control_points.get(index).copied());
+    let Some(current) = current else {
+        // The point went away while typing
+        entry.target = None;
+        return;
+    };
+
+    let mut changed = false;
+    for event in key_events.read() {
+        if !event.state.is_pressed() {
+            continue;
+        }
+        match event.key_code {
+            KeyCode::Enter | KeyCode::NumpadEnter => {
+                match NumericEntry::parse(&entry.text, current) {
+                    Some(position) => {
+                        writer.write(SetControlPointPosition {
+                            spline: spline_entity,
+                            index,
+                            position,
+                        });
+                    }
+                    None => warn!("Invalid position: {}", entry.text),
+                }
+                entry.target = None;
+                break;
+            }
+            KeyCode::Escape => {
+                entry.target = None;
+                break;
+            }
+            KeyCode::Backspace => {
+                changed |= entry.text.pop().is_some();
+            }
+            _ => {
+                let accepted = |c: &char| c.is_ascii_digit() || matches!(c, '.' | '-' | ',' | ' ');
+                let len = entry.text.len();
+                let typed = event.text.iter().flat_map(|text| text.chars());
+                entry.text.extend(typed.filter(accepted));
+                changed |= entry.text.len() > len;
+            }
+        }
+    }
+    if changed {
+        info!("Position of point {index}: {}", entry.text);
+    }
+
+    // Keep hotkeys from reacting to what was typed
+    keyboard.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::input::{keyboard::Key, ButtonState, InputPlugin};
+
+    use crate::camera::{CameraInputSystems, CameraPlugin, OrbitCamera};
+    use crate::spline::SplineType;
+
+    use super::*;
+
+    #[test]
+    fn test_numeric_entry_parse() {
+        let current = Vec3::new(1.0, 2.0, 3.0);
+        assert_eq!(
+            NumericEntry::parse("-1.5, 0, 4", current),
+            Some(Vec3::new(-1.5, 0.0, 4.0))
+        );
+        // Empty and missing fields keep the current coordinates
+        assert_eq!(
+            NumericEntry::parse(", 5", current),
+            Some(Vec3::new(1.0, 5.0, 3.0))
+        );
+        assert_eq!(NumericEntry::parse("", current), Some(current));
+        assert_eq!(NumericEntry::parse("1, 2, 3, 4", current), None);
+        assert_eq!(NumericEntry::parse("1-", current), None);
+    }
+
+    #[test]
+    fn test_typing_during_entry_leaves_camera_alone() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, InputPlugin, CameraPlugin))
+            .init_resource::<EditorSettings>()
+            .init_resource::<EditorKeyBindings>()
+            .init_resource::<NumericEntry>()
+            .add_message::<SetControlPointPosition>()
+            // Ordered as in SplineEditorPlugin
+            .add_systems(Update, handle_numeric_entry.before(CameraInputSystems));
+
+        let spline = app
+            .world_mut()
+            .spawn(Spline::new(
+                SplineType::CatmullRom,
+                vec![
+                    Vec3::new(-20.0, 0.0, 0.0),
+                    Vec3::ZERO,
+                    Vec3::new(20.0, 0.0, 5.0),
+                    Vec3::new(40.0, 0.0, 0.0),
+                ],
+            ))
+            .id();
+        app.world_mut().spawn((
+            ControlPointMarker {
+                spline_entity: spline,
+                index: 1,
+            },
+            SelectedControlPoint,
+        ));
+        let camera = app.world_mut().spawn(OrbitCamera::default()).id();
+
+        let type_key = |app: &mut App, key_code: KeyCode, text: &str| {
+            let window = Entity::PLACEHOLDER;
+            for state in [ButtonState::Pressed, ButtonState::Released] {
+                app.world_mut().write_message(KeyboardInput {
+                    key_code,
+                    logical_key: Key::Character(text.into()),
+                    state,
+                    text: Some(text.into()),
+                    repeat: false,
+                    window,
+                });
+                app.update();
+            }
+        };
+        let numeric_entry = app.world().resource::<EditorKeyBindings>().numeric_entry;
+        type_key(&mut app, numeric_entry, "n");
+        assert!(app.world().resource::<NumericEntry>().target.is_some());
+
+        // `.` also frames the selection, and digits recall bookmarks
+        let before = app.world().get::<OrbitCamera>(camera).unwrap().clone();
+        type_key(&mut app, KeyCode::Period, ".");
+        type_key(&mut app, KeyCode::Digit1, "1");
+        let after = app.world().get::<OrbitCamera>(camera).unwrap();
+        assert_eq!(after.focus, before.focus);
+        assert_eq!(after.radius, before.radius);
+        assert_eq!(app.world().resource::<NumericEntry>().text, ".1");
+    }
+}
@@ -0,0 +1,116 @@
+//! Screen-space adaptive sampling for gizmo rendering.
+
+use bevy::prelude::*;
+
+use crate::spline::Spline;
+
+/// Minimum subdivision depth per segment.
+/// Guards against S-shaped segments whose midpoint lies on the chord.
+const MIN_DEPTH: u32 = 2;
+
+/// Maximum subdivision depth per segment (at most 2^10 lines per segment).
+const MAX_DEPTH: u32 = 10;
+
+/// Sample a spline adaptively so that the projected curve deviates from its
+/// polyline by at most `tolerance` pixels on screen.
+///
+/// Tight corners close to the camera get more samples while straight or distant
+/// sections get fewer, keeping curves smooth at any zoom level without a high
+/// global `curve_resolution`.
+///
+/// Returns the sampled points in world space.
+pub fn sample_screen_space(
+    spline: &Spline,
+    spline_transform: &GlobalTransform,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    tolerance: f32,
+) -> Vec<Vec3> {
+    let segment_count = spline.segment_count();
+    if segment_count == 0 {
+        return Vec::new();
+    }
+
+    let sampler = ScreenSpaceSampler {
+        spline,
+        spline_transform,
+        camera,
+        camera_transform,
+        tolerance: tolerance.max(0.01),
+    };
+
+    let Some(start) = sampler.evaluate(0.0) else {
+        return Vec::new();
+    };
+
+    let mut points = vec![start];
+    for segment in 0..segment_count {
+        let t0 = segment as f32 / segment_count as f32;
+        let t1 = (segment + 1) as f32 / segment_count as f32;
+        let (Some(p0), Some(p1)) = (sampler.evaluate(t0), sampler.evaluate(t1)) else {
+            continue;
+        };
+        sampler.subdivide(t0, p0, t1, p1, 0, &mut points);
+    }
+
+    points
+}
+
+struct ScreenSpaceSampler<'a> {
+    spline: &'a Spline,
+    spline_transform: &'a GlobalTransform,
+    camera: &'a Camera,
+    camera_transform: &'a GlobalTransform,
+    tolerance: f32,
+}
+
+impl ScreenSpaceSampler<'_> {
+    fn evaluate(&self, t: f32) -> Option<Vec3> {
+        self.spline
+            .evaluate(t)
+            .map(|p| self.spline_transform.transform_point(p))
+    }
+
+    fn to_screen(&self, world: Vec3) -> Option<Vec2> {
+        self.camera
+            .world_to_viewport(self.camera_transform, world)
+            .ok()
+    }
+
+    /// Recursively subdivide `[t0, t1]`, pushing every point after `p0` into `out`.
+    fn subdivide(&self, t0: f32, p0: Vec3, t1: f32, p1: Vec3, depth: u32, out: &mut Vec<Vec3>) {
+        let t_mid = (t0 + t1) * 0.5;
+        let Some(mid) = self.evaluate(t_mid) else {
+            out.push(p1);
+            return;
+        };
+
+        let split = depth < MIN_DEPTH
+            || (depth < MAX_DEPTH && self.screen_error(p0, mid, p1) > self.tolerance);
+
+        if split {
+            self.subdivide(t0, p0, t_mid, mid, depth + 1, out);
+            self.subdivide(t_mid, mid, t1, p1, depth + 1, out);
+        } else {
+            out.push(p1);
+        }
+    }
+
+    /// Pixel distance from the projected midpoint to the projected chord.
+    /// Sections that can't be projected (e.g. behind the camera) count as flat.
+    fn screen_error(&self, p0: Vec3, mid: Vec3, p1: Vec3) -> f32 {
+        let (Some(a), Some(m), Some(b)) =
+            (self.to_screen(p0), self.to_screen(mid), self.to_screen(p1))
+        else {
+            return 0.0;
+        };
+
+        let chord = b - a;
+        let len_sq = chord.length_squared();
+        if len_sq < 1e-6 {
+            return m.distance(a);
+        }
+        let s = ((m - a).dot(chord) / len_sq).clamp(0.0, 1.0);
+        m.distance(a + chord * s)
+    }
+}
@@ -1,25 +1,80 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 
 use crate::spline::{
-    get_effective_control_points, ControlPointMarker, ProjectedSplineCache, SelectedControlPoint,
+    get_effective_control_points, get_effective_curve_points, pick_point, pick_polyline_screen,
+    CachedSplineCurve, ControlPointMarker, ProjectedSplineCache, SelectedControlPoint,
     SelectedSpline, Spline,
 };
 
-use super::EditorSettings;
+use super::{snap_to_grid, EditHistory, EditorSettings};
+
+/// World axis a drag can be locked to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragAxis {
+    /// The world X axis.
+    X,
+    /// The world Y axis.
+    Y,
+    /// The world Z axis.
+    Z,
+}
+
+impl DragAxis {
+    /// Get the axis locked by a key pressed during a drag.
+    pub fn from_key(key: KeyCode) -> Option<Self> {
+        match key {
+            KeyCode::KeyX => Some(Self::X),
+            KeyCode::KeyY => Some(Self::Y),
+            KeyCode::KeyZ => Some(Self::Z),
+            _ => None,
+        }
+    }
+
+    /// Get the unit vector along this axis.
+    pub fn direction(&self) -> Vec3 {
+        match self {
+            Self::X => Vec3::X,
+            Self::Y => Vec3::Y,
+            Self::Z => Vec3::Z,
+        }
+    }
+
+    /// Get the color the axis guide is drawn in.
+    pub fn color(&self) -> Color {
+        match self {
+            Self::X => Color::srgb(0.9, 0.2, 0.2),
+            Self::Y => Color::srgb(0.3, 0.8, 0.2),
+            Self::Z => Color::srgb(0.2, 0.4, 0.9),
+        }
+    }
+}
+
+/// Length of the guide line drawn along a locked drag axis.
+const DRAG_AXIS_GUIDE_LENGTH: f32 = 1000.0;
 
 /// Resource tracking the current selection state.
 #[derive(Resource, Default, Debug, Clone)]
 pub struct SelectionState {
     /// Currently hovered control point, if any.
     pub hovered_point: Option<(Entity, usize)>,
+    /// Currently hovered spline curve, if any.
+    /// Only set when no control point is hovered (points take priority).
+    pub hovered_spline: Option<Entity>,
+    /// Curve parameter `t` under the cursor on the hovered spline.
+    pub hovered_spline_t: Option<f32>,
     /// Whether we're currently dragging a point.
     pub dragging: bool,
     /// The point(s) being dragged: (spline_entity, point_index).
     pub dragged_points: Vec<(Entity, usize)>,
+    /// Local positions of the dragged points when the drag started, in the same
+    /// order as `dragged_points`.
+    pub drag_original_points: Vec<Vec3>,
     /// The plane normal for drag operations (perpendicular to camera).
     pub drag_plane_normal: Vec3,
     /// The initial drag plane point (for consistent plane during drag).
     pub drag_plane_point: Vec3,
+    /// World axis the current drag is locked to, if any.
+    pub drag_axis: Option<DragAxis>,
     /// Whether we're currently box selecting.
     pub box_selecting: bool,
     /// Screen-space start position of box selection.
@@ -44,13 +99,24 @@ pub fn clear_all_selections(
     }
 }
 
-/// System to handle mouse picking of control points.
+/// System to handle mouse picking of control points and spline curves.
 /// Uses projected positions when surface projection is enabled for the spline.
+///
+/// Control points take priority; the curve body is only picked when no point is hovered.
+/// Points are picked within twice their radius, scaled like their spheres when
+/// [`GizmoSizes::constant_screen_size`](super::GizmoSizes::constant_screen_size) is set.
+#[allow(clippy::type_complexity)]
 pub fn pick_control_points(
     settings: Res<EditorSettings>,
     windows: Query<&Window, With<PrimaryWindow>>,
-    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
-    splines: Query<(Entity, &Spline, &GlobalTransform, Option<&ProjectedSplineCache>)>,
+    cameras: Query<(&Camera, &GlobalTransform, &Projection), With<Camera3d>>,
+    splines: Query<(
+        Entity,
+        &Spline,
+        &GlobalTransform,
+        Option<&CachedSplineCurve>,
+        Option<&ProjectedSplineCache>,
+    )>,
     mut selection_state: ResMut<SelectionState>,
 ) {
     if !settings.enabled {
@@ -68,31 +134,41 @@ pub fn pick_control_points(
 
     let Some(cursor_pos) = window.cursor_position() else {
         selection_state.hovered_point = None;
+        selection_state.hovered_spline = None;
+        selection_state.hovered_spline_t = None;
         return;
     };
 
     // Find the active camera (handles multiple Camera3d entities)
-    let Some((camera, camera_transform)) = cameras.iter().find(|(c, _)| c.is_active) else {
+    let Some((camera, camera_transform, projection)) = cameras.iter().find(|(c, _, _)| c.is_active)
+    else {
         return;
     };
+    // Splines are picked in world space, where the camera sits past the floating origin
+    let camera_transform = &settings.visuals.world_camera_transform(camera_transform);
 
     let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_pos) else {
         return;
     };
 
+    let pick_radius = settings.sizes.point_radius * 2.0;
+    let camera_position = camera_transform.translation();
     let mut closest: Option<(Entity, usize, f32)> = None;
 
-    for (entity, spline, spline_transform, projected) in &splines {
+    for (entity, spline, spline_transform, _, projected) in &splines {
         // Use the centralized helper to get effective control points
-        let control_points = get_effective_control_points(spline, projected);
-
-        for (i, &point) in control_points.iter().enumerate() {
-            // Transform point to world space
-            let world_point = spline_transform.transform_point(point);
-            // Simple sphere-ray intersection
-            let pick_radius = settings.sizes.point_radius * 2.0;
-            if let Some(dist) = ray_sphere_intersect(ray.origin, ray.direction, world_point, pick_radius) {
-                if closest.is_none() || dist < closest.unwrap().2 {
+        let world_points: Vec<Vec3> = get_effective_control_points(spline, projected)
+            .iter()
+            .map(|&p| spline_transform.transform_point(p))
+            .collect();
+
+        for (i, &point) in world_points.iter().enumerate() {
+            let scale = settings
+                .sizes
+                .point_scale(projection, camera_position.distance(point));
+            let radius = pick_radius * scale;
+            if let Some((_, dist)) = pick_point(&[point], ray.origin, *ray.direction, radius) {
+                if closest.is_none_or(|(_, _, closest_dist)| dist < closest_dist) {
                     closest = Some((entity, i, dist));
                 }
             }
@@ -100,28 +176,48 @@ pub fn pick_control_points(
     }
 
     selection_state.hovered_point = closest.map(|(e, i, _)| (e, i));
-}
 
-fn ray_sphere_intersect(origin: Vec3, direction: Dir3, center: Vec3, radius: f32) -> Option<f32> {
-    let oc = origin - center;
-    let a = direction.dot(*direction);
-    let b = 2.0 * oc.dot(*direction);
-    let c = oc.dot(oc) - radius * radius;
-    let discriminant = b * b - 4.0 * a * c;
+    if selection_state.hovered_point.is_some() {
+        selection_state.hovered_spline = None;
+        selection_state.hovered_spline_t = None;
+        return;
+    }
+
+    // No point hovered - pick the curve body on screen using the cached curve
+    // segments, so thin and distant curves are as easy to hit as near ones
+    let mut closest_spline: Option<(Entity, f32, f32)> = None;
 
-    if discriminant < 0.0 {
-        None
-    } else {
-        let t = (-b - discriminant.sqrt()) / (2.0 * a);
-        if t > 0.0 {
-            Some(t)
-        } else {
-            None
+    for (entity, spline, spline_transform, cache, projected) in &splines {
+        if !spline.is_valid() {
+            continue;
+        }
+        let Some(curve_points) = get_effective_curve_points(cache, projected) else {
+            continue;
+        };
+        let screen_points: Vec<Option<Vec2>> = curve_points
+            .iter()
+            .map(|&p| {
+                let world = spline_transform.transform_point(p);
+                camera.world_to_viewport(camera_transform, world).ok()
+            })
+            .collect();
+
+        // The curve points are sampled evenly in t, so the polyline fraction is t
+        if let Some((t, dist)) =
+            pick_polyline_screen(&screen_points, cursor_pos, settings.curve_pick_distance)
+        {
+            if closest_spline.is_none_or(|(_, _, closest_dist)| dist < closest_dist) {
+                closest_spline = Some((entity, t, dist));
+            }
         }
     }
+
+    selection_state.hovered_spline = closest_spline.map(|(e, _, _)| e);
+    selection_state.hovered_spline_t = closest_spline.map(|(_, t, _)| t);
 }
 
 /// System to handle selection on mouse click.
+#[allow(clippy::too_many_arguments)]
 pub fn handle_selection_click(
     mut commands: Commands,
     mouse: Res<ButtonInput<MouseButton>>,
@@ -129,7 +225,7 @@ pub fn handle_selection_click(
     selection_state: Res<SelectionState>,
     keyboard: Res<ButtonInput<KeyCode>>,
     _splines: Query<(Entity, &Spline)>,
-    _selected_splines: Query<Entity, With<SelectedSpline>>,
+    selected_splines: Query<Entity, With<SelectedSpline>>,
     markers: Query<(Entity, &ControlPointMarker)>,
     selected_points: Query<Entity, With<SelectedControlPoint>>,
     _cameras: Query<&GlobalTransform, With<Camera>>,
@@ -148,6 +244,9 @@ pub fn handle_selection_click(
     }
 
     let shift_held = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
+    // Ctrl-clicking the curve body inserts a point instead (see `handle_curve_insert`)
+    let ctrl_held =
+        keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
 
     if let Some((spline_entity, point_index)) = selection_state.hovered_point {
         // Check if this point is already selected
@@ -187,17 +286,102 @@ pub fn handle_selection_click(
                 }
             }
         }
+    } else if let Some(spline_entity) = selection_state.hovered_spline.filter(|_| !ctrl_held) {
+        // Clicking on the curve body selects the whole spline without picking a point
+        if shift_held {
+            // Shift-click toggles the spline selection
+            if selected_splines.contains(spline_entity) {
+                commands.entity(spline_entity).remove::<SelectedSpline>();
+            } else {
+                commands.entity(spline_entity).insert(SelectedSpline);
+            }
+        } else {
+            clear_all_selections(
+                &mut commands,
+                selected_splines.iter(),
+                selected_points.iter(),
+            );
+            commands.entity(spline_entity).insert(SelectedSpline);
+        }
     }
     // Note: We don't clear selection on empty click here anymore.
     // Box selection handles that - if user just clicks without dragging,
     // selection is cleared when box selection ends with no points selected.
 }
 
+/// System to insert a control point where the curve is Ctrl-clicked.
+///
+/// The new point becomes the only selected point, ready to be dragged. Bézier
+/// splines gain a handle/anchor/handle triple (see [`Spline::insert_point_at`]).
+#[allow(clippy::too_many_arguments)]
+pub fn handle_curve_insert(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    settings: Res<EditorSettings>,
+    selection_state: Res<SelectionState>,
+    mut history: ResMut<EditHistory>,
+    mut splines: Query<&mut Spline>,
+    selected_splines: Query<Entity, With<SelectedSpline>>,
+    markers: Query<(Entity, &ControlPointMarker)>,
+    selected_points: Query<Entity, With<SelectedControlPoint>>,
+) {
+    if !settings.enabled || selection_state.dragging || selection_state.box_selecting {
+        return;
+    }
+
+    let ctrl_held =
+        keyboard.pressed(KeyCode::ControlLeft) || keyboard.pressed(KeyCode::ControlRight);
+    if !ctrl_held || !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+
+    let (Some(spline_entity), Some(t)) = (
+        selection_state.hovered_spline,
+        selection_state.hovered_spline_t,
+    ) else {
+        return;
+    };
+    let Ok(mut spline) = splines.get_mut(spline_entity) else {
+        return;
+    };
+    let before = spline.clone();
+    let Some(index) = spline.insert_point_at(t) else {
+        return;
+    };
+    history.record([(spline_entity, before)]);
+
+    clear_all_selections(
+        &mut commands,
+        selected_splines.iter(),
+        selected_points.iter(),
+    );
+    commands.entity(spline_entity).insert(SelectedSpline);
+
+    // Markers are rebuilt for the changed spline next frame, keeping the selection
+    // by index, so selecting the current marker at `index` selects the new point
+    if let Some((marker_entity, _)) = markers
+        .iter()
+        .find(|(_, marker)| marker.spline_entity == spline_entity && marker.index == index)
+    {
+        commands.entity(marker_entity).insert(SelectedControlPoint);
+    }
+}
+
 /// System to handle dragging control points.
 /// When multiple points are selected, they all move together maintaining relative positions.
+///
+/// With [`EditorSettings::snap_increment`] set, a single dragged point snaps to the
+/// grid and multiple points move in whole grid steps. Dragging a single Bézier handle
+/// moves its paired handle as set by [`EditorSettings::handle_mirror`].
+///
+/// Pressing `X`, `Y` or `Z` during a drag locks movement to that world axis through
+/// the point the drag started from; pressing the same key again unlocks it. The key
+/// press is consumed so it doesn't also trigger the editor hotkey bound to it.
 #[allow(clippy::too_many_arguments)]
 pub fn handle_point_drag(
     mouse: Res<ButtonInput<MouseButton>>,
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
     settings: Res<EditorSettings>,
     mut selection_state: ResMut<SelectionState>,
     windows: Query<&Window, With<PrimaryWindow>>,
@@ -205,6 +389,8 @@ pub fn handle_point_drag(
     mut splines: Query<(&mut Spline, &GlobalTransform, Option<&ProjectedSplineCache>)>,
     markers: Query<(Entity, &ControlPointMarker)>,
     selected_points: Query<Entity, With<SelectedControlPoint>>,
+    mut history: ResMut<EditHistory>,
+    mut drag_start: Local<Vec<(Entity, Spline)>>,
 ) {
     if !settings.enabled {
         return;
@@ -221,6 +407,7 @@ pub fn handle_point_drag(
             });
 
             selection_state.dragging = true;
+            selection_state.drag_axis = None;
 
             if hovered_is_selected {
                 // Drag all selected points together
@@ -234,6 +421,29 @@ pub fn handle_point_drag(
                 selection_state.dragged_points = vec![(spline_entity, point_index)];
             }
 
+            // Points are moved from where they started, not from where they were last frame
+            selection_state.drag_original_points = selection_state
+                .dragged_points
+                .iter()
+                .map(|&(entity, index)| {
+                    splines
+                        .get(entity)
+                        .ok()
+                        .and_then(|(spline, _, _)| spline.control_points.get(index).copied())
+                        .unwrap_or_default()
+                })
+                .collect();
+
+            // Keep the splines as they were, to record the drag for undo once it ends
+            drag_start.clear();
+            for &(entity, _) in &selection_state.dragged_points {
+                if drag_start.iter().all(|(recorded, _)| *recorded != entity) {
+                    if let Ok((spline, _, _)) = splines.get(entity) {
+                        drag_start.push((entity, spline.clone()));
+                    }
+                }
+            }
+
             if let Some((_, camera_transform)) = cameras.iter().find(|(c, _)| c.is_active) {
                 selection_state.drag_plane_normal = camera_transform.forward().as_vec3();
 
@@ -253,6 +463,31 @@ pub fn handle_point_drag(
     if mouse.just_released(MouseButton::Left) {
         selection_state.dragging = false;
         selection_state.dragged_points.clear();
+        selection_state.drag_original_points.clear();
+        selection_state.drag_axis = None;
+
+        // Record the drag if it moved anything
+        let moved = drag_start.drain(..).filter(|(entity, before)| {
+            splines
+                .get(*entity)
+                .is_ok_and(|(spline, _, _)| spline.control_points != before.control_points)
+        });
+        history.record(moved.collect::<Vec<_>>());
+    }
+
+    // Toggle the axis lock, taking the key from the hotkeys that run after us
+    if selection_state.dragging {
+        let pressed = keyboard
+            .get_just_pressed()
+            .find_map(|&key| DragAxis::from_key(key).map(|axis| (key, axis)));
+        if let Some((key, axis)) = pressed {
+            keyboard.clear_just_pressed(key);
+            selection_state.drag_axis = if selection_state.drag_axis == Some(axis) {
+                None
+            } else {
+                Some(axis)
+            };
+        }
     }
 
     // Continue drag - move all dragged points by the same delta
@@ -266,26 +501,36 @@ pub fn handle_point_drag(
         let Some((camera, camera_transform)) = cameras.iter().find(|(c, _)| c.is_active) else {
             return;
         };
+        let camera_transform = &settings.visuals.world_camera_transform(camera_transform);
         let Ok(ray) = camera.viewport_to_world(camera_transform, cursor_pos) else {
             return;
         };
 
-        // Use the fixed drag plane point for consistent behavior (in world space)
+        // The drag plane point stays where the drag started, so the delta below is
+        // the total movement of the drag (in world space)
         let plane_point = selection_state.drag_plane_point;
         let plane_normal = selection_state.drag_plane_normal;
 
-        // Calculate the new position on the drag plane (in world space)
-        let Some(new_world_pos) = ray_plane_intersect(
-            ray.origin,
-            *ray.direction,
-            plane_point,
-            plane_normal,
-        ) else {
+        // Calculate the new position on the drag plane, or on the locked axis (in world space)
+        let new_world_pos = match selection_state.drag_axis {
+            Some(axis) => {
+                ray_line_closest_point(ray.origin, *ray.direction, plane_point, axis.direction())
+            }
+            None => ray_plane_intersect(ray.origin, *ray.direction, plane_point, plane_normal),
+        };
+        let Some(new_world_pos) = new_world_pos else {
             return;
         };
 
-        // Calculate delta in world space
-        let world_delta = new_world_pos - plane_point;
+        // Calculate delta in world space, in whole grid steps when snapping so
+        // multiple points keep their relative positions
+        let snap_increment = settings.active_snap_increment(&keyboard);
+        let world_delta = match snap_increment {
+            Some(increment) => {
+                snap_to_grid(new_world_pos, increment) - snap_to_grid(plane_point, increment)
+            }
+            None => new_world_pos - plane_point,
+        };
 
         // Apply delta to all dragged points
         let dragged_points = selection_state.dragged_points.clone();
@@ -295,32 +540,50 @@ pub fn handle_point_drag(
             let (spline_entity, point_index) = dragged_points[0];
             if let Ok((mut spline, spline_transform, _)) = splines.get_mut(spline_entity) {
                 if point_index < spline.control_points.len() {
+                    let world_pos = match snap_increment {
+                        Some(increment) => snap_to_grid(new_world_pos, increment),
+                        None => new_world_pos,
+                    };
                     // Convert world position to local space
                     let inverse_transform = spline_transform.affine().inverse();
-                    let local_pos = inverse_transform.transform_point3(new_world_pos);
+                    let local_pos = inverse_transform.transform_point3(world_pos);
                     spline.control_points[point_index] = local_pos;
+                    // Keep Bézier joins smooth by moving the paired handle too
+                    spline.mirror_bezier_handle(point_index, settings.handle_mirror);
                 }
             }
         } else {
-            // For multi-point drag, apply delta to maintain relative positions
-            for &(spline_entity, point_index) in &dragged_points {
+            // For multi-point drag, move every point from its start by the same delta
+            let originals = selection_state.drag_original_points.clone();
+            for (&(spline_entity, point_index), &original) in dragged_points.iter().zip(&originals)
+            {
                 if let Ok((mut spline, spline_transform, _)) = splines.get_mut(spline_entity) {
                     if point_index < spline.control_points.len() {
-                        // Convert world delta to local space delta
-                        // We need to transform the delta direction, not position
-                        let inverse_transform = spline_transform.affine().inverse();
-                        let local_delta = inverse_transform.transform_vector3(world_delta);
-                        spline.control_points[point_index] += local_delta;
+                        spline.control_points[point_index] =
+                            dragged_position(original, world_delta, spline_transform);
                     }
                 }
             }
-            // Update the drag plane point so delta is computed correctly next frame
-            selection_state.drag_plane_point = new_world_pos;
         }
     }
 }
 
-fn ray_plane_intersect(
+/// Local position of a point that started a drag at `original`, after the drag
+/// has moved a total of `world_delta`.
+///
+/// Computing positions from the start of the drag instead of adding each frame's
+/// movement keeps dragged points from drifting apart through rounding errors.
+pub(super) fn dragged_position(
+    original: Vec3,
+    world_delta: Vec3,
+    spline_transform: &GlobalTransform,
+) -> Vec3 {
+    // Transform the delta as a direction, not a position
+    let inverse_transform = spline_transform.affine().inverse();
+    original + inverse_transform.transform_vector3(world_delta)
+}
+
+pub(super) fn ray_plane_intersect(
     ray_origin: Vec3,
     ray_direction: Vec3,
     plane_point: Vec3,
@@ -339,6 +602,27 @@ fn ray_plane_intersect(
     }
 }
 
+/// Get the point on the line through `line_point` along `line_direction` that is
+/// closest to the ray. Returns `None` if the ray runs parallel to the line.
+fn ray_line_closest_point(
+    ray_origin: Vec3,
+    ray_direction: Vec3,
+    line_point: Vec3,
+    line_direction: Vec3,
+) -> Option<Vec3> {
+    let ray_direction = ray_direction.normalize_or_zero();
+    let line_direction = line_direction.normalize_or_zero();
+    let b = line_direction.dot(ray_direction);
+    let denom = 1.0 - b * b;
+    if denom < 1e-6 {
+        return None;
+    }
+
+    let w = line_point - ray_origin;
+    let s = (b * ray_direction.dot(w) - line_direction.dot(w)) / denom;
+    Some(line_point + line_direction * s)
+}
+
 /// System to handle box selection of multiple control points.
 #[allow(clippy::too_many_arguments)]
 pub fn handle_box_selection(
@@ -375,16 +659,16 @@ pub fn handle_box_selection(
     let Some((camera, camera_transform)) = cameras.iter().find(|(c, _)| c.is_active) else {
         return;
     };
+    let camera_transform = &settings.visuals.world_camera_transform(camera_transform);
 
     let shift_held = keyboard.pressed(KeyCode::ShiftLeft) || keyboard.pressed(KeyCode::ShiftRight);
 
-    // Start box selection when clicking on empty space
-    if mouse.just_pressed(MouseButton::Left) && selection_state.hovered_point.is_none() {
-        selection_state.box_selecting = true;
-        selection_state.box_start = cursor_pos;
-        selection_state.box_end = cursor_pos;
-
-        // Always clear control point selection when starting box select (unless shift held)
+    // Clicking on empty space clears the selection and starts box selection
+    if mouse.just_pressed(MouseButton::Left)
+        && selection_state.hovered_point.is_none()
+        && selection_state.hovered_spline.is_none()
+    {
+        // Always clear control point selection on empty click (unless shift held)
         // Only clear spline selection if clear_selection_on_empty_click is enabled
         if !shift_held {
             // Always clear control point selections
@@ -398,6 +682,15 @@ pub fn handle_box_selection(
                 }
             }
         }
+
+        let modifier_held = settings
+            .box_selection_modifier
+            .is_none_or(|modifier| modifier.pressed(&keyboard));
+        if modifier_held {
+            selection_state.box_selecting = true;
+            selection_state.box_start = cursor_pos;
+            selection_state.box_end = cursor_pos;
+        }
     }
 
     // Update box end position while selecting
@@ -506,6 +799,7 @@ pub fn render_box_selection(
                 plane_point,
                 cam_forward.as_vec3(),
             ) {
+                // The camera is already relative to the floating origin
                 corners_3d.push(world_pos);
             }
         }
@@ -519,3 +813,190 @@ pub fn render_box_selection(
         gizmos.line(corners_3d[3], corners_3d[0], color);
     }
 }
+
+/// Render a guide line along the axis the current drag is locked to.
+pub fn render_drag_axis(
+    selection_state: Res<SelectionState>,
+    settings: Res<EditorSettings>,
+    mut gizmos: Gizmos,
+) {
+    if !settings.enabled || !settings.show_gizmos || !selection_state.dragging {
+        return;
+    }
+    let Some(axis) = selection_state.drag_axis else {
+        return;
+    };
+
+    let center =
+        selection_state.drag_plane_point - settings.visuals.floating_origin.unwrap_or(Vec3::ZERO);
+    let half_length = axis.direction() * DRAG_AXIS_GUIDE_LENGTH / 2.0;
+    gizmos.line(center - half_length, center + half_length, axis.color());
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use bevy::{
+        camera::{ComputedCameraValues, RenderTargetInfo},
+        ecs::system::RunSystemOnce,
+        prelude::*,
+        window::PrimaryWindow,
+    };
+
+    use super::{handle_point_drag, pick_control_points, SelectionState};
+    use crate::editor::{EditHistory, EditorSettings};
+    use crate::spline::{ControlPointMarker, SelectedControlPoint, Spline, SplineType};
+
+    /// Spawn a primary window with the cursor at `cursor` (in physical pixels from
+    /// its center) and a perspective camera at the origin looking down -Z.
+    pub(crate) fn spawn_view(world: &mut World, cursor: Vec2) {
+        let mut window = Window::default();
+        let size = window.resolution.physical_size();
+        let center = size.as_vec2() / 2.0;
+        window.set_physical_cursor_position(Some((center + cursor).as_dvec2()));
+        world.spawn((window, PrimaryWindow));
+
+        let camera = Camera {
+            computed: ComputedCameraValues {
+                clip_from_view: Mat4::perspective_infinite_reverse_rh(
+                    std::f32::consts::FRAC_PI_4,
+                    size.x as f32 / size.y as f32,
+                    0.1,
+                ),
+                target_info: Some(RenderTargetInfo {
+                    physical_size: size,
+                    scale_factor: 1.0,
+                }),
+                ..default()
+            },
+            ..default()
+        };
+        world.spawn((Camera3d::default(), camera, GlobalTransform::IDENTITY));
+    }
+
+    #[test]
+    fn test_multi_point_drag_keeps_relative_offsets() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<EditorSettings>()
+            .init_resource::<SelectionState>()
+            .init_resource::<EditHistory>()
+            .add_systems(Update, handle_point_drag);
+        spawn_view(app.world_mut(), Vec2::ZERO);
+
+        let originals = vec![
+            Vec3::new(0.1, 0.2, -20.3),
+            Vec3::new(-4.7, 1.9, -20.05),
+            Vec3::new(6.3, -0.001, -27.77),
+            Vec3::new(10.0, 0.0, -20.0),
+        ];
+        let transform = GlobalTransform::from(
+            Transform::from_xyz(3.0, -1.0, 2.0)
+                .with_rotation(Quat::from_rotation_y(0.2))
+                .with_scale(Vec3::splat(1.3)),
+        );
+        let spline = app
+            .world_mut()
+            .spawn((
+                Spline::new(SplineType::CatmullRom, originals.clone()),
+                transform,
+            ))
+            .id();
+        for index in [0, 2] {
+            app.world_mut().spawn((
+                ControlPointMarker {
+                    spline_entity: spline,
+                    index,
+                },
+                SelectedControlPoint,
+            ));
+        }
+        app.world_mut()
+            .resource_mut::<SelectionState>()
+            .hovered_point = Some((spline, 0));
+
+        let points = |app: &App| {
+            app.world()
+                .get::<Spline>(spline)
+                .unwrap()
+                .control_points
+                .clone()
+        };
+        let move_cursor = |app: &mut App, cursor: Vec2| {
+            let mut windows = app
+                .world_mut()
+                .query_filtered::<&mut Window, With<PrimaryWindow>>();
+            let mut window = windows.single_mut(app.world_mut()).unwrap();
+            let center = window.resolution.physical_size().as_vec2() / 2.0;
+            window.set_physical_cursor_position(Some((center + cursor).as_dvec2()));
+        };
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(MouseButton::Left);
+        app.update();
+        assert!(app.world().resource::<SelectionState>().dragging);
+
+        // Uneven cursor movement over several frames, as at a varying framerate
+        for cursor in [
+            Vec2::new(3.0, -1.0),
+            Vec2::new(41.0, 7.5),
+            Vec2::new(-90.25, 130.0),
+            Vec2::new(225.5, -60.0),
+        ] {
+            app.world_mut()
+                .resource_mut::<ButtonInput<MouseButton>>()
+                .clear();
+            move_cursor(&mut app, cursor);
+            app.update();
+
+            let moved = points(&app);
+            assert!(moved[0].distance(originals[0]) > 0.01);
+            assert!((moved[2] - moved[0]).distance(originals[2] - originals[0]) < 1e-3);
+            assert_eq!(moved[1], originals[1]);
+            assert_eq!(moved[3], originals[3]);
+        }
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(MouseButton::Left);
+        app.update();
+        assert!(!app.world().resource::<SelectionState>().dragging);
+        assert!(app.world().resource::<EditHistory>().can_undo());
+    }
+
+    #[test]
+    fn test_pick_control_point_past_floating_origin() {
+        let origin = Vec3::new(10_000.0, 0.0, 5_000.0);
+        let mut world = World::new();
+        let mut settings = EditorSettings::default();
+        settings.visuals.floating_origin = Some(origin);
+        world.insert_resource(settings);
+        world.init_resource::<SelectionState>();
+
+        // Cursor in the middle of the window, on the camera's forward axis. The
+        // camera sits at the render-space origin, which is `origin` in world space
+        spawn_view(&mut world, Vec2::ZERO);
+
+        let spline = world
+            .spawn((
+                Spline::new(
+                    SplineType::CatmullRom,
+                    vec![
+                        origin + Vec3::new(-5.0, 3.0, -20.0),
+                        origin + Vec3::new(0.0, 0.0, -20.0),
+                        origin + Vec3::new(5.0, 3.0, -20.0),
+                        origin + Vec3::new(10.0, 0.0, -20.0),
+                    ],
+                ),
+                GlobalTransform::IDENTITY,
+            ))
+            .id();
+
+        world.run_system_once(pick_control_points).unwrap();
+
+        // The point drawn under the cursor is picked, not one near the world origin
+        let selection = world.resource::<SelectionState>();
+        assert_eq!(selection.hovered_point, Some((spline, 1)));
+    }
+}
@@ -0,0 +1,327 @@
+//! Grabbing, rotating and scaling whole splines.
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::camera::{CameraMode, CameraModeOverride};
+use crate::spline::{SelectedSpline, Spline};
+
+use super::{
+    camera_is_flying,
+    selection::{ray_plane_intersect, SelectionState},
+    EditHistory, EditorKeyBindings, EditorSettings,
+};
+
+/// Kind of whole-spline transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplineTransformKind {
+    /// Move the splines on a plane facing the camera.
+    Grab,
+    /// Rotate the splines around the view axis through their centroid.
+    Rotate,
+    /// Scale the splines about their centroid.
+    Scale,
+}
+
+/// A whole-spline transform in progress, following the cursor until confirmed.
+#[derive(Debug, Clone)]
+pub struct ActiveSplineTransform {
+    /// What the cursor drives.
+    kind: SplineTransformKind,
+    /// The selected splines as they were when the transform started.
+    originals: Vec<(Entity, Spline, GlobalTransform)>,
+    /// World-space centroid of all original control points.
+    pivot: Vec3,
+    /// The pivot's viewport position.
+    pivot_screen: Vec2,
+    /// Camera forward direction, the grab plane normal and rotation axis.
+    view_axis: Vec3,
+    /// Cursor position when the transform started.
+    start_cursor: Vec2,
+    /// Cursor position the splines were last updated for.
+    last_cursor: Option<Vec2>,
+}
+
+impl ActiveSplineTransform {
+    fn new(
+        kind: SplineTransformKind,
+        originals: Vec<(Entity, Spline, GlobalTransform)>,
+        cursor: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+    ) -> Option<Self> {
+        let world_points: Vec<Vec3> = originals
+            .iter()
+            .flat_map(|(_, spline, transform)| {
+                spline
+                    .control_points
+                    .iter()
+                    .map(|&p| transform.transform_point(p))
+            })
+            .collect();
+        if world_points.is_empty() {
+            return None;
+        }
+        let pivot = world_points.iter().sum::<Vec3>() / world_points.len() as f32;
+        let pivot_screen = camera.world_to_viewport(camera_transform, pivot).ok()?;
+
+        Some(Self {
+            kind,
+            originals,
+            pivot,
+            pivot_screen,
+            view_axis: camera_transform.forward().as_vec3(),
+            start_cursor: cursor,
+            last_cursor: None,
+        })
+    }
+
+    /// Put the original splines back, returning them.
+    fn restore(
+        self,
+        splines: &mut Query<(Entity, &mut Spline, &GlobalTransform)>,
+    ) -> Vec<(Entity, Spline, GlobalTransform)> {
+        for (entity, original, _) in &self.originals {
+            if let Ok((_, mut spline, _)) = splines.get_mut(*entity) {
+                *spline = original.clone();
+            }
+        }
+        self.originals
+    }
+
+    /// Transform the original splines for the current cursor position.
+    fn apply(
+        &mut self,
+        cursor: Vec2,
+        camera: &Camera,
+        camera_transform: &GlobalTransform,
+        splines: &mut Query<(Entity, &mut Spline, &GlobalTransform)>,
+    ) {
+        if self.last_cursor == Some(cursor) {
+            return;
+        }
+
+        let start = self.start_cursor - self.pivot_screen;
+        let current = cursor - self.pivot_screen;
+        let offset = match self.kind {
+            SplineTransformKind::Grab => {
+                let hit = |position: Vec2| {
+                    let ray = camera.viewport_to_world(camera_transform, position).ok()?;
+                    ray_plane_intersect(ray.origin, *ray.direction, self.pivot, self.view_axis)
+                };
+                let (Some(from), Some(to)) = (hit(self.start_cursor), hit(cursor)) else {
+                    return;
+                };
+                to - from
+            }
+            _ => Vec3::ZERO,
+        };
+        // Viewport y points down, so a positive angle turns clockwise on screen,
+        // matching a positive rotation around the axis pointing into the screen
+        let rotation = Quat::from_axis_angle(self.view_axis, start.angle_to(current));
+        let factor = current.length() / start.length().max(1.0);
+
+        for (entity, original, transform) in &self.originals {
+            let Ok((_, mut spline, _)) = splines.get_mut(*entity) else {
+                continue;
+            };
+            let to_local = transform.affine().inverse();
+            let local_pivot = to_local.transform_point3(self.pivot);
+            let mut updated = original.clone();
+            match self.kind {
+                SplineTransformKind::Grab => {
+                    updated.translate(to_local.transform_vector3(offset));
+                }
+                SplineTransformKind::Rotate => {
+                    let (_, spline_rotation, _) = transform.to_scale_rotation_translation();
+                    let local_rotation = spline_rotation.inverse() * rotation * spline_rotation;
+                    updated.rotate_around(local_pivot, local_rotation);
+                }
+                SplineTransformKind::Scale => updated.scale_around(local_pivot, factor),
+            }
+            *spline = updated;
+        }
+        self.last_cursor = Some(cursor);
+    }
+}
+
+/// System to grab, rotate and scale the selected splines as a whole.
+///
+/// [`EditorKeyBindings::grab`], [`rotate`](EditorKeyBindings::rotate) and
+/// [`scale`](EditorKeyBindings::scale) start a transform that follows the cursor:
+/// grabbing moves the splines on a plane facing the camera, rotating turns them
+/// around the view axis through their centroid, and scaling follows the cursor's
+/// distance from the centroid. Pressing another of the keys switches the
+/// kind of transform. A left click or [`confirm`](EditorKeyBindings::confirm)
+/// (`Enter`) confirms, recording an undo step, and
+/// [`deselect`](EditorKeyBindings::deselect) (`Escape`) cancels; the confirming
+/// click and keys are consumed.
+///
+/// The keys don't start or switch transforms while the camera is flying, since
+/// [`FlyCamera`](crate::camera::FlyCamera) moves with some of them.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_spline_transform(
+    mut keyboard: ResMut<ButtonInput<KeyCode>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    settings: Res<EditorSettings>,
+    bindings: Res<EditorKeyBindings>,
+    selection_state: Res<SelectionState>,
+    mut history: ResMut<EditHistory>,
+    camera_mode: Option<Res<CameraMode>>,
+    mode_override: Option<Res<CameraModeOverride>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    mut splines: Query<(Entity, &mut Spline, &GlobalTransform)>,
+    selected: Query<Entity, With<SelectedSpline>>,
+    mut active: Local<Option<ActiveSplineTransform>>,
+) {
+    if !settings.enabled || !settings.hotkeys_enabled {
+        if let Some(transform) = active.take() {
+            transform.restore(&mut splines);
+        }
+        return;
+    }
+
+    let Some(cursor) = windows.single().ok().and_then(Window::cursor_position) else {
+        return;
+    };
+    let Some((camera, camera_transform)) = cameras.iter().find(|(c, _)| c.is_active) else {
+        return;
+    };
+    let camera_transform = &settings.visuals.world_camera_transform(camera_transform);
+
+    let keys = [
+        (bindings.grab, SplineTransformKind::Grab),
+        (bindings.rotate, SplineTransformKind::Rotate),
+        (bindings.scale, SplineTransformKind::Scale),
+    ];
+    let pressed = keys
+        .into_iter()
+        .find(|(key, _)| keyboard.just_pressed(*key));
+    let flying = camera_is_flying(camera_mode.as_deref(), mode_override.as_deref());
+    if let Some((key, kind)) = pressed.filter(|_| !selection_state.dragging && !flying) {
+        // Switching kinds starts over from the original shapes
+        let originals = match active.take() {
+            Some(previous) => previous.restore(&mut splines),
+            None => selected
+                .iter()
+                .filter_map(|entity| splines.get(entity).ok())
+                .map(|(entity, spline, transform)| (entity, spline.clone(), *transform))
+                .collect(),
+        };
+        *active = ActiveSplineTransform::new(kind, originals, cursor, camera, camera_transform);
+        if active.is_some() {
+            keyboard.clear_just_pressed(key);
+        }
+    }
+
+    let Some(transform) = active.as_mut() else {
+        return;
+    };
+
+    if keyboard.just_pressed(bindings.deselect) {
+        keyboard.clear_just_pressed(bindings.deselect);
+        if let Some(transform) = active.take() {
+            transform.restore(&mut splines);
+        }
+        return;
+    }
+
+    transform.apply(cursor, camera, camera_transform, &mut splines);
+
+    if mouse.just_pressed(MouseButton::Left) || keyboard.just_pressed(bindings.confirm) {
+        mouse.clear_just_pressed(MouseButton::Left);
+        keyboard.clear_just_pressed(bindings.confirm);
+        if let Some(transform) = active.take() {
+            history.record(
+                transform
+                    .originals
+                    .into_iter()
+                    .map(|(entity, spline, _)| (entity, spline)),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{prelude::*, window::PrimaryWindow};
+
+    use super::handle_spline_transform;
+    use crate::editor::selection::{tests::spawn_view, SelectionState};
+    use crate::editor::{EditHistory, EditorKeyBindings, EditorSettings};
+    use crate::spline::{SelectedSpline, Spline, SplineType};
+
+    #[test]
+    fn test_rebound_deselect_cancels_transform() {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<EditorSettings>()
+            .init_resource::<SelectionState>()
+            .init_resource::<EditHistory>()
+            .insert_resource(EditorKeyBindings {
+                deselect: KeyCode::Backspace,
+                ..default()
+            })
+            .add_systems(Update, handle_spline_transform);
+        spawn_view(app.world_mut(), Vec2::ZERO);
+
+        let points = vec![
+            Vec3::new(-2.0, 0.0, -10.0),
+            Vec3::new(0.0, 1.0, -10.0),
+            Vec3::new(2.0, 0.0, -10.0),
+            Vec3::new(4.0, 1.0, -10.0),
+        ];
+        let spline = app
+            .world_mut()
+            .spawn((
+                Spline::new(SplineType::CatmullRom, points.clone()),
+                GlobalTransform::IDENTITY,
+                SelectedSpline,
+            ))
+            .id();
+
+        let move_cursor = |app: &mut App, cursor: Vec2| {
+            let mut windows = app
+                .world_mut()
+                .query_filtered::<&mut Window, With<PrimaryWindow>>();
+            let mut window = windows.single_mut(app.world_mut()).unwrap();
+            let center = window.resolution.physical_size().as_vec2() / 2.0;
+            window.set_physical_cursor_position(Some((center + cursor).as_dvec2()));
+        };
+        let press = |app: &mut App, key: KeyCode| {
+            let mut keyboard = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keyboard.clear();
+            keyboard.press(key);
+        };
+
+        // Grab and follow the cursor
+        press(&mut app, KeyCode::KeyG);
+        app.update();
+        move_cursor(&mut app, Vec2::new(80.0, 0.0));
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        app.update();
+        let moved = &app.world().get::<Spline>(spline).unwrap().control_points;
+        assert!(moved[0].distance(points[0]) > 0.1);
+
+        // Escape is no longer bound, so it leaves the transform running
+        press(&mut app, KeyCode::Escape);
+        app.update();
+        let moved = &app.world().get::<Spline>(spline).unwrap().control_points;
+        assert!(moved[0].distance(points[0]) > 0.1);
+
+        // The rebound key cancels, restoring the spline without an undo step
+        press(&mut app, KeyCode::Backspace);
+        app.update();
+        move_cursor(&mut app, Vec2::new(-60.0, 30.0));
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        app.update();
+        let restored = &app.world().get::<Spline>(spline).unwrap().control_points;
+        assert_eq!(restored, &points);
+        assert!(!app.world().resource::<EditHistory>().can_undo());
+    }
+}
@@ -49,6 +49,48 @@ impl CoordinateFrame {
         Self { tangent, right, up }
     }
 
+    /// Carry this frame from a sample at `from` to the next sample at `to`, whose
+    /// tangent is `tangent`, with as little twist about the tangent as possible.
+    ///
+    /// Uses the double reflection method. Applying it sample by sample along a
+    /// curve gives rotation-minimizing frames, which (unlike [`Self::from_tangent`])
+    /// don't flip when the tangent passes through the up axis.
+    pub fn transport(&self, from: Vec3, to: Vec3, tangent: Vec3) -> Self {
+        let tangent = tangent.normalize_or_zero();
+        if tangent == Vec3::ZERO {
+            return *self;
+        }
+
+        // Reflect the frame across the plane bisecting the two sample points
+        let step = to - from;
+        let step_sq = step.length_squared();
+        let (reflected_up, reflected_tangent) = if step_sq > 1e-12 {
+            (
+                self.up - step * (2.0 * step.dot(self.up) / step_sq),
+                self.tangent - step * (2.0 * step.dot(self.tangent) / step_sq),
+            )
+        } else {
+            (self.up, self.tangent)
+        };
+
+        // Reflect again so the reflected tangent lines up with the new tangent
+        let correction = tangent - reflected_tangent;
+        let correction_sq = correction.length_squared();
+        let up = if correction_sq > 1e-12 {
+            reflected_up - correction * (2.0 * correction.dot(reflected_up) / correction_sq)
+        } else {
+            reflected_up
+        };
+
+        // Re-orthogonalize against accumulated floating point error
+        let up = (up - tangent * up.dot(tangent)).normalize_or_zero();
+        if up == Vec3::ZERO {
+            return Self::from_tangent(tangent);
+        }
+        let right = tangent.cross(up).normalize_or_zero();
+        Self { tangent, right, up }
+    }
+
     /// Build a coordinate frame from a forward direction (Bevy convention: -Z is forward).
     ///
     /// This is useful for entity orientations where you want the entity's
@@ -163,4 +205,31 @@ mod tests {
         let expected = origin + frame.right + frame.up * 2.0 + frame.tangent * 3.0;
         assert!((world - expected).length() < 0.001);
     }
+
+    #[test]
+    fn test_transport_keeps_frame_continuous() {
+        // Sample half a vertical loop: level, straight up, then level upside down
+        let positions: Vec<Vec3> = (0..=64)
+            .map(|i| {
+                let angle = i as f32 / 64.0 * std::f32::consts::PI;
+                Vec3::new(0.0, 1.0 - angle.cos(), angle.sin()) * 5.0
+            })
+            .collect();
+        let tangent = |i: usize| {
+            let angle = i as f32 / 64.0 * std::f32::consts::PI;
+            Vec3::new(0.0, angle.sin(), angle.cos())
+        };
+
+        let mut frame = CoordinateFrame::from_tangent(tangent(0));
+        for i in 1..positions.len() {
+            let next = frame.transport(positions[i - 1], positions[i], tangent(i));
+            assert!(next.is_valid());
+            assert!(next.up.dot(frame.up) > 0.99);
+            assert!(next.tangent.dot(next.up).abs() < 1e-4);
+            frame = next;
+        }
+
+        // After going over the top the frame is upside down, as on a real loop
+        assert!((frame.up - Vec3::NEG_Y).length() < 0.01);
+    }
 }
@@ -4,7 +4,7 @@
 //!
 //! ## Features
 //!
-//! - Multiple spline types: Cubic Bézier, Catmull-Rom, B-Spline
+//! - Multiple spline types: Cubic Bézier, Catmull-Rom, B-Spline, TCB (Kochanek–Bartels)
 //! - Interactive control point editing with gizmos
 //! - Serializable with Bevy's scene system (RON format)
 //! - Orbit and fly camera controls
@@ -91,34 +91,53 @@ pub use editor::SplineEditorPlugin;
 
 /// Convenient re-exports of commonly used types.
 pub mod prelude {
-    pub use crate::camera::{CameraMode, CameraPlugin, FlyCamera, OrbitCamera};
+    pub use crate::camera::{
+        cursor_pivot, frame_points, pinch_zoom_factor, CameraBookmark, CameraBookmarks,
+        CameraInputSystems, CameraMode, CameraModeOverride, CameraPlugin, CameraSettings,
+        FlyCamera, OrbitCamera, RailCamera, SnapView, SnappedView,
+    };
     pub use crate::distribution::{
         DistributedInstance, DistributionOrientation, DistributionSource, DistributionSpacing,
-        SplineDistribution, SplineDistributionPlugin,
+        RoadEdge, RoadSide, SplineDistribution, SplineDistributionPlugin,
     };
     pub use crate::path_follow::{
-        FollowerEvent, FollowerEventKind, FollowerState, LoopMode, SplineFollowPlugin,
-        SplineFollower,
+        bake_follower_clip, FollowerEvent, FollowerEventKind, FollowerState, FollowerTrigger,
+        FollowerTriggered, FollowerTriggers, LoopMode, SplineFollowPlugin, SplineFollower,
+        SplinePath, TriggerMode,
     };
     pub use crate::road::{
-        create_road_segment_mesh, GeneratedIntersectionMesh, GeneratedRoadMesh,
-        RoadConnection, RoadEnd, RoadIntersection, SplineRoad, SplineRoadPlugin,
+        bake_roads, create_road_segment_mesh, road_geometry, GeneratedIntersectionMesh,
+        GeneratedRoadMesh, IntersectionUvMode, RoadConnection, RoadEnd, RoadGeometry,
+        RoadGeometryOptions, RoadIntersection, RoadUvMode, SplineRoad, SplineRoadPlugin,
     };
     pub use crate::spline::{
-        CachedSplineCurve, ControlPointMarker, ProjectedSplineCache, SelectedControlPoint,
-        SelectedSpline, Spline, SplineEvaluator, SplinePlugin, SplineType,
-        get_effective_control_points, get_effective_curve_points,
+        CachedSplineCurve, ContinuityLevel, ControlPointMarker, HandleMirror,
+        ProjectedSplineCache, SelectedControlPoint, SelectedSpline, Spline, Spline2d,
+        SplineChangeKind, SplineChanged, SplineBasis, SplineError, SplineEvaluator, SplinePlugin,
+        SplinePoint, SplinePreview, SplineType, TcbParams,
+        get_effective_control_points, get_effective_curve_points, pick_point, pick_polyline,
+        pick_polyline_screen,
     };
+    #[cfg(feature = "avian3d")]
+    pub use crate::spline::project_spline;
 
     #[cfg(feature = "editor")]
     pub use crate::editor::{
-        EditorSettings, GizmoColors, GizmoSizes, GizmoVisuals, SelectionState, SplineEditorPlugin,
+        duplicate_spline, sample_screen_space, screen_size_scale, snap_to_grid, DragAxis,
+        EditHistory, EditorKeyBindings, EditorSettings, GizmoColors, GizmoSizes, GizmoVisuals,
+        ModifierKey, NumericEntry, SelectionState, SetControlPointPosition, SplineEditorPlugin,
+        SplineTransformKind,
     };
 
     pub use crate::surface::{
-        ProjectionHit, ProjectionLayer, RawProjectionHit, SplineMeshProjection,
-        SplineMeshProjectionPlugin, cast_projection_ray, create_projection_filter,
+        ProjectionHit, RawProjectionHit, SplineMeshProjection, SplineMeshProjectionPlugin,
     };
 
+    #[cfg(feature = "avian3d")]
+    pub use crate::surface::{cast_projection_ray, create_projection_filter, ProjectionLayer};
+
+    #[cfg(feature = "mesh_raycast")]
+    pub use crate::surface::{cast_projection_ray_mesh, ProjectionSurface};
+
     pub use crate::geometry::CoordinateFrame;
 }
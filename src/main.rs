@@ -83,32 +83,44 @@ fn show_help(
         *shown = !*shown;
     }
 
-    if keyboard.just_pressed(KeyCode::KeyH) || camera_mode.is_changed() {
-        if *shown {
-            println!("\n=== Spline Editor Help ===");
-            println!("Camera Mode: {}", camera_mode.name());
-            println!("Editor: {}", if editor_settings.enabled { "Enabled" } else { "Disabled" });
-            println!();
-            println!("Controls:");
-            println!("  H         - Toggle this help");
-            println!("  F         - Toggle camera mode (Orbit/Fly)");
-            println!("  A         - Add control point");
-            println!("  X         - Delete selected point");
-            println!("  Tab       - Cycle spline type");
-            println!("  C         - Toggle closed/open");
-            println!("  Escape    - Deselect all");
-            println!();
-            println!("Camera (Orbit):");
-            println!("  RMB + drag - Orbit");
-            println!("  Scroll     - Zoom");
-            println!();
-            println!("Camera (Fly):");
-            println!("  RMB + drag - Look");
-            println!("  WASD       - Move");
-            println!("  Q/Space    - Up");
-            println!("  E/Ctrl     - Down");
-            println!("  Shift      - Sprint");
-            println!("========================\n");
-        }
+    if (keyboard.just_pressed(KeyCode::KeyH) || camera_mode.is_changed()) && *shown {
+        println!("\n=== Spline Editor Help ===");
+        println!("Camera Mode: {}", camera_mode.name());
+        println!("Editor: {}", if editor_settings.enabled { "Enabled" } else { "Disabled" });
+        println!();
+        println!("Controls:");
+        println!("  H         - Toggle this help");
+        println!("  F         - Toggle camera mode (Orbit/Fly)");
+        println!("  A         - Add control point");
+        println!("  Shift+A   - Add control point before selection");
+        println!("  Ctrl+Click - Insert control point on the curve");
+        println!("  X/Y/Z while dragging - Lock drag to axis");
+        println!("  X         - Delete selected point");
+        println!("  Tab       - Cycle spline type");
+        println!("  C         - Toggle closed/open");
+        println!("  Shift+D   - Duplicate selected splines (not while flying)");
+        println!("  J         - Join two selected splines");
+        println!("  M         - Preview smoothing");
+        println!("  B         - Preview baking surface projection");
+        println!("  Enter     - Apply preview");
+        println!("  Escape    - Discard preview / Deselect all");
+        println!("  Ctrl+Z    - Undo (Ctrl+Shift+Z: redo)");
+        println!("  N         - Type selected point position (x, y, z + Enter)");
+        println!("  G/R/S     - Grab/rotate/scale selected splines (not while flying)");
+        println!();
+        println!("Camera (Orbit):");
+        println!("  RMB + drag - Orbit");
+        println!("  Shift + RMB + drag - Pan");
+        println!("  Scroll     - Zoom");
+        println!("  .          - Frame selection (or all splines)");
+        println!("  Numpad 1/3/7 - Front/right/top view (Ctrl: opposite)");
+        println!();
+        println!("Camera (Fly):");
+        println!("  RMB + drag - Look");
+        println!("  WASD       - Move");
+        println!("  Q/Space    - Up");
+        println!("  E/Ctrl     - Down");
+        println!("  Shift      - Sprint");
+        println!("========================\n");
     }
 }
@@ -0,0 +1,169 @@
+//! Baking follower motion into animation clips.
+//!
+//! Converts a [`SplineFollower`] configuration into a Bevy [`AnimationClip`] so the
+//! authored path can be played, blended, and mixed through the animation graph
+//! instead of being driven by [`update_spline_followers`](super::update_spline_followers).
+
+use bevy::{
+    animation::{animated_field, AnimationTargetId},
+    prelude::*,
+};
+
+use crate::spline::{approximate_arc_length, Spline};
+
+use super::systems::{bank_angle, calculate_orientation};
+use super::{LoopMode, SplineFollower};
+
+/// Bake the motion of a follower into an [`AnimationClip`].
+///
+/// The clip animates `Transform::translation` (and `Transform::rotation` when
+/// `align_to_tangent` is enabled) of the entity identified by `target`, keyed over
+/// the time the follower would take to traverse the spline at its configured speed
+/// (or following the spline's recorded timestamps, when the follower uses them).
+///
+/// One traversal is baked, starting at the spline start (or the end when
+/// `direction` is negative):
+/// - [`LoopMode::Once`] and [`LoopMode::Loop`] bake a single pass; set the
+///   animation to repeat on the player to loop it.
+/// - [`LoopMode::PingPong`] bakes a full there-and-back cycle.
+///
+/// `samples` is the number of keyframes per pass (minimum 2).
+/// The spline's `GlobalTransform` is applied so the clip plays in world space.
+///
+/// Returns `None` if the spline is invalid or the follower has no speed.
+pub fn bake_follower_clip(
+    follower: &SplineFollower,
+    spline: &Spline,
+    spline_transform: &GlobalTransform,
+    target: AnimationTargetId,
+    samples: usize,
+) -> Option<AnimationClip> {
+    if !spline.is_valid() || follower.speed <= 0.0 {
+        return None;
+    }
+
+    // Recorded time range, when the follower replays timestamps
+    let recorded = follower
+        .use_timestamps
+        .then(|| spline.time_range())
+        .flatten();
+
+    // Duration of one pass, matching the follower system.
+    let pass_duration = if let Some((start, end)) = recorded {
+        (end - start) / follower.speed
+    } else if follower.constant_speed {
+        let arc_length_samples = follower
+            .arc_length_samples
+            .unwrap_or_else(|| spline.default_arc_length_samples());
+        let total_length = approximate_arc_length(spline, arc_length_samples);
+        if total_length <= 0.0 {
+            return None;
+        }
+        total_length / follower.speed
+    } else {
+        1.0 / follower.speed
+    };
+
+    // Time into a pass at which the follower reaches t, travelling in `direction`
+    let pass_time = |t: f32, direction: f32| {
+        let forward_time = match recorded {
+            Some((start, _)) => spline
+                .time_at(t)
+                .map_or(0.0, |time| (time - start) / follower.speed),
+            None => t * pass_duration,
+        };
+        if direction >= 0.0 {
+            forward_time
+        } else {
+            pass_duration - forward_time
+        }
+    };
+
+    let samples = samples.max(2);
+    let forward = follower.direction >= 0.0;
+
+    // (time, t, direction) for each keyframe
+    let mut keys: Vec<(f32, f32, f32)> = Vec::with_capacity(samples * 2);
+    for i in 0..samples {
+        let progress = i as f32 / (samples - 1) as f32;
+        let t = if forward { progress } else { 1.0 - progress };
+        let direction = if forward { 1.0 } else { -1.0 };
+        keys.push((pass_time(t, direction), t, direction));
+    }
+
+    if follower.loop_mode == LoopMode::PingPong {
+        for i in 1..samples {
+            let progress = i as f32 / (samples - 1) as f32;
+            let t = if forward { 1.0 - progress } else { progress };
+            let direction = if forward { -1.0 } else { 1.0 };
+            keys.push((pass_duration + pass_time(t, direction), t, direction));
+        }
+    }
+
+    let spline_rotation = spline_transform.to_scale_rotation_translation().1;
+
+    let mut translations = Vec::with_capacity(keys.len());
+    let mut rotations = Vec::with_capacity(keys.len());
+
+    for &(time, t, direction) in &keys {
+        let position_t = follower.ease(t, direction);
+        let (local_position, tangent) = spline.evaluate_pos_tan(position_t)?;
+        let world_position = spline_transform.transform_point(local_position);
+
+        let rotation = if follower.align_to_tangent {
+            let roll = bank_angle(spline, position_t, follower.up_vector, follower.bank_factor);
+            spline_rotation * calculate_orientation(tangent, follower.up_vector, direction, roll)
+        } else {
+            Quat::IDENTITY
+        };
+
+        translations.push((time, world_position + rotation * follower.offset));
+        rotations.push((time, rotation));
+    }
+
+    let mut clip = AnimationClip::default();
+
+    let translation_curve = AnimatableKeyframeCurve::new(translations).ok()?;
+    clip.add_curve_to_target(
+        target,
+        AnimatableCurve::new(animated_field!(Transform::translation), translation_curve),
+    );
+
+    if follower.align_to_tangent {
+        let rotation_curve = AnimatableKeyframeCurve::new(rotations).ok()?;
+        clip.add_curve_to_target(
+            target,
+            AnimatableCurve::new(animated_field!(Transform::rotation), rotation_curve),
+        );
+    }
+
+    Some(clip)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bake_ping_pong_doubles_duration() {
+        let spline = Spline::new(
+            crate::spline::SplineType::CatmullRom,
+            vec![Vec3::ZERO, Vec3::X, Vec3::X * 2.0, Vec3::X * 3.0],
+        );
+        let target = AnimationTargetId::from_name(&Name::new("follower"));
+
+        let once = SplineFollower::new(Entity::PLACEHOLDER)
+            .with_speed(0.5)
+            .with_constant_speed(false);
+        let ping_pong = once.clone().with_loop_mode(LoopMode::PingPong);
+
+        let once_clip =
+            bake_follower_clip(&once, &spline, &GlobalTransform::IDENTITY, target, 16).unwrap();
+        let ping_pong_clip =
+            bake_follower_clip(&ping_pong, &spline, &GlobalTransform::IDENTITY, target, 16)
+                .unwrap();
+
+        assert!((once_clip.duration() - 2.0).abs() < 1e-4);
+        assert!((ping_pong_clip.duration() - 4.0).abs() < 1e-4);
+    }
+}
@@ -1,5 +1,9 @@
 use bevy::prelude::*;
 
+use crate::spline::{ArcLengthTable, Spline};
+
+use super::systems::world_distance;
+
 /// How the follower behaves when reaching the end of the spline.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
 #[reflect(Default)]
@@ -68,6 +72,60 @@ pub struct SplineFollower {
     /// When true, the follower moves at a constant world-space speed.
     /// When false, speed varies based on control point density.
     pub constant_speed: bool,
+
+    /// Number of samples used for the arc-length approximation when `constant_speed` is true.
+    ///
+    /// When `None`, the resolution is derived from the spline's segment count
+    /// (see [`Spline::default_arc_length_samples`]).
+    #[reflect(default)]
+    pub arc_length_samples: Option<usize>,
+
+    /// Whether to move according to the spline's recorded
+    /// [`timestamps`](Spline::timestamps).
+    ///
+    /// When true and the spline has valid timestamps, `speed` is a playback rate
+    /// (1.0 = real time) and the follower replays the recorded timing exactly.
+    /// Otherwise `constant_speed` applies.
+    #[reflect(default)]
+    pub use_timestamps: bool,
+
+    /// Easing applied to each pass along the spline, for ease-in/ease-out motion.
+    ///
+    /// `t` still advances at the configured speed; the follower is placed at
+    /// [`Self::ease`] of it instead. With [`LoopMode::PingPong`] the easing is
+    /// applied to each leg in its direction of travel. Triggers fire on `t`.
+    #[reflect(default)]
+    pub speed_curve: Option<EaseFunction>,
+
+    /// How far to bank (roll) into turns when `align_to_tangent` is true.
+    ///
+    /// The roll in radians is the curvature of the turn (1 / radius) times this
+    /// factor, leaning towards the inside of the turn and limited to 90°. Only
+    /// turning about `up_vector` banks, so hills and dips don't. 0.0 disables banking.
+    #[reflect(default)]
+    pub bank_factor: f32,
+
+    /// Spline t values at which a [`FollowerEventKind::ReachedWaypoint`] event is
+    /// emitted when the follower passes them, in either direction.
+    ///
+    /// For waypoints that fire only once, or with a payload, use [`FollowerTriggers`].
+    #[reflect(default)]
+    pub waypoints: Vec<f32>,
+
+    /// Total distance covered along the spline so far, in world units.
+    ///
+    /// Measured between the positions the follower is placed at, so it includes
+    /// the speed curve and the spline's scale. Keeps growing through loops and
+    /// ping-pong reversals, like an odometer. Set by [`Self::set_world_distance`] and
+    /// [`Self::set_distance`].
+    #[reflect(default)]
+    pub distance_traveled: f32,
+
+    /// Index of the current segment when following a [`SplinePath`].
+    ///
+    /// `t` is then the position within that segment's spline.
+    #[reflect(default)]
+    pub path_segment: usize,
 }
 
 impl Default for SplineFollower {
@@ -83,6 +141,13 @@ impl Default for SplineFollower {
             direction: 1.0,
             offset: Vec3::ZERO,
             constant_speed: true,
+            arc_length_samples: None,
+            use_timestamps: false,
+            speed_curve: None,
+            bank_factor: 0.0,
+            waypoints: Vec::new(),
+            distance_traveled: 0.0,
+            path_segment: 0,
         }
     }
 }
@@ -138,6 +203,99 @@ impl SplineFollower {
         self
     }
 
+    /// Set the number of arc-length samples used for constant speed.
+    pub fn with_arc_length_samples(mut self, samples: usize) -> Self {
+        self.arc_length_samples = Some(samples);
+        self
+    }
+
+    /// Enable or disable replaying the spline's recorded timestamps.
+    pub fn with_timestamps(mut self, use_timestamps: bool) -> Self {
+        self.use_timestamps = use_timestamps;
+        self
+    }
+
+    /// Set the easing applied to each pass along the spline.
+    pub fn with_speed_curve(mut self, curve: EaseFunction) -> Self {
+        self.speed_curve = Some(curve);
+        self
+    }
+
+    /// Set how far to bank into turns.
+    pub fn with_bank_factor(mut self, bank_factor: f32) -> Self {
+        self.bank_factor = bank_factor;
+        self
+    }
+
+    /// Add a waypoint at spline parameter `t`.
+    pub fn with_waypoint(mut self, t: f32) -> Self {
+        self.waypoints.push(t);
+        self
+    }
+
+    /// Jump to the point `distance` along the spline from its start, measured in the
+    /// spline's local units like `table`.
+    ///
+    /// `table` must have been computed for the followed spline, e.g. its
+    /// [`CachedArcLength`](crate::spline::CachedArcLength). Distances are clamped to
+    /// the ends of the table, and `distance_traveled` is set to the clamped distance.
+    /// Use [`Self::set_world_distance`] for scaled or closed splines.
+    pub fn set_distance(&mut self, distance: f32, table: &ArcLengthTable) {
+        let clamped = distance.clamp(0.0, table.total_length());
+        self.t = table.length_to_t(clamped);
+        self.distance_traveled = clamped;
+    }
+
+    /// Jump to the point `distance` world units along the spline from its start.
+    ///
+    /// `table` must have been computed for the followed `spline`, e.g. its
+    /// [`CachedArcLength`](crate::spline::CachedArcLength). Unlike
+    /// [`Self::set_distance`], this needs the spline and its [`GlobalTransform`]:
+    /// `distance_traveled` is in world units, so `spline_transform` converts
+    /// `distance` to the table's local lengths, and `spline` tells whether to wrap
+    /// around a closed loop. Distances wrap around closed splines and are clamped to
+    /// the ends of open ones. Also sets `distance_traveled` to `distance`, clamped
+    /// the same way on open splines.
+    pub fn set_world_distance(
+        &mut self,
+        distance: f32,
+        spline: &Spline,
+        spline_transform: &GlobalTransform,
+        table: &ArcLengthTable,
+    ) {
+        let world_length = world_distance(spline, spline_transform, table, 0.0, 1.0);
+        if world_length <= 0.0 {
+            self.t = 0.0;
+            self.distance_traveled = if spline.closed { distance } else { 0.0 };
+            return;
+        }
+
+        let (along, traveled) = if spline.closed {
+            (distance.rem_euclid(world_length), distance)
+        } else {
+            let clamped = distance.clamp(0.0, world_length);
+            (clamped, clamped)
+        };
+        self.t = table.length_to_t(along * table.total_length() / world_length);
+        self.distance_traveled = traveled;
+    }
+
+    /// Map progress `t` through the speed curve, travelling in `direction`.
+    ///
+    /// Returns the spline parameter the follower is placed at. Without a speed
+    /// curve this is `t` itself.
+    pub fn ease(&self, t: f32, direction: f32) -> f32 {
+        let Some(curve) = self.speed_curve else {
+            return t;
+        };
+        // Ease from whichever end the current leg started at
+        if direction >= 0.0 {
+            curve.sample_clamped(t)
+        } else {
+            1.0 - curve.sample_clamped(1.0 - t)
+        }
+    }
+
     /// Start or resume playback.
     pub fn play(&mut self) {
         self.state = FollowerState::Playing;
@@ -166,6 +324,136 @@ impl SplineFollower {
     }
 }
 
+/// Whether a [`FollowerTrigger`] fires once or every time it is crossed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+pub enum TriggerMode {
+    /// Fire the first time the follower crosses the trigger.
+    #[default]
+    Once,
+    /// Fire every time the follower crosses the trigger.
+    Repeat,
+}
+
+/// A trigger that fires when a follower crosses a parametric position.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+pub struct FollowerTrigger {
+    /// The parametric position on the spline (0.0 to 1.0).
+    pub t: f32,
+    /// Whether the trigger fires once or repeatedly.
+    pub mode: TriggerMode,
+    /// Whether the trigger has fired at least once.
+    pub fired: bool,
+}
+
+impl FollowerTrigger {
+    /// Create a one-shot trigger at `t`.
+    pub fn once(t: f32) -> Self {
+        Self {
+            t,
+            mode: TriggerMode::Once,
+            fired: false,
+        }
+    }
+
+    /// Create a trigger at `t` that fires every time it is crossed.
+    pub fn repeating(t: f32) -> Self {
+        Self {
+            t,
+            mode: TriggerMode::Repeat,
+            fired: false,
+        }
+    }
+}
+
+/// Component holding position triggers for a [`SplineFollower`] on the same entity.
+///
+/// When the follower crosses a trigger's `t`, the trigger is marked as fired and a
+/// [`FollowerTriggered`] message is emitted.
+///
+/// # Example
+/// ```ignore
+/// commands.spawn((
+///     SplineFollower::new(spline),
+///     FollowerTriggers::default()
+///         .with_trigger(FollowerTrigger::once(0.5))
+///         .with_trigger(FollowerTrigger::repeating(0.9)),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct FollowerTriggers {
+    /// The triggers, checked in order.
+    pub triggers: Vec<FollowerTrigger>,
+}
+
+impl FollowerTriggers {
+    /// Add a trigger.
+    pub fn with_trigger(mut self, trigger: FollowerTrigger) -> Self {
+        self.triggers.push(trigger);
+        self
+    }
+
+    /// Re-arm all triggers so one-shot triggers can fire again.
+    pub fn reset(&mut self) {
+        for trigger in &mut self.triggers {
+            trigger.fired = false;
+        }
+    }
+}
+
+/// Message emitted when a follower crosses one of its [`FollowerTriggers`].
+#[derive(Message, Debug, Clone)]
+pub struct FollowerTriggered {
+    /// The entity with the SplineFollower component.
+    pub entity: Entity,
+    /// Index of the trigger in [`FollowerTriggers::triggers`].
+    pub index: usize,
+    /// The trigger's parametric position.
+    pub t: f32,
+}
+
+/// Component that chains several splines into one path for [`SplineFollower`]s.
+///
+/// Point a follower's `spline` at the entity with this component to traverse the
+/// segments in order. The path's `loop_mode` applies at the ends of the whole path
+/// and the follower's own `loop_mode` is ignored. For the orientation to stay
+/// continuous, each segment should start where the previous one ends, with a
+/// matching tangent direction.
+///
+/// Speed curves, waypoints, triggers and timestamps only apply to single splines.
+///
+/// # Example
+/// ```ignore
+/// let path = commands
+///     .spawn(SplinePath::new(vec![first_spline, second_spline]))
+///     .id();
+/// commands.spawn((Transform::default(), SplineFollower::new(path)));
+/// ```
+#[derive(Component, Debug, Clone, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct SplinePath {
+    /// The spline entities to traverse, in order.
+    pub segments: Vec<Entity>,
+    /// How the follower behaves when reaching either end of the path.
+    pub loop_mode: LoopMode,
+}
+
+impl SplinePath {
+    /// Create a path through the given spline entities.
+    pub fn new(segments: Vec<Entity>) -> Self {
+        Self {
+            segments,
+            ..default()
+        }
+    }
+
+    /// Set the loop mode.
+    pub fn with_loop_mode(mut self, mode: LoopMode) -> Self {
+        self.loop_mode = mode;
+        self
+    }
+}
+
 /// Message emitted when a follower reaches a significant point.
 #[derive(Message, Debug, Clone)]
 pub struct FollowerEvent {
@@ -186,4 +474,8 @@ pub enum FollowerEventKind {
     LoopCompleted,
     /// Follower finished (LoopMode::Once).
     Finished,
+    /// Follower passed the waypoint at this index of [`SplineFollower::waypoints`].
+    ReachedWaypoint(usize),
+    /// Follower moved onto the segment at this index of a [`SplinePath`].
+    EnteredSegment(usize),
 }
@@ -32,11 +32,13 @@
 //! }
 //! ```
 
+mod bake;
 mod components;
 mod systems;
 
+pub use bake::bake_follower_clip;
 pub use components::*;
-pub use systems::update_spline_followers;
+pub use systems::{cache_arc_lengths, update_path_followers, update_spline_followers};
 
 use bevy::prelude::*;
 
@@ -51,7 +53,20 @@ impl Plugin for SplineFollowPlugin {
         app.register_type::<SplineFollower>()
             .register_type::<LoopMode>()
             .register_type::<FollowerState>()
+            .register_type::<FollowerTriggers>()
+            .register_type::<FollowerTrigger>()
+            .register_type::<TriggerMode>()
+            .register_type::<SplinePath>()
             .add_message::<FollowerEvent>()
-            .add_systems(Update, systems::update_spline_followers);
+            .add_message::<FollowerTriggered>()
+            .add_systems(
+                Update,
+                (
+                    systems::cache_arc_lengths,
+                    systems::update_spline_followers,
+                    systems::update_path_followers,
+                )
+                    .chain(),
+            );
     }
 }
@@ -1,30 +1,54 @@
+use std::f32::consts::FRAC_PI_2;
+
 use bevy::prelude::*;
 
 use crate::geometry::CoordinateFrame;
-use crate::spline::{approximate_arc_length, Spline};
+use crate::spline::{ArcLengthTable, CachedArcLength, Spline};
 
-use super::{FollowerEvent, FollowerEventKind, FollowerState, LoopMode, SplineFollower};
+use super::{
+    FollowerEvent, FollowerEventKind, FollowerState, FollowerTriggered, FollowerTriggers,
+    LoopMode, SplineFollower, SplinePath, TriggerMode,
+};
 
-/// Number of samples for arc-length approximation.
-const ARC_LENGTH_SAMPLES: usize = 128;
+/// System that rebuilds the [`CachedArcLength`] of splines that changed.
+pub fn cache_arc_lengths(
+    mut commands: Commands,
+    mut splines: Query<(Entity, &Spline, Option<&mut CachedArcLength>), Changed<Spline>>,
+) {
+    for (entity, spline, cache) in &mut splines {
+        let computed = CachedArcLength::compute(spline);
+        match cache {
+            Some(mut cache) => *cache = computed,
+            None => {
+                commands.entity(entity).insert(computed);
+            }
+        }
+    }
+}
 
 /// System that updates all spline followers.
 pub fn update_spline_followers(
-    mut followers: Query<(Entity, &mut SplineFollower, &mut Transform)>,
-    splines: Query<(&Spline, &GlobalTransform)>,
+    mut followers: Query<(
+        Entity,
+        &mut SplineFollower,
+        &mut Transform,
+        Option<&mut FollowerTriggers>,
+    )>,
+    splines: Query<(&Spline, &GlobalTransform, Option<&CachedArcLength>)>,
     time: Res<Time>,
     mut events: MessageWriter<FollowerEvent>,
+    mut triggered: MessageWriter<FollowerTriggered>,
 ) {
     let delta = time.delta_secs();
 
-    for (entity, mut follower, mut transform) in &mut followers {
+    for (entity, mut follower, mut transform, triggers) in &mut followers {
         // Skip if not playing
         if follower.state != FollowerState::Playing {
             continue;
         }
 
         // Get the spline and its transform
-        let Ok((spline, spline_transform)) = splines.get(follower.spline) else {
+        let Ok((spline, spline_transform, cached_length)) = splines.get(follower.spline) else {
             continue;
         };
 
@@ -32,10 +56,26 @@ pub fn update_spline_followers(
             continue;
         }
 
+        // Recorded timing takes precedence when available
+        let timed_dt = follower
+            .use_timestamps
+            .then(|| {
+                let elapsed = follower.speed * delta * follower.direction;
+                timed_t_delta(spline, follower.t, elapsed)
+            })
+            .flatten();
+
+        // Built on first use if the spline's cached table doesn't fit the follower
+        let mut built_table = None;
+
         // Calculate t delta based on speed mode
-        let dt = if follower.constant_speed {
+        let dt = if let Some(dt) = timed_dt {
+            // `elapsed` already carries the direction, which is applied again below
+            dt * follower.direction
+        } else if follower.constant_speed {
             // Arc-length parameterization for constant speed
-            let total_length = approximate_arc_length(spline, ARC_LENGTH_SAMPLES);
+            let total_length =
+                arc_length_table(&follower, spline, cached_length, &mut built_table).total_length();
             if total_length > 0.0 {
                 (follower.speed * delta) / total_length
             } else {
@@ -49,9 +89,67 @@ pub fn update_spline_followers(
         // Update t based on direction
         let new_t = follower.t + dt * follower.direction;
 
+        // Report waypoints crossed by this step
+        for (index, &waypoint) in follower.waypoints.iter().enumerate() {
+            if crosses(follower.t, new_t, waypoint, follower.loop_mode) {
+                events.write(FollowerEvent {
+                    entity,
+                    kind: FollowerEventKind::ReachedWaypoint(index),
+                });
+            }
+        }
+
+        // Fire any triggers crossed by this step
+        if let Some(mut triggers) = triggers {
+            for (index, trigger) in triggers.triggers.iter_mut().enumerate() {
+                if trigger.mode == TriggerMode::Once && trigger.fired {
+                    continue;
+                }
+                if crosses(follower.t, new_t, trigger.t, follower.loop_mode) {
+                    trigger.fired = true;
+                    triggered.write(FollowerTriggered {
+                        entity,
+                        index,
+                        t: trigger.t,
+                    });
+                }
+            }
+        }
+
         // Handle bounds and loop modes
         let (final_t, new_direction, event) = handle_bounds(new_t, follower.direction, follower.loop_mode);
 
+        // Measure the distance between the eased positions, along each leg of the
+        // step: up to the end that was reached, then on from where it wrapped or
+        // reflected to. Once stops at the ends.
+        if dt != 0.0 {
+            let table = arc_length_table(&follower, spline, cached_length, &mut built_table);
+            let boundary = new_t.clamp(0.0, 1.0);
+            let eased = |t, direction| follower.ease(t, direction);
+            let mut distance = world_distance(
+                spline,
+                spline_transform,
+                table,
+                eased(follower.t, follower.direction),
+                eased(boundary, follower.direction),
+            );
+            if boundary != new_t && follower.loop_mode != LoopMode::Once {
+                let leg_start = if follower.loop_mode == LoopMode::Loop {
+                    1.0 - boundary
+                } else {
+                    boundary
+                };
+                distance += world_distance(
+                    spline,
+                    spline_transform,
+                    table,
+                    eased(leg_start, new_direction),
+                    eased(final_t, new_direction),
+                );
+            }
+            follower.distance_traveled += distance;
+        }
+
         follower.t = final_t;
         follower.direction = new_direction;
 
@@ -66,28 +164,225 @@ pub fn update_spline_followers(
         }
 
         // Update transform
-        if let Some(local_position) = spline.evaluate(follower.t) {
-            // Transform the local position to world space using the spline's transform
-            let world_position = spline_transform.transform_point(local_position);
-
-            let rotation = if follower.align_to_tangent {
-                let local_rotation =
-                    calculate_orientation(spline, follower.t, follower.up_vector, follower.direction);
-                // Combine spline's rotation with the tangent-based rotation
-                spline_transform.to_scale_rotation_translation().1 * local_rotation
+        let position_t = follower.ease(follower.t, follower.direction);
+        place_follower(
+            &follower,
+            spline,
+            spline_transform,
+            position_t,
+            &mut transform,
+        );
+    }
+}
+
+/// System that moves followers along [`SplinePath`]s.
+///
+/// Followers whose `spline` is a path entity travel its segments in order. Distance
+/// left over when a segment ends carries into the next one, and a
+/// [`FollowerEventKind::EnteredSegment`] event is emitted at each transition.
+pub fn update_path_followers(
+    mut followers: Query<(Entity, &mut SplineFollower, &mut Transform)>,
+    paths: Query<&SplinePath>,
+    splines: Query<(&Spline, &GlobalTransform, Option<&CachedArcLength>)>,
+    time: Res<Time>,
+    mut events: MessageWriter<FollowerEvent>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut follower, mut transform) in &mut followers {
+        if follower.state != FollowerState::Playing {
+            continue;
+        }
+        let Ok(path) = paths.get(follower.spline) else {
+            continue;
+        };
+        let segment_count = path.segments.len();
+        if segment_count == 0 {
+            continue;
+        }
+        follower.path_segment = follower.path_segment.min(segment_count - 1);
+
+        // Distance (or t units, without constant speed) still to cover this frame.
+        // Each pass either finishes the step or reaches a segment boundary.
+        let mut remaining = follower.speed * delta;
+        for _ in 0..=segment_count * 2 {
+            let segment = path.segments[follower.path_segment];
+            let Ok((spline, spline_transform, cached_length)) = splines.get(segment) else {
+                break;
+            };
+            let mut built_table = None;
+            let table = arc_length_table(&follower, spline, cached_length, &mut built_table);
+            let length = table.total_length();
+            let units_per_t = if follower.constant_speed { length } else { 1.0 };
+
+            let new_t = if units_per_t > 0.0 {
+                follower.t + remaining / units_per_t * follower.direction
             } else {
-                transform.rotation
+                // Zero-length segments are crossed immediately
+                follower.t + follower.direction
             };
+            if (0.0..=1.0).contains(&new_t) {
+                follower.distance_traveled +=
+                    world_distance(spline, spline_transform, table, follower.t, new_t);
+                follower.t = new_t;
+                break;
+            }
+
+            // Reached the end of this segment in the direction of travel
+            let boundary = new_t.clamp(0.0, 1.0);
+            let covered = (boundary - follower.t).abs();
+            follower.distance_traveled +=
+                world_distance(spline, spline_transform, table, follower.t, boundary);
+            remaining = (remaining - covered * units_per_t).max(0.0);
+
+            let forward = follower.direction >= 0.0;
+            let next = if forward {
+                follower.path_segment + 1
+            } else {
+                follower.path_segment.wrapping_sub(1)
+            };
+            if next < segment_count {
+                follower.path_segment = next;
+                follower.t = 1.0 - boundary;
+                events.write(FollowerEvent {
+                    entity,
+                    kind: FollowerEventKind::EnteredSegment(next),
+                });
+                continue;
+            }
 
-            // Apply offset in local space (relative to follower's rotation)
-            let world_offset = rotation * follower.offset;
+            // Reached the end of the whole path
+            match path.loop_mode {
+                LoopMode::Once => {
+                    follower.t = boundary;
+                    follower.state = FollowerState::Finished;
+                    events.write(FollowerEvent {
+                        entity,
+                        kind: FollowerEventKind::Finished,
+                    });
+                    break;
+                }
+                LoopMode::Loop => {
+                    let first = if forward { 0 } else { segment_count - 1 };
+                    follower.path_segment = first;
+                    follower.t = 1.0 - boundary;
+                    events.write(FollowerEvent {
+                        entity,
+                        kind: FollowerEventKind::LoopCompleted,
+                    });
+                }
+                LoopMode::PingPong => {
+                    follower.t = boundary;
+                    follower.direction = -follower.direction;
+                    let kind = if forward {
+                        FollowerEventKind::ReachedEnd
+                    } else {
+                        FollowerEventKind::ReachedStart
+                    };
+                    events.write(FollowerEvent { entity, kind });
+                }
+            }
+        }
 
-            transform.translation = world_position + world_offset;
-            transform.rotation = rotation;
+        let segment = path.segments[follower.path_segment];
+        if let Ok((spline, spline_transform, _)) = splines.get(segment) {
+            place_follower(
+                &follower,
+                spline,
+                spline_transform,
+                follower.t,
+                &mut transform,
+            );
         }
     }
 }
 
+/// Move a follower's transform to `position_t` on `spline`.
+fn place_follower(
+    follower: &SplineFollower,
+    spline: &Spline,
+    spline_transform: &GlobalTransform,
+    position_t: f32,
+    transform: &mut Transform,
+) {
+    let Some((local_position, tangent)) = spline.evaluate_pos_tan(position_t) else {
+        return;
+    };
+    // Transform the local position to world space using the spline's transform
+    let world_position = spline_transform.transform_point(local_position);
+
+    let rotation = if follower.align_to_tangent {
+        let roll = bank_angle(spline, position_t, follower.up_vector, follower.bank_factor);
+        let local_rotation =
+            calculate_orientation(tangent, follower.up_vector, follower.direction, roll);
+        // Combine spline's rotation with the tangent-based rotation
+        spline_transform.to_scale_rotation_translation().1 * local_rotation
+    } else {
+        transform.rotation
+    };
+
+    // Apply offset in local space (relative to follower's rotation)
+    let world_offset = rotation * follower.offset;
+
+    transform.translation = world_position + world_offset;
+    transform.rotation = rotation;
+}
+
+/// Get the arc length table to move `follower` along `spline` with.
+///
+/// This is the spline's cached table, unless the follower asks for another
+/// resolution; then a table is built into `built` the first time it's needed.
+fn arc_length_table<'a>(
+    follower: &SplineFollower,
+    spline: &Spline,
+    cached: Option<&'a CachedArcLength>,
+    built: &'a mut Option<ArcLengthTable>,
+) -> &'a ArcLengthTable {
+    let samples = follower
+        .arc_length_samples
+        .unwrap_or_else(|| spline.default_arc_length_samples());
+    match cached {
+        Some(cache) if cache.samples == samples => &cache.table,
+        _ => built.get_or_insert_with(|| ArcLengthTable::compute(spline, samples)),
+    }
+}
+
+/// Get the world space distance along `spline` between parameters `t0` and `t1`.
+///
+/// The table's local length is scaled by how much `spline_transform` stretches the
+/// curve halfway between the two, which is exact for uniform scales.
+pub(crate) fn world_distance(
+    spline: &Spline,
+    spline_transform: &GlobalTransform,
+    table: &ArcLengthTable,
+    t0: f32,
+    t1: f32,
+) -> f32 {
+    let local = table.length_between(t0, t1).abs();
+    if local == 0.0 {
+        return 0.0;
+    }
+    let stretch = spline
+        .evaluate_tangent((t0 + t1) / 2.0)
+        .filter(|tangent| tangent.length_squared() > 0.0)
+        .map_or(1.0, |tangent| {
+            spline_transform.affine().transform_vector3(tangent).length() / tangent.length()
+        });
+    local * stretch
+}
+
+/// Get the t delta for advancing `elapsed` seconds of recorded time from `t`.
+///
+/// Overshoot past either end of the recording is converted at the average rate, so
+/// it can be wrapped or reflected by [`handle_bounds`] like any other step.
+fn timed_t_delta(spline: &Spline, t: f32, elapsed: f32) -> Option<f32> {
+    let (start, end) = spline.time_range()?;
+    let time = spline.time_at(t)? + elapsed;
+    let clamped = time.clamp(start, end);
+    let overshoot = (time - clamped) / (end - start);
+    Some(spline.t_at_time(clamped)? + overshoot - t)
+}
+
 /// Handle t value bounds based on loop mode.
 /// Returns (new_t, new_direction, optional_event).
 fn handle_bounds(
@@ -130,17 +425,501 @@ fn handle_bounds(
     }
 }
 
-/// Calculate orientation from spline tangent.
-fn calculate_orientation(spline: &Spline, t: f32, up: Vec3, direction: f32) -> Quat {
-    let Some(tangent) = spline.evaluate_tangent(t) else {
-        return Quat::IDENTITY;
+/// Check whether a step from `from` to the unbounded `to` passes over `trigger`.
+///
+/// `to` may lie outside 0.0 - 1.0; the overshoot is wrapped or reflected
+/// according to the loop mode, matching [`handle_bounds`].
+fn crosses(from: f32, to: f32, trigger: f32, loop_mode: LoopMode) -> bool {
+    // Half-open interval in the direction of travel: the start is excluded so a
+    // trigger isn't fired twice by consecutive steps
+    let within = |from: f32, to: f32| {
+        if to >= from {
+            trigger > from && trigger <= to
+        } else {
+            trigger < from && trigger >= to
+        }
     };
 
+    match loop_mode {
+        _ if (0.0..=1.0).contains(&to) => within(from, to),
+        LoopMode::Once => within(from, to.clamp(0.0, 1.0)),
+        LoopMode::Loop if to > 1.0 => within(from, 1.0) || within(0.0, to - 1.0),
+        LoopMode::Loop => within(from, 0.0) || within(1.0, 1.0 + to),
+        LoopMode::PingPong if to > 1.0 => within(from, 1.0) || within(1.0, 2.0 - to),
+        LoopMode::PingPong => within(from, 0.0) || within(0.0, -to),
+    }
+}
+
+/// Calculate orientation from spline tangent, rolled by `roll` radians about the
+/// direction of travel.
+///
+/// `roll` is the bank angle for travel along the spline (see [`bank_angle`]); it is
+/// mirrored when moving backwards, where the turns go the other way.
+pub(super) fn calculate_orientation(tangent: Vec3, up: Vec3, direction: f32, roll: f32) -> Quat {
     let frame = CoordinateFrame::from_tangent_with_up(tangent, up);
     if !frame.is_valid() {
         return Quat::IDENTITY;
     }
 
-    frame.to_rotation_with_direction(direction)
+    let roll = if direction >= 0.0 { roll } else { -roll };
+    frame.to_rotation_with_direction(direction) * Quat::from_rotation_z(roll)
 }
 
+/// Get the angle to bank into the turn at `t`, for travel along the spline.
+///
+/// Positive angles lean left. Only the part of the curvature that turns about `up`
+/// counts, so straight sections and pure climbs give zero.
+pub(super) fn bank_angle(spline: &Spline, t: f32, up: Vec3, bank_factor: f32) -> f32 {
+    if bank_factor == 0.0 {
+        return 0.0;
+    }
+    let (Some(curvature), Some(velocity), Some(acceleration)) = (
+        spline.curvature_at(t),
+        spline.evaluate_tangent(t),
+        spline.evaluate_second_derivative(t),
+    ) else {
+        return 0.0;
+    };
+
+    // Cosine between the turn axis and up; positive for turns to the left
+    let axis = velocity.cross(acceleration).normalize_or_zero();
+    let turn = axis.dot(up.normalize_or_zero());
+    (curvature * turn * bank_factor).clamp(-FRAC_PI_2, FRAC_PI_2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigger_crossing_with_loop_modes() {
+        // Plain forward and backward steps
+        assert!(crosses(0.4, 0.6, 0.5, LoopMode::Once));
+        assert!(crosses(0.6, 0.4, 0.5, LoopMode::Once));
+        assert!(!crosses(0.5, 0.6, 0.5, LoopMode::Once));
+        assert!(!crosses(0.1, 0.2, 0.5, LoopMode::Once));
+
+        // Wrapping past the end continues from the start
+        assert!(crosses(0.95, 1.1, 0.05, LoopMode::Loop));
+        assert!(crosses(0.95, 1.1, 0.98, LoopMode::Loop));
+        assert!(!crosses(0.95, 1.1, 0.5, LoopMode::Loop));
+
+        // Bouncing off the end travels back over the reflected range
+        assert!(crosses(0.95, 1.1, 0.92, LoopMode::PingPong));
+        assert!(!crosses(0.95, 1.1, 0.85, LoopMode::PingPong));
+        assert!(crosses(0.05, -0.1, 0.08, LoopMode::PingPong));
+    }
+
+    #[test]
+    fn test_arc_length_cached_once_per_spline_change() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_message::<FollowerEvent>()
+            .add_message::<FollowerTriggered>()
+            .add_systems(Update, (cache_arc_lengths, update_spline_followers).chain());
+
+        let points = vec![
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::new(2.0, 1.0, 0.0),
+            Vec3::new(3.0, 0.0, 0.0),
+        ];
+        let spline = app
+            .world_mut()
+            .spawn((
+                Spline::new(crate::spline::SplineType::CatmullRom, points.clone()),
+                GlobalTransform::default(),
+            ))
+            .id();
+        for _ in 0..100 {
+            app.world_mut()
+                .spawn((Transform::default(), SplineFollower::new(spline)));
+        }
+
+        app.update();
+        let samples = |app: &App| app.world().get::<CachedArcLength>(spline).unwrap().samples;
+        let default_samples = app
+            .world()
+            .get::<Spline>(spline)
+            .unwrap()
+            .default_arc_length_samples();
+        assert_eq!(samples(&app), default_samples);
+
+        // A table built after the last spline change is left alone, not rebuilt
+        let sentinel = CachedArcLength {
+            table: ArcLengthTable::compute(
+                &Spline::new(crate::spline::SplineType::CatmullRom, points),
+                4,
+            ),
+            samples: 4,
+        };
+        app.world_mut().entity_mut(spline).insert(sentinel);
+        app.update();
+        app.update();
+        assert_eq!(samples(&app), 4);
+
+        // Changing the spline rebuilds it
+        app.world_mut()
+            .get_mut::<Spline>(spline)
+            .unwrap()
+            .add_point(Vec3::new(4.0, 0.0, 0.0));
+        app.update();
+        let spline = app.world().get::<Spline>(spline).unwrap();
+        assert_eq!(samples(&app), spline.default_arc_length_samples());
+    }
+
+    #[test]
+    fn test_speed_curve_eases_each_leg() {
+        let step = |follower: &SplineFollower, t: f32, direction: f32| {
+            (follower.ease(t + 0.01, direction) - follower.ease(t, direction)).abs()
+        };
+
+        let ease_in_out = SplineFollower::default().with_speed_curve(EaseFunction::CubicInOut);
+        for direction in [1.0, -1.0] {
+            assert!(step(&ease_in_out, 0.0, direction) < step(&ease_in_out, 0.5, direction));
+            assert!(step(&ease_in_out, 0.99, direction) < step(&ease_in_out, 0.5, direction));
+        }
+        assert_eq!(ease_in_out.ease(0.0, 1.0), 0.0);
+        assert_eq!(ease_in_out.ease(1.0, 1.0), 1.0);
+
+        // An ease-in starts slowly on both legs of a ping-pong
+        let ease_in = SplineFollower::default().with_speed_curve(EaseFunction::QuadraticIn);
+        assert!(step(&ease_in, 0.0, 1.0) < step(&ease_in, 0.99, 1.0));
+        assert!(step(&ease_in, 0.99, -1.0) < step(&ease_in, 0.0, -1.0));
+
+        // No curve leaves t unchanged
+        assert_eq!(SplineFollower::default().ease(0.3, -1.0), 0.3);
+    }
+
+    #[test]
+    fn test_bank_angle_leans_into_turns() {
+        // Quarter circle of radius 2 in the XZ plane, turning left
+        let radius = 2.0;
+        let handle = radius * 0.552_284_8;
+        let arc = |side: f32| {
+            Spline::new(
+                crate::spline::SplineType::CubicBezier,
+                vec![
+                    Vec3::new(0.0, 0.0, 0.0),
+                    Vec3::new(handle, 0.0, 0.0),
+                    Vec3::new(radius, 0.0, side * (radius - handle)),
+                    Vec3::new(radius, 0.0, side * radius),
+                ],
+            )
+        };
+
+        let left = bank_angle(&arc(-1.0), 0.5, Vec3::Y, 1.0);
+        assert!((left - 1.0 / radius).abs() < 0.03);
+        let right = bank_angle(&arc(1.0), 0.5, Vec3::Y, 1.0);
+        assert!((right + left).abs() < 1e-4);
+        assert_eq!(bank_angle(&arc(-1.0), 0.5, Vec3::Y, 0.0), 0.0);
+
+        // A positive roll tilts the follower's up towards its left
+        let rotation = calculate_orientation(Vec3::X, Vec3::Y, 1.0, left);
+        assert!((rotation * Vec3::Y).z < 0.0);
+
+        // No banking on straight sections
+        let line = Spline::new(
+            crate::spline::SplineType::CatmullRom,
+            (0..4).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+        );
+        assert!(bank_angle(&line, 0.5, Vec3::Y, 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_waypoints_fire_when_stepped_over() {
+        #[derive(Resource, Default)]
+        struct Received(Vec<FollowerEventKind>);
+
+        fn record(mut events: MessageReader<FollowerEvent>, mut received: ResMut<Received>) {
+            received.0.extend(events.read().map(|event| event.kind));
+        }
+
+        // Advance a follower by one large step and collect its events
+        let step = |follower: SplineFollower, t_step: f32| {
+            let mut app = App::new();
+            app.init_resource::<Time>()
+                .init_resource::<Received>()
+                .add_message::<FollowerEvent>()
+                .add_message::<FollowerTriggered>()
+                .add_systems(Update, (update_spline_followers, record).chain());
+            let spline = app
+                .world_mut()
+                .spawn((
+                    Spline::new(
+                        crate::spline::SplineType::CatmullRom,
+                        (0..4).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+                    ),
+                    GlobalTransform::default(),
+                ))
+                .id();
+            app.world_mut().spawn((
+                Transform::default(),
+                SplineFollower {
+                    spline,
+                    constant_speed: false,
+                    ..follower
+                },
+            ));
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(std::time::Duration::from_secs_f32(t_step));
+            app.update();
+            app.world_mut().remove_resource::<Received>().unwrap().0
+        };
+        let follower = SplineFollower::default()
+            .with_waypoint(0.2)
+            .with_waypoint(0.95);
+
+        // Jumping from 0.0 to 0.6 passes the first waypoint only
+        assert_eq!(
+            step(follower.clone(), 0.6),
+            vec![FollowerEventKind::ReachedWaypoint(0)]
+        );
+
+        // Wrapping from 0.9 to 0.3 passes both
+        let looping = follower
+            .clone()
+            .with_loop_mode(LoopMode::Loop)
+            .with_start_t(0.9);
+        let events = step(looping, 0.4);
+        assert!(events.contains(&FollowerEventKind::ReachedWaypoint(0)));
+        assert!(events.contains(&FollowerEventKind::ReachedWaypoint(1)));
+
+        // Bouncing off the end passes 0.95 twice but reports it once
+        let bouncing = follower
+            .with_loop_mode(LoopMode::PingPong)
+            .with_start_t(0.9);
+        let events = step(bouncing, 0.3);
+        let waypoint_events = events
+            .iter()
+            .filter(|&&kind| kind == FollowerEventKind::ReachedWaypoint(1))
+            .count();
+        assert_eq!(waypoint_events, 1);
+        assert!(events.contains(&FollowerEventKind::ReachedEnd));
+    }
+
+    #[test]
+    fn test_distance_traveled_accumulates_through_reversals() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_message::<FollowerEvent>()
+            .add_message::<FollowerTriggered>()
+            .add_systems(Update, update_spline_followers);
+
+        // Straight line from x = 1 to x = 4
+        let spline = Spline::new(
+            crate::spline::SplineType::CatmullRom,
+            (0..6).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+        );
+        let table = crate::spline::ArcLengthTable::compute(&spline, 256);
+        let spline_data = spline.clone();
+        let spline = app
+            .world_mut()
+            .spawn((spline, GlobalTransform::default()))
+            .id();
+        let follower = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                SplineFollower::new(spline)
+                    .with_speed(2.0)
+                    .with_loop_mode(LoopMode::PingPong),
+            ))
+            .id();
+
+        // Two seconds at 2 units per second goes to the end and 1 unit back
+        for _ in 0..4 {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(std::time::Duration::from_secs_f32(0.5));
+            app.update();
+        }
+        let state = app.world().get::<SplineFollower>(follower).unwrap();
+        assert!((state.distance_traveled - 4.0).abs() < 0.01);
+        assert!((state.t - 2.0 / 3.0).abs() < 0.01);
+
+        let mut state = state.clone();
+        state.set_world_distance(1.5, &spline_data, &GlobalTransform::default(), &table);
+        assert!((state.t - 0.5).abs() < 1e-3);
+        assert_eq!(state.distance_traveled, 1.5);
+    }
+
+    #[test]
+    fn test_set_world_distance_uses_world_units() {
+        // Straight line from x = 1 to x = 4, scaled up to 6 units long
+        let spline = Spline::new(
+            crate::spline::SplineType::CatmullRom,
+            (0..6).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+        );
+        let table = crate::spline::ArcLengthTable::compute(&spline, 256);
+        let transform = GlobalTransform::from_scale(Vec3::splat(2.0));
+        let mut follower = SplineFollower::default();
+
+        follower.set_world_distance(3.0, &spline, &transform, &table);
+        assert!((follower.t - 0.5).abs() < 1e-3);
+        assert_eq!(follower.distance_traveled, 3.0);
+
+        // Open splines clamp to their ends
+        follower.set_world_distance(10.0, &spline, &transform, &table);
+        assert!((follower.t - 1.0).abs() < 1e-3);
+        assert!((follower.distance_traveled - 6.0).abs() < 1e-3);
+
+        // Closed splines wrap, keeping the odometer reading
+        let mut spline = Spline::new(
+            crate::spline::SplineType::CatmullRom,
+            vec![Vec3::ZERO, Vec3::X, Vec3::new(1.0, 0.0, 1.0), Vec3::Z],
+        );
+        spline.closed = true;
+        let table = crate::spline::ArcLengthTable::compute(&spline, 256);
+        let length = table.total_length() * 2.0;
+        follower.set_world_distance(length * 1.25, &spline, &transform, &table);
+        assert!((follower.t - table.length_to_t(table.total_length() * 0.25)).abs() < 1e-3);
+        assert!((follower.distance_traveled - length * 1.25).abs() < 1e-3);
+        follower.set_world_distance(-length * 0.25, &spline, &transform, &table);
+        assert!((follower.t - table.length_to_t(table.total_length() * 0.75)).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_set_distance_uses_table_units() {
+        // Straight line from x = 1 to x = 4
+        let spline = Spline::new(
+            crate::spline::SplineType::CatmullRom,
+            (0..6).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+        );
+        let table = crate::spline::ArcLengthTable::compute(&spline, 256);
+        let mut follower = SplineFollower::default();
+
+        follower.set_distance(1.5, &table);
+        assert!((follower.t - 0.5).abs() < 1e-3);
+        assert_eq!(follower.distance_traveled, 1.5);
+
+        follower.set_distance(10.0, &table);
+        assert!((follower.t - 1.0).abs() < 1e-3);
+        assert!((follower.distance_traveled - 3.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_distance_traveled_follows_eased_world_positions() {
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .add_message::<FollowerEvent>()
+            .add_message::<FollowerTriggered>()
+            .add_systems(Update, update_spline_followers);
+
+        // A curve with uneven control point spacing, scaled up twice
+        let spline = Spline::new(
+            crate::spline::SplineType::CatmullRom,
+            vec![
+                Vec3::ZERO,
+                Vec3::X,
+                Vec3::new(1.5, 0.0, 0.5),
+                Vec3::new(4.0, 0.0, 2.0),
+                Vec3::new(6.0, 0.0, 2.0),
+            ],
+        );
+        let table = crate::spline::ArcLengthTable::compute(&spline, 1024);
+        let spline = app
+            .world_mut()
+            .spawn((spline, GlobalTransform::from_scale(Vec3::splat(2.0))))
+            .id();
+        let follower = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                SplineFollower::new(spline)
+                    .with_speed(0.25)
+                    .with_constant_speed(false)
+                    .with_speed_curve(EaseFunction::QuadraticIn),
+            ))
+            .id();
+
+        for _ in 0..3 {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(std::time::Duration::from_secs_f32(0.5));
+            app.update();
+        }
+        let state = app.world().get::<SplineFollower>(follower).unwrap();
+        let expected = 2.0 * table.t_to_length(state.ease(state.t, 1.0));
+        assert!((state.t - 0.375).abs() < 1e-4);
+        assert!(
+            (state.distance_traveled - expected).abs() < 0.01,
+            "{} != {expected}",
+            state.distance_traveled
+        );
+    }
+
+    #[test]
+    fn test_path_traversal_time_is_sum_of_segments() {
+        #[derive(Resource, Default)]
+        struct Received(Vec<FollowerEventKind>);
+
+        fn record(mut events: MessageReader<FollowerEvent>, mut received: ResMut<Received>) {
+            received.0.extend(events.read().map(|event| event.kind));
+        }
+
+        let mut app = App::new();
+        app.init_resource::<Time>()
+            .init_resource::<Received>()
+            .add_message::<FollowerEvent>()
+            .add_message::<FollowerTriggered>()
+            .add_systems(Update, (update_path_followers, record).chain());
+
+        // Straight segments of length 3 and 6, the second continuing the first
+        let line = |start: f32, end: f32| {
+            let step = (end - start) / 3.0;
+            Spline::new(
+                crate::spline::SplineType::CatmullRom,
+                (-1..=4)
+                    .map(|i| Vec3::new(start + step * i as f32, 0.0, 0.0))
+                    .collect(),
+            )
+        };
+        let first = app
+            .world_mut()
+            .spawn((line(0.0, 3.0), GlobalTransform::default()))
+            .id();
+        let second = app
+            .world_mut()
+            .spawn((line(3.0, 9.0), GlobalTransform::default()))
+            .id();
+        let path = app
+            .world_mut()
+            .spawn(SplinePath::new(vec![first, second]))
+            .id();
+        let follower = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                SplineFollower::new(path).with_speed(3.0),
+            ))
+            .id();
+
+        // 9 units at 3 units per second takes 3 seconds, or 60 frames
+        let mut frames = 0;
+        let playing = |app: &App| {
+            app.world().get::<SplineFollower>(follower).unwrap().state == FollowerState::Playing
+        };
+        while playing(&app) {
+            app.world_mut()
+                .resource_mut::<Time>()
+                .advance_by(std::time::Duration::from_secs_f32(0.05));
+            app.update();
+            frames += 1;
+            assert!(frames <= 100);
+        }
+        assert!((59..=61).contains(&frames));
+
+        let received = &app.world().resource::<Received>().0;
+        assert_eq!(
+            received[..],
+            [
+                FollowerEventKind::EnteredSegment(1),
+                FollowerEventKind::Finished
+            ]
+        );
+        let transform = app.world().get::<Transform>(follower).unwrap();
+        assert!(transform.translation.distance(Vec3::new(9.0, 0.0, 0.0)) < 1e-3);
+    }
+}
@@ -0,0 +1,222 @@
+//! Baking road networks into a single static mesh.
+//!
+//! [`bake_roads`] merges generated road meshes into one [`Mesh`] in a shared
+//! space, so a finished network can ship without any spline components.
+
+use bevy::{
+    mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
+    prelude::*,
+};
+
+/// Merge road meshes into a single mesh.
+///
+/// Each mesh is transformed by its paired [`GlobalTransform`] before merging, so
+/// the result is in world space. Indices are offset so each input keeps its own
+/// triangles. Tangents are kept only when every input has them.
+///
+/// Returns `None` if `roads` is empty or any mesh isn't an indexed triangle list
+/// with positions, normals and UVs.
+///
+/// # Example
+///
+/// ```ignore
+/// fn bake(
+///     roads: Query<(&Mesh3d, &GlobalTransform), With<GeneratedRoadMesh>>,
+///     meshes: Res<Assets<Mesh>>,
+/// ) -> Option<Mesh> {
+///     bake_roads(
+///         roads
+///             .iter()
+///             .filter_map(|(mesh, transform)| Some((meshes.get(mesh)?, *transform))),
+///     )
+/// }
+/// ```
+pub fn bake_roads<'a>(
+    roads: impl IntoIterator<Item = (&'a Mesh, GlobalTransform)>,
+) -> Option<Mesh> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut tangents: Option<Vec<[f32; 4]>> = Some(Vec::new());
+    let mut indices: Vec<u32> = Vec::new();
+    let mut empty = true;
+
+    for (mesh, transform) in roads {
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let (
+            Some(VertexAttributeValues::Float32x3(mesh_positions)),
+            Some(VertexAttributeValues::Float32x3(mesh_normals)),
+            Some(VertexAttributeValues::Float32x2(mesh_uvs)),
+            Some(mesh_indices),
+        ) = (
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL),
+            mesh.attribute(Mesh::ATTRIBUTE_UV_0),
+            mesh.indices(),
+        )
+        else {
+            return None;
+        };
+        empty = false;
+
+        let affine = transform.affine();
+        // Normals use the inverse transpose so non-uniform scale keeps them perpendicular
+        let normal_matrix = affine.matrix3.inverse().transpose();
+        // Mirroring transforms flip the bitangent
+        let handedness = affine.matrix3.determinant().signum();
+
+        let offset = positions.len() as u32;
+        positions.extend(
+            mesh_positions
+                .iter()
+                .map(|p| affine.transform_point3(Vec3::from_array(*p)).to_array()),
+        );
+        normals.extend(mesh_normals.iter().map(|n| {
+            (normal_matrix * Vec3A::from_array(*n))
+                .normalize_or_zero()
+                .to_array()
+        }));
+        uvs.extend_from_slice(mesh_uvs);
+        let first_index = indices.len();
+        indices.extend(mesh_indices.iter().map(|i| offset + i as u32));
+        if handedness < 0.0 {
+            // Mirroring also turns the triangles inside out, so restore their winding
+            for triangle in indices[first_index..].as_chunks_mut::<3>().0 {
+                triangle.swap(1, 2);
+            }
+        }
+
+        tangents = match (tangents, mesh.attribute(Mesh::ATTRIBUTE_TANGENT)) {
+            (Some(mut merged), Some(VertexAttributeValues::Float32x4(mesh_tangents))) => {
+                merged.extend(mesh_tangents.iter().map(|&[x, y, z, w]| {
+                    let tangent = affine.transform_vector3(Vec3::new(x, y, z));
+                    tangent
+                        .normalize_or_zero()
+                        .extend(w * handedness)
+                        .to_array()
+                }));
+                Some(merged)
+            }
+            _ => None,
+        };
+    }
+
+    if empty {
+        return None;
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    if let Some(tangents) = tangents {
+        mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
+    }
+    mesh.insert_indices(Indices::U32(indices));
+    Some(mesh)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::road::{create_road_segment_mesh, generate_road_mesh};
+    use crate::spline::{Spline, SplineType};
+
+    #[test]
+    fn test_bake_roads_merges_meshes() {
+        let segment = create_road_segment_mesh(4.0, 1.0, 0.2, 0.5);
+        let straight = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 4.0, 8.0, 12.0]
+                .map(|x| Vec3::new(x, 0.0, 0.0))
+                .to_vec(),
+        );
+        let curved = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(3.0, 0.0, 3.0),
+                Vec3::new(6.0, 0.0, 0.0),
+                Vec3::new(9.0, 0.0, 3.0),
+                Vec3::new(12.0, 0.0, 0.0),
+            ],
+        );
+        let a = generate_road_mesh(&straight, &segment, 8, 1.0).unwrap();
+        let b = generate_road_mesh(&curved, &segment, 20, 1.0).unwrap();
+        let offset = Vec3::new(0.0, 0.0, 20.0);
+
+        let baked = bake_roads([
+            (&a, GlobalTransform::IDENTITY),
+            (&b, GlobalTransform::from_translation(offset)),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            baked.count_vertices(),
+            a.count_vertices() + b.count_vertices()
+        );
+        let indices: Vec<usize> = baked.indices().unwrap().iter().collect();
+        assert_eq!(
+            indices.len(),
+            a.indices().unwrap().len() + b.indices().unwrap().len()
+        );
+        assert!(indices.iter().all(|&i| i < baked.count_vertices()));
+        assert!(baked.attribute(Mesh::ATTRIBUTE_TANGENT).is_some());
+
+        // The second road's triangles point at its own, translated vertices
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            baked.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("baked mesh should have positions");
+        };
+        let first_b = b.indices().unwrap().iter().next().unwrap();
+        let Some(VertexAttributeValues::Float32x3(b_positions)) =
+            b.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("road mesh should have positions");
+        };
+        let merged = Vec3::from_array(positions[indices[a.indices().unwrap().len()]]);
+        let original = Vec3::from_array(b_positions[first_b]) + offset;
+        assert!(merged.distance(original) < 1e-5);
+
+        assert!(bake_roads(std::iter::empty::<(&Mesh, GlobalTransform)>()).is_none());
+    }
+
+    #[test]
+    fn test_bake_roads_keeps_winding_when_mirrored() {
+        let segment = create_road_segment_mesh(4.0, 1.0, 0.2, 0.5);
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 4.0, 8.0, 12.0]
+                .map(|x| Vec3::new(x, 0.0, x * 0.25))
+                .to_vec(),
+        );
+        let road = generate_road_mesh(&spline, &segment, 8, 1.0).unwrap();
+        let mirror = GlobalTransform::from_scale(Vec3::new(-1.0, 1.0, 1.0));
+        let baked = bake_roads([(&road, mirror)]).unwrap();
+
+        let (
+            Some(VertexAttributeValues::Float32x3(positions)),
+            Some(VertexAttributeValues::Float32x3(normals)),
+        ) = (
+            baked.attribute(Mesh::ATTRIBUTE_POSITION),
+            baked.attribute(Mesh::ATTRIBUTE_NORMAL),
+        )
+        else {
+            panic!("baked mesh should have positions and normals");
+        };
+        // Every triangle still faces the way its vertex normals point
+        let indices: Vec<usize> = baked.indices().unwrap().iter().collect();
+        for triangle in indices.as_chunks::<3>().0 {
+            let [a, b, c] = [0, 1, 2].map(|k| Vec3::from_array(positions[triangle[k]]));
+            let face = (b - a).cross(c - a);
+            if face.length_squared() < 1e-10 {
+                continue;
+            }
+            let normal = Vec3::from_array(normals[triangle[0]]);
+            assert!(face.dot(normal) > 0.0, "triangle {triangle:?} is inside out");
+        }
+    }
+}
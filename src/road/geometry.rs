@@ -0,0 +1,592 @@
+//! Raw road extrusion geometry.
+//!
+//! [`road_geometry`] extrudes a cross-section profile along a spline and returns
+//! the vertex data and per-sample frames, without building a [`Mesh`]. Use it to
+//! post-process road geometry (custom attributes, merging, submeshes) before
+//! creating a mesh yourself, or call [`RoadGeometry::into_mesh`].
+
+use bevy::{
+    mesh::{Indices, PrimitiveTopology},
+    prelude::*,
+};
+
+use crate::geometry::CoordinateFrame;
+use crate::spline::Spline;
+
+use super::{ProfileVertex, RoadEnd};
+
+/// How the V texture coordinate advances along a road.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum RoadUvMode {
+    /// V is proportional to the spline parameter t. Textures stretch where the
+    /// spline's control points are far apart and compress where they bunch up.
+    #[default]
+    Parametric,
+    /// V is proportional to the distance along the road, so textures keep the
+    /// same scale everywhere.
+    ArcLength,
+}
+
+/// Options controlling road extrusion.
+#[derive(Debug, Clone)]
+pub struct RoadGeometryOptions {
+    /// UV V-coordinate at the end of the road (V runs from 0 at the start).
+    pub uv_tile_length: f32,
+    /// How V is distributed between the start and end of the road.
+    pub uv_mode: RoadUvMode,
+    /// Whether to use rotation-minimizing frames instead of world-up frames.
+    ///
+    /// World-up frames ([`CoordinateFrame::from_tangent`]) keep the road level but
+    /// flip the cross-section where the spline passes through vertical. Rotation-
+    /// minimizing frames start level and are carried along the spline without
+    /// twisting, so they stay continuous through loops and steep climbs.
+    pub use_rmf: bool,
+    /// Whether to close the start and end of open roads with flat caps.
+    ///
+    /// The profile outline is closed with a straight edge from its last vertex
+    /// back to its first, and the enclosed area is triangulated. Profiles that
+    /// enclose no area, such as flat strips, get no caps. Closed splines never get
+    /// caps; their last row is stitched onto the first.
+    pub cap_ends: bool,
+    /// Ends of the road joined to a [`RoadIntersection`](super::RoadIntersection),
+    /// which stay open even with `cap_ends`, since the intersection covers them.
+    pub intersection_ends: Vec<RoadEnd>,
+    /// Whether to smooth normals across creases in the profile.
+    ///
+    /// When disabled, profile vertices marked as [`ProfileVertex::crease`] are
+    /// duplicated so each side of the crease keeps its own normal, giving crisp
+    /// curb edges.
+    pub smooth_normals: bool,
+}
+
+impl Default for RoadGeometryOptions {
+    fn default() -> Self {
+        Self {
+            uv_tile_length: 1.0,
+            uv_mode: RoadUvMode::Parametric,
+            use_rmf: false,
+            cap_ends: false,
+            intersection_ends: Vec::new(),
+            smooth_normals: true,
+        }
+    }
+}
+
+/// A sample along the spline where a row of profile vertices was placed.
+#[derive(Debug, Clone, Copy)]
+pub struct RoadSample {
+    /// Spline parameter of this sample.
+    pub t: f32,
+    /// Position on the spline in the spline's local space.
+    pub position: Vec3,
+    /// Coordinate frame used to place the profile.
+    pub frame: CoordinateFrame,
+}
+
+/// Vertex data of an extruded road.
+///
+/// Vertices are laid out row by row: row `i` holds the profile vertices placed at
+/// `samples[i]`, so vertex `i * profile_len + j` is profile vertex `j` of sample `i`.
+/// End caps, when enabled, follow the last row: for each cap a copy of its end row,
+/// ear-clipped into triangles.
+#[derive(Debug, Clone, Default)]
+pub struct RoadGeometry {
+    /// Vertex positions in the spline's local space.
+    pub positions: Vec<[f32; 3]>,
+    /// Per-vertex normals (the up vector of each sample's frame).
+    pub normals: Vec<[f32; 3]>,
+    /// Per-vertex UVs: U from the profile, V along the road.
+    pub uvs: Vec<[f32; 2]>,
+    /// Triangle list indices.
+    pub indices: Vec<u32>,
+    /// Spline samples, one per row of vertices.
+    pub samples: Vec<RoadSample>,
+    /// Number of vertices per row.
+    pub profile_len: usize,
+}
+
+impl RoadGeometry {
+    /// Set the V coordinate of each row from the distance along the sampled centerline.
+    ///
+    /// V runs from 0 at the start to `uv_tile_length` at the end.
+    fn apply_arc_length_v(&mut self, uv_tile_length: f32) {
+        let mut distances = Vec::with_capacity(self.samples.len());
+        let mut distance = 0.0;
+        for (i, sample) in self.samples.iter().enumerate() {
+            if i > 0 {
+                distance += sample.position.distance(self.samples[i - 1].position);
+            }
+            distances.push(distance);
+        }
+        if distance <= 0.0 {
+            return;
+        }
+
+        for (row, row_distance) in distances.into_iter().enumerate() {
+            let v = row_distance / distance * uv_tile_length;
+            for uv in &mut self.uvs[row * self.profile_len..(row + 1) * self.profile_len] {
+                uv[1] = v;
+            }
+        }
+    }
+
+    /// Close the row of vertices at `row` with a flat cap facing `outward`.
+    ///
+    /// The cap gets its own vertices so its normals stay flat.
+    fn push_cap(&mut self, row: usize, outward: Vec3) {
+        let ring = row * self.profile_len..(row + 1) * self.profile_len;
+        // (u, v, outward) is right-handed, so counter-clockwise in (u, v) faces outward
+        let u = outward.any_orthonormal_vector();
+        let v = outward.cross(u);
+        let outline: Vec<Vec2> = ring
+            .clone()
+            .map(|i| {
+                let position = Vec3::from_array(self.positions[i]);
+                Vec2::new(position.dot(u), position.dot(v))
+            })
+            .collect();
+        let triangles = triangulate_outline(&outline);
+        if triangles.is_empty() {
+            return;
+        }
+
+        let start = self.positions.len() as u32;
+        for i in ring {
+            self.positions.push(self.positions[i]);
+            self.normals.push(outward.to_array());
+            self.uvs.push(self.uvs[i]);
+        }
+        for triangle in triangles {
+            self.indices
+                .extend(triangle.map(|corner| start + corner as u32));
+        }
+    }
+
+    /// Build a triangle-list [`Mesh`] from this geometry.
+    pub fn into_mesh(self) -> Mesh {
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, self.positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, self.normals);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, self.uvs);
+        mesh.insert_indices(Indices::U32(self.indices));
+        mesh
+    }
+}
+
+/// Extrude a cross-section profile along a spline.
+///
+/// The spline is sampled at `segments + 1` evenly spaced t values. Returns `None`
+/// if the profile is empty, `segments` is zero, or the spline can't be evaluated.
+pub fn road_geometry(
+    spline: &Spline,
+    profile: &[ProfileVertex],
+    segments: usize,
+    options: &RoadGeometryOptions,
+) -> Option<RoadGeometry> {
+    if profile.is_empty() || segments == 0 {
+        return None;
+    }
+
+    // Split the profile at creases so each side gets its own vertices
+    let (profile, seams) = if options.smooth_normals {
+        (profile.to_vec(), vec![false; profile.len() - 1])
+    } else {
+        split_creases(profile)
+    };
+
+    let profile_len = profile.len();
+    let total_vertices = profile_len * (segments + 1);
+
+    let mut positions = Vec::with_capacity(total_vertices);
+    let mut normals = Vec::with_capacity(total_vertices);
+    let mut uvs = Vec::with_capacity(total_vertices);
+
+    let samples = road_samples(spline, segments, options.use_rmf)?;
+    for &RoadSample { t, position, frame } in &samples {
+        // Transform each profile vertex
+        for vertex in &profile {
+            let world_offset = frame.transform_profile_point(vertex.position.x, vertex.position.y);
+            let world_pos = position + world_offset;
+
+            positions.push(world_pos.to_array());
+            normals.push(frame.up.to_array());
+
+            // UV: X from profile, Y from spline progress
+            let v = t * options.uv_tile_length;
+            let u = vertex.uv.map(|uv| uv.x).unwrap_or(0.0);
+            uvs.push([u, v]);
+        }
+    }
+
+    // Two triangles per quad between consecutive rows
+    let mut indices = Vec::with_capacity(segments * (profile_len - 1) * 6);
+    for seg_idx in 0..segments {
+        let row_start = seg_idx * profile_len;
+        let next_row_start = (seg_idx + 1) * profile_len;
+
+        for (i, &seam) in seams.iter().enumerate() {
+            if seam {
+                continue;
+            }
+            let a = (row_start + i) as u32;
+            let b = (row_start + i + 1) as u32;
+            let c = (next_row_start + i) as u32;
+            let d = (next_row_start + i + 1) as u32;
+
+            indices.extend_from_slice(&[a, b, c]);
+            indices.extend_from_slice(&[b, d, c]);
+        }
+    }
+
+    let mut geometry = RoadGeometry {
+        positions,
+        normals,
+        uvs,
+        indices,
+        samples,
+        profile_len,
+    };
+
+    if options.uv_mode == RoadUvMode::ArcLength {
+        geometry.apply_arc_length_v(options.uv_tile_length);
+    }
+
+    if spline.closed {
+        // Stitch the last row onto the first so the loop has no gap, even when
+        // the frames don't line up exactly after going around
+        let last_row = segments * profile_len;
+        for j in 0..profile_len {
+            geometry.positions[last_row + j] = geometry.positions[j];
+            geometry.normals[last_row + j] = geometry.normals[j];
+        }
+    } else if options.cap_ends && profile_len >= 3 {
+        if !options.intersection_ends.contains(&RoadEnd::Start) {
+            let start_tangent = geometry.samples[0].frame.tangent;
+            geometry.push_cap(0, -start_tangent);
+        }
+        if !options.intersection_ends.contains(&RoadEnd::End) {
+            let end_tangent = geometry.samples[segments].frame.tangent;
+            geometry.push_cap(segments, end_tangent);
+        }
+    }
+
+    Some(geometry)
+}
+
+/// Sample `segments + 1` evenly spaced rows along the spline, with the frames
+/// [`road_geometry`] places the profile in.
+///
+/// With `use_rmf`, each frame is carried on from the previous row; otherwise it is
+/// built from the tangent alone. Returns `None` if the spline can't be evaluated.
+pub(crate) fn road_samples(
+    spline: &Spline,
+    segments: usize,
+    use_rmf: bool,
+) -> Option<Vec<RoadSample>> {
+    let spline_samples = spline.sample_with_tangents(segments + 1);
+    if spline_samples.len() != segments + 1 {
+        return None;
+    }
+
+    let mut samples: Vec<RoadSample> = Vec::with_capacity(segments + 1);
+    for (seg_idx, (position, tangent)) in spline_samples.into_iter().enumerate() {
+        let t = seg_idx as f32 / segments as f32;
+
+        // Build local coordinate frame
+        let frame = match samples.last() {
+            Some(previous) if use_rmf => {
+                previous
+                    .frame
+                    .transport(previous.position, position, tangent)
+            }
+            _ => CoordinateFrame::from_tangent(tangent),
+        };
+
+        samples.push(RoadSample { t, position, frame });
+    }
+    Some(samples)
+}
+
+/// The rotation-minimizing frame at `t`, carried on from the last of the
+/// `samples` (from [`road_samples`]) at or before it.
+///
+/// `position` and `tangent` are the spline's position and tangent at `t`.
+pub(crate) fn transport_road_frame(
+    samples: &[RoadSample],
+    t: f32,
+    position: Vec3,
+    tangent: Vec3,
+) -> Option<CoordinateFrame> {
+    let index = samples.partition_point(|sample| sample.t <= t).saturating_sub(1);
+    let previous = samples.get(index)?;
+    Some(previous.frame.transport(previous.position, position, tangent))
+}
+
+/// Ear-clip a closed outline into counter-clockwise triangles of indices into `points`.
+///
+/// Repeated vertices and vertices lying on the line through their neighbours are
+/// skipped, so outlines that enclose no area give no triangles and edges running
+/// back along each other (like the closing edge across the tops of curbs) don't
+/// produce overlapping triangles.
+fn triangulate_outline(points: &[Vec2]) -> Vec<[usize; 3]> {
+    let (min, max) = points
+        .iter()
+        .fold((Vec2::MAX, Vec2::MIN), |(min, max), &p| {
+            (min.min(p), max.max(p))
+        });
+    let extent = (max - min).max_element();
+    if extent.is_nan() || extent <= 0.0 {
+        return Vec::new();
+    }
+    let epsilon = extent * extent * 1e-6;
+    let corner = |[a, b, c]: [usize; 3]| (points[b] - points[a]).perp_dot(points[c] - points[b]);
+    let around = |remaining: &[usize], i: usize| {
+        let n = remaining.len();
+        [
+            remaining[(i + n - 1) % n],
+            remaining[i],
+            remaining[(i + 1) % n],
+        ]
+    };
+
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    while let Some(i) =
+        (0..remaining.len()).find(|&i| corner(around(&remaining, i)).abs() <= epsilon)
+    {
+        remaining.remove(i);
+        if remaining.len() < 3 {
+            return Vec::new();
+        }
+    }
+
+    let area: f32 = (0..remaining.len())
+        .map(|i| {
+            let [_, a, b] = around(&remaining, i);
+            points[a].perp_dot(points[b])
+        })
+        .sum();
+    if area.abs() <= epsilon {
+        return Vec::new();
+    }
+    if area < 0.0 {
+        remaining.reverse();
+    }
+
+    let mut triangles = Vec::with_capacity(remaining.len() - 2);
+    while remaining.len() >= 3 {
+        let ear = (0..remaining.len()).find(|&i| {
+            let triangle = around(&remaining, i);
+            corner(triangle) > epsilon
+                && remaining.iter().all(|&p| {
+                    triangle.contains(&p) || !in_triangle(points[p], triangle.map(|t| points[t]))
+                })
+        });
+        // Self-intersecting outlines run out of ears; cap what was clipped so far
+        let Some(i) = ear else {
+            break;
+        };
+        triangles.push(around(&remaining, i));
+        remaining.remove(i);
+    }
+    triangles
+}
+
+/// Whether `p` lies inside or on the counter-clockwise triangle `[a, b, c]`.
+fn in_triangle(p: Vec2, [a, b, c]: [Vec2; 3]) -> bool {
+    (b - a).perp_dot(p - a) >= 0.0
+        && (c - b).perp_dot(p - b) >= 0.0
+        && (a - c).perp_dot(p - c) >= 0.0
+}
+
+/// Duplicate each crease vertex of a profile.
+///
+/// Returns the new profile and, for each pair of neighbouring vertices, whether
+/// the pair is a seam between duplicates that must not be bridged by a quad.
+fn split_creases(profile: &[ProfileVertex]) -> (Vec<ProfileVertex>, Vec<bool>) {
+    let mut split = Vec::with_capacity(profile.len());
+    let mut seams = Vec::with_capacity(profile.len());
+    for (i, vertex) in profile.iter().enumerate() {
+        if i > 0 {
+            seams.push(false);
+        }
+        split.push(vertex.clone());
+        if vertex.crease {
+            seams.push(true);
+            split.push(vertex.clone());
+        }
+    }
+    (split, seams)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_road_geometry_layout() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 1.0, 2.0, 3.0]
+                .map(|x| Vec3::new(x * 4.0, 0.0, 0.0))
+                .to_vec(),
+        );
+        let profile = [-1.0, 0.0, 1.0].map(|x| ProfileVertex {
+            position: Vec3::new(x, 0.0, 0.0),
+            uv: Some(Vec2::new((x + 1.0) / 2.0, 0.0)),
+            crease: false,
+        });
+
+        let geometry =
+            road_geometry(&spline, &profile, 4, &RoadGeometryOptions::default()).unwrap();
+
+        assert_eq!(geometry.profile_len, 3);
+        assert_eq!(geometry.samples.len(), 5);
+        assert_eq!(geometry.positions.len(), 15);
+        assert_eq!(geometry.normals.len(), 15);
+        assert_eq!(geometry.uvs.len(), 15);
+        assert_eq!(geometry.indices.len(), 4 * 2 * 6);
+
+        // The middle profile vertex of each row lies on the spline
+        for (row, sample) in geometry.samples.iter().enumerate() {
+            let center = Vec3::from_array(geometry.positions[row * 3 + 1]);
+            assert!(center.distance(sample.position) < 1e-5);
+        }
+        assert_eq!(geometry.uvs[14], [1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_rmf_road_has_no_flips_through_vertical() {
+        // Climbs straight up and comes back down the other side
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, -4.0),
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(0.0, 4.0, 2.0),
+                Vec3::new(0.0, 8.0, 0.0),
+                Vec3::new(0.0, 8.0, -4.0),
+            ],
+        );
+        let profile = [-1.0, 1.0].map(|x| ProfileVertex {
+            position: Vec3::new(x, 0.0, 0.0),
+            uv: None,
+            crease: false,
+        });
+        let options = RoadGeometryOptions {
+            use_rmf: true,
+            ..default()
+        };
+
+        let geometry = road_geometry(&spline, &profile, 64, &options).unwrap();
+        for pair in geometry.samples.windows(2) {
+            assert!(pair[0].frame.up.dot(pair[1].frame.up) > 0.95);
+        }
+    }
+
+    #[test]
+    fn test_end_caps_face_outward() {
+        let mut spline = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 1.0, 2.0, 3.0]
+                .map(|x| Vec3::new(x * 4.0, 0.0, 0.0))
+                .to_vec(),
+        );
+        // Box profile, listed clockwise
+        let profile =
+            [(-1.0, 0.0), (-1.0, 1.0), (1.0, 1.0), (1.0, 0.0)].map(|(x, y)| ProfileVertex {
+                position: Vec3::new(x, y, 0.0),
+                uv: None,
+                crease: false,
+            });
+        let options = RoadGeometryOptions {
+            cap_ends: true,
+            ..default()
+        };
+
+        let geometry = road_geometry(&spline, &profile, 4, &options).unwrap();
+        let rows = 5 * 4;
+        assert_eq!(geometry.positions.len(), rows + 2 * 4);
+        assert_eq!(geometry.indices.len(), 4 * 3 * 6 + 2 * 2 * 3);
+
+        // Cap triangles wind towards the outside of each end
+        let tangent = geometry.samples[0].frame.tangent;
+        let caps = &geometry.indices[4 * 3 * 6..];
+        for (cap, outward) in caps.chunks(6).zip([-tangent, tangent]) {
+            for triangle in cap.chunks(3) {
+                let [a, b, c] =
+                    [0, 1, 2].map(|i| Vec3::from_array(geometry.positions[triangle[i] as usize]));
+                assert!((b - a).cross(c - a).dot(outward) > 0.0);
+            }
+        }
+        assert_eq!(geometry.normals[rows], (-tangent).to_array());
+
+        // Ends joined to an intersection stay open
+        let options = RoadGeometryOptions {
+            cap_ends: true,
+            intersection_ends: vec![RoadEnd::End],
+            ..default()
+        };
+        let geometry = road_geometry(&spline, &profile, 4, &options).unwrap();
+        assert_eq!(geometry.positions.len(), rows + 4);
+        assert_eq!(geometry.indices.len(), 4 * 3 * 6 + 2 * 3);
+
+        // Closed splines are stitched instead of capped
+        spline.closed = true;
+        let geometry = road_geometry(&spline, &profile, 4, &options).unwrap();
+        assert_eq!(geometry.positions.len(), rows);
+        assert_eq!(geometry.positions[..4], geometry.positions[rows - 4..]);
+    }
+
+    #[test]
+    fn test_arc_length_v_is_evenly_spaced() {
+        // Control points bunch up at the start, so t moves slowly there
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 0.5, 1.0, 10.0, 11.0]
+                .map(|x| Vec3::new(x, 0.0, 0.0))
+                .to_vec(),
+        );
+        let profile = [-1.0, 1.0].map(|x| ProfileVertex {
+            position: Vec3::new(x, 0.0, 0.0),
+            uv: None,
+            crease: false,
+        });
+        let options = RoadGeometryOptions {
+            uv_tile_length: 4.0,
+            uv_mode: RoadUvMode::ArcLength,
+            ..default()
+        };
+
+        let geometry = road_geometry(&spline, &profile, 32, &options).unwrap();
+        let v_per_distance: Vec<f32> = geometry
+            .samples
+            .windows(2)
+            .enumerate()
+            .map(|(i, pair)| {
+                let dv = geometry.uvs[(i + 1) * 2][1] - geometry.uvs[i * 2][1];
+                dv / pair[0].position.distance(pair[1].position)
+            })
+            .collect();
+        for ratio in &v_per_distance {
+            assert!((ratio - v_per_distance[0]).abs() < 1e-3);
+        }
+        assert_eq!(geometry.uvs[0][1], 0.0);
+        assert!((geometry.uvs[65][1] - 4.0).abs() < 1e-5);
+
+        // Parametric V follows t instead, so its increments vary with distance
+        let options = RoadGeometryOptions {
+            uv_tile_length: 4.0,
+            ..default()
+        };
+        let parametric = road_geometry(&spline, &profile, 32, &options).unwrap();
+        for (i, sample) in parametric.samples.iter().enumerate() {
+            let expected = sample.t * 4.0;
+            assert!((parametric.uvs[i * 2][1] - expected).abs() < 1e-6);
+            assert!((parametric.uvs[i * 2 + 1][1] - expected).abs() < 1e-6);
+        }
+        // Both start at zero but part ways in the middle of the road
+        assert!((parametric.uvs[0][1] - geometry.uvs[0][1]).abs() < 1e-6);
+        assert!((parametric.uvs[32][1] - geometry.uvs[32][1]).abs() > 0.1);
+    }
+}
@@ -12,19 +12,45 @@ use crate::geometry::CoordinateFrame;
 use crate::spline::Spline;
 use super::{extract_mesh_profile, SplineRoad};
 
+/// Distance below which two intersection points are considered coincident.
+const COINCIDENT_EPSILON: f32 = 1e-3;
+
 /// Calculate the coordinate frame at a point on the spline.
 /// Returns (position, frame) where frame contains tangent, right, and up vectors.
 fn calculate_frame(spline: &Spline, t: f32, direction: f32) -> Option<(Vec3, CoordinateFrame)> {
-    let position = spline.evaluate(t)?;
-    let tangent = spline
-        .evaluate_tangent(t)
-        .map(|t| t.normalize_or_zero() * direction)
-        .unwrap_or(Vec3::Z);
+    let (position, tangent) = spline.evaluate_pos_tan(t)?;
+    let tangent = tangent.normalize_or_zero() * direction;
 
     let frame = CoordinateFrame::from_tangent(tangent);
     Some((position, frame))
 }
 
+/// Determine the tangent direction multiplier that makes a road endpoint face the intersection.
+///
+/// [`RoadEnd::direction`] assumes the spline runs into the intersection at its end and out
+/// of it at its start. When the spline is authored the other way, the tangent at the
+/// endpoint points away from the center; in that case the direction is flipped so the
+/// frame (and its left/right edges) still face the intersection.
+fn infer_direction(spline: &Spline, end: RoadEnd, position: Vec3, center: Vec3) -> f32 {
+    let default_direction = end.direction();
+
+    let to_center = center - position;
+    if to_center.length_squared() < 1e-6 {
+        // Endpoint sits on the center - nothing to compare against
+        return default_direction;
+    }
+
+    let Some(tangent) = spline.evaluate_tangent(end.t()) else {
+        return default_direction;
+    };
+
+    if (tangent * default_direction).dot(to_center) < 0.0 {
+        -default_direction
+    } else {
+        default_direction
+    }
+}
+
 /// Which end of a road connects to an intersection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
 pub enum RoadEnd {
@@ -45,6 +71,9 @@ impl RoadEnd {
     }
 
     /// Get the direction multiplier (1 for outward from intersection, -1 for inward).
+    ///
+    /// This is the default assumption; intersections flip it when the spline's tangent
+    /// at this end points away from the intersection center.
     pub fn direction(&self) -> f32 {
         match self {
             RoadEnd::Start => -1.0, // Tangent points away, we want toward intersection
@@ -53,6 +82,18 @@ impl RoadEnd {
     }
 }
 
+/// How texture coordinates are laid out on an intersection mesh.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Reflect, Default)]
+pub enum IntersectionUvMode {
+    /// UVs spread radially from the center: (0.5, 0.5) at the center and the unit
+    /// circle at the road edges. Doesn't tile with the connected roads.
+    #[default]
+    Radial,
+    /// UVs are the vertex X and Z coordinates scaled by
+    /// [`RoadIntersection::uv_scale`], so tiling textures stay continuous.
+    Planar,
+}
+
 /// A connection between a road and an intersection.
 #[derive(Debug, Clone, Reflect)]
 pub struct RoadConnection {
@@ -93,6 +134,16 @@ pub struct RoadIntersection {
     /// Radius of the intersection (how far the blend extends).
     /// If None, calculated automatically from road widths.
     pub radius: Option<f32>,
+    /// How texture coordinates are laid out on the generated mesh.
+    #[reflect(default)]
+    pub uv_mode: IntersectionUvMode,
+    /// Texture repeats per world unit in [`IntersectionUvMode::Planar`] mode.
+    #[reflect(default = "default_uv_scale")]
+    pub uv_scale: f32,
+}
+
+fn default_uv_scale() -> f32 {
+    1.0
 }
 
 impl Default for RoadIntersection {
@@ -101,6 +152,8 @@ impl Default for RoadIntersection {
             connections: Vec::new(),
             auto_update: true,
             radius: None,
+            uv_mode: IntersectionUvMode::Radial,
+            uv_scale: 1.0,
         }
     }
 }
@@ -125,6 +178,19 @@ impl RoadIntersection {
         self.radius = Some(radius);
         self
     }
+
+    /// Set how texture coordinates are laid out.
+    pub fn with_uv_mode(mut self, uv_mode: IntersectionUvMode) -> Self {
+        self.uv_mode = uv_mode;
+        self
+    }
+
+    /// Use planar UVs with the given number of texture repeats per world unit.
+    pub fn with_planar_uvs(mut self, uv_scale: f32) -> Self {
+        self.uv_mode = IntersectionUvMode::Planar;
+        self.uv_scale = uv_scale;
+        self
+    }
 }
 
 /// Marker component for generated intersection mesh entities.
@@ -143,6 +209,8 @@ struct RoadEndpoint {
     left_edge: Vec3,
     /// Right edge position in world space (from road's perspective looking outward).
     right_edge: Vec3,
+    /// Direction from the intersection toward the road, used when the endpoint sits on the center.
+    outward: Vec3,
     /// The angle around the intersection center (for sorting).
     angle: f32,
 }
@@ -156,10 +224,14 @@ struct EdgePoint {
 
 /// Generate intersection mesh where roads meet.
 ///
-/// The mesh connects the edge vertices of each road to form a seamless surface.
+/// The edge points of all roads, sorted by angle around `center`, form the boundary
+/// polygon of the intersection: each road's mouth spans its two edges, and adjacent
+/// roads are joined by straight fillets. The polygon is then triangulated, so
+/// unequal angles and collinear roads (T-junctions) don't produce overlapping triangles.
 fn generate_intersection_mesh(
     endpoints: &[RoadEndpoint],
     center: Vec3,
+    intersection: &RoadIntersection,
 ) -> Option<Mesh> {
     if endpoints.len() < 2 {
         return None;
@@ -178,41 +250,31 @@ fn generate_intersection_mesh(
     }
 
     // Sort by angle for consistent ordering around the intersection
-    edge_points.sort_by(|a, b| a.angle.partial_cmp(&b.angle).unwrap());
+    edge_points.sort_by(|a, b| a.angle.total_cmp(&b.angle));
+
+    let boundary: Vec<Vec3> = edge_points.iter().map(|edge| edge.position).collect();
+    let indices = triangulate_boundary(&boundary);
+    if indices.is_empty() {
+        return None;
+    }
 
-    // Build mesh as a triangle fan from center
     let mut positions: Vec<[f32; 3]> = Vec::new();
     let mut normals = Vec::new();
     let mut uvs = Vec::new();
-    let mut indices: Vec<u32> = Vec::new();
 
-    // Add center vertex (index 0)
-    positions.push([center.x, center.y, center.z]);
-    normals.push([0.0, 1.0, 0.0]);
-    uvs.push([0.5, 0.5]);
-
-    // Add edge vertices in sorted angle order
-    for edge in &edge_points {
-        let pos = edge.position;
+    for &pos in &boundary {
         positions.push([pos.x, pos.y, pos.z]);
         normals.push([0.0, 1.0, 0.0]);
 
-        // UV based on position relative to center
-        let dir = (pos - center).normalize_or_zero();
-        uvs.push([0.5 + dir.x * 0.5, 0.5 + dir.z * 0.5]);
-    }
-
-    // Create triangle fan: center -> edge[i+1] -> edge[i]
-    // CW winding for upward-facing normals in Bevy
-    let num_edges = edge_points.len();
-    for i in 0..num_edges {
-        let curr_idx = (i + 1) as u32; // +1 because center is at index 0
-        let next_idx = ((i + 1) % num_edges + 1) as u32;
-
-        // Triangle: center -> next -> current (CW when viewed from above)
-        indices.push(0);
-        indices.push(next_idx);
-        indices.push(curr_idx);
+        let uv = match intersection.uv_mode {
+            IntersectionUvMode::Radial => {
+                // UV based on direction from center
+                let dir = (pos - center).normalize_or_zero();
+                [0.5 + dir.x * 0.5, 0.5 + dir.z * 0.5]
+            }
+            IntersectionUvMode::Planar => (pos.xz() * intersection.uv_scale).to_array(),
+        };
+        uvs.push(uv);
     }
 
     let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
@@ -224,7 +286,84 @@ fn generate_intersection_mesh(
     Some(mesh)
 }
 
+/// Triangulate a simple polygon lying roughly in the XZ plane by ear clipping.
+///
+/// Returns a triangle list wound to face +Y. Vertices that lie on the line between
+/// their neighbours are dropped without emitting a triangle, so no triangle has
+/// zero area.
+fn triangulate_boundary(points: &[Vec3]) -> Vec<u32> {
+    let flat: Vec<Vec2> = points.iter().map(|p| p.xz()).collect();
+    let n = flat.len();
+    if n < 3 {
+        return Vec::new();
+    }
+
+    // Twice the signed area; its sign tells which way the polygon winds
+    let area: f32 = (0..n).map(|i| flat[i].perp_dot(flat[(i + 1) % n])).sum();
+    if area.abs() < COINCIDENT_EPSILON * COINCIDENT_EPSILON {
+        return Vec::new();
+    }
+    let winding = area.signum();
+
+    let mut remaining: Vec<usize> = (0..n).collect();
+    let mut indices = Vec::with_capacity((n - 2) * 3);
+    let mut i = 0;
+    let mut misses = 0;
+
+    while remaining.len() >= 3 && misses < remaining.len() {
+        let len = remaining.len();
+        i %= len;
+        let prev = remaining[(i + len - 1) % len];
+        let curr = remaining[i];
+        let next = remaining[(i + 1) % len];
+        let [a, b, c] = [flat[prev], flat[curr], flat[next]];
+
+        let turn = (b - a).perp_dot(c - b) * winding;
+        if turn.abs() < COINCIDENT_EPSILON * COINCIDENT_EPSILON {
+            // Collinear (or duplicate) vertex - it adds nothing to the area
+            remaining.remove(i);
+            misses = 0;
+            continue;
+        }
+
+        let is_ear = turn > 0.0
+            && !remaining
+                .iter()
+                .any(|&j| j != prev && j != curr && j != next && inside_triangle(flat[j], a, b, c));
+        if !is_ear {
+            i += 1;
+            misses += 1;
+            continue;
+        }
+
+        // A negative XZ winding faces +Y
+        let triangle = if winding < 0.0 {
+            [prev, curr, next]
+        } else {
+            [prev, next, curr]
+        };
+        indices.extend(triangle.map(|index| index as u32));
+        remaining.remove(i);
+        misses = 0;
+    }
+
+    indices
+}
+
+/// Whether `p` lies strictly inside the triangle `abc`, in either winding.
+fn inside_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2) -> bool {
+    let d1 = (b - a).perp_dot(p - a);
+    let d2 = (c - b).perp_dot(p - b);
+    let d3 = (a - c).perp_dot(p - c);
+    (d1 > 0.0 && d2 > 0.0 && d3 > 0.0) || (d1 < 0.0 && d2 < 0.0 && d3 < 0.0)
+}
+
 /// System to update intersection meshes when roads change.
+///
+/// Invalid or duplicate connections and coincident endpoints are reported once per
+/// intersection with a warning. Degenerate intersections are skipped and any previously
+/// generated mesh is removed.
+#[allow(clippy::too_many_arguments)]
 pub fn update_intersection_meshes(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -240,6 +379,7 @@ pub fn update_intersection_meshes(
     changed_intersections: Query<Entity, Changed<RoadIntersection>>,
     existing_meshes: Query<(Entity, &GeneratedIntersectionMesh)>,
     children_query: Query<&Children>,
+    mut warned_intersections: Local<std::collections::HashSet<Entity>>,
 ) {
     let changed_spline_set: std::collections::HashSet<Entity> =
         changed_splines.iter().collect();
@@ -253,7 +393,7 @@ pub fn update_intersection_meshes(
         let needs_update = changed_intersection_set.contains(&intersection_entity)
             || (intersection.auto_update && intersection.connections.iter().any(|conn| {
                 changed_road_set.contains(&conn.road)
-                    || roads.get(conn.road).ok().map_or(false, |road| {
+                    || roads.get(conn.road).ok().is_some_and(|road| {
                         changed_spline_set.contains(&road.spline)
                     })
             }));
@@ -262,26 +402,107 @@ pub fn update_intersection_meshes(
             continue;
         }
 
-        // Gather endpoint information for each connected road
-        let mut endpoints: Vec<RoadEndpoint> = Vec::new();
+        // Find the existing mesh entity, if any
+        let existing_mesh_entity = children_query
+            .get(intersection_entity)
+            .ok()
+            .and_then(|children| children.iter().find(|&child| existing_meshes.contains(child)));
+
+        // Problems that make this intersection (partially) invalid, reported once
+        let mut problems: Vec<String> = Vec::new();
+
+        // Gather the connected road endpoints and the intersection center
+        let mut connected: Vec<(&SplineRoad, &Spline, &RoadConnection, Vec3)> = Vec::new();
         let mut center = Vec3::ZERO;
 
         for conn in &intersection.connections {
+            if connected
+                .iter()
+                .any(|(_, _, other, _)| other.road == conn.road && other.end == conn.end)
+            {
+                problems.push(format!(
+                    "duplicate connection to {:?} ({:?})",
+                    conn.road, conn.end
+                ));
+                continue;
+            }
+
             let Ok(road) = roads.get(conn.road) else {
+                problems.push(format!("{:?} is not a SplineRoad", conn.road));
                 continue;
             };
 
             let Ok(spline) = splines.get(road.spline) else {
+                problems.push(format!("road {:?} has no Spline", conn.road));
                 continue;
             };
 
             if !spline.is_valid() {
+                problems.push(format!(
+                    "spline of road {:?} has too few control points",
+                    conn.road
+                ));
+                continue;
+            }
+
+            let Some(position) = spline.evaluate(conn.end.t()) else {
                 continue;
+            };
+
+            center += position;
+            connected.push((road, spline, conn, position));
+        }
+
+        // Coincident endpoints collapse the fan into a degenerate mesh
+        let mut degenerate = false;
+        for (i, (_, _, a, pos_a)) in connected.iter().enumerate() {
+            for (_, _, b, pos_b) in &connected[i + 1..] {
+                if pos_a.distance_squared(*pos_b) < COINCIDENT_EPSILON * COINCIDENT_EPSILON {
+                    problems.push(format!(
+                        "endpoints of roads {:?} and {:?} coincide",
+                        a.road, b.road
+                    ));
+                    degenerate = true;
+                }
+            }
+        }
+
+        if connected.len() < 2 {
+            problems.push(format!(
+                "needs at least 2 valid connections, found {}",
+                connected.len()
+            ));
+            degenerate = true;
+        }
+
+        if problems.is_empty() {
+            warned_intersections.remove(&intersection_entity);
+        } else if warned_intersections.insert(intersection_entity) {
+            warn!(
+                "RoadIntersection {intersection_entity:?}: {}",
+                problems.join("; ")
+            );
+        }
+
+        if degenerate {
+            // Don't leave a stale mesh from a previous valid configuration behind
+            if let Some(mesh_entity) = existing_mesh_entity {
+                commands.entity(mesh_entity).despawn();
             }
+            continue;
+        }
+
+        // Calculate center point
+        center /= connected.len() as f32;
+
+        // Gather endpoint information for each connected road
+        let mut endpoints: Vec<RoadEndpoint> = Vec::new();
 
-            // Get the coordinate frame at the road endpoint
+        for (road, spline, conn, position) in connected {
+            // Get the coordinate frame at the road endpoint, facing the intersection
             let t = conn.end.t();
-            let Some((position, frame)) = calculate_frame(spline, t, conn.end.direction()) else {
+            let direction = infer_direction(spline, conn.end, position, center);
+            let Some((position, frame)) = calculate_frame(spline, t, direction) else {
                 continue;
             };
 
@@ -313,51 +534,45 @@ pub fn update_intersection_meshes(
                 (position + frame.right * half_width, position - frame.right * half_width)
             };
 
-            center += position;
+            // The frame tangent faces the intersection, so the road lies behind it
+            let outward = -frame.tangent;
 
             endpoints.push(RoadEndpoint {
                 position,
                 left_edge,
                 right_edge,
+                outward,
                 angle: 0.0, // Will be calculated below
             });
         }
 
-        if endpoints.len() < 2 {
-            continue;
-        }
-
-        // Calculate center point
-        center /= endpoints.len() as f32;
-
         // Calculate angles for each endpoint relative to center
         for endpoint in &mut endpoints {
-            let dir = (endpoint.position - center).normalize_or_zero();
+            let offset = endpoint.position - center;
+            // An endpoint on the center has no direction - use the road's own heading
+            let dir = if offset.length_squared() < COINCIDENT_EPSILON * COINCIDENT_EPSILON {
+                endpoint.outward
+            } else {
+                offset
+            };
             endpoint.angle = dir.z.atan2(dir.x);
         }
 
         // Sort endpoints by angle for proper mesh generation
-        endpoints.sort_by(|a, b| a.angle.partial_cmp(&b.angle).unwrap());
+        endpoints.sort_by(|a, b| a.angle.total_cmp(&b.angle));
 
         // Generate the intersection mesh
-        let Some(mesh) = generate_intersection_mesh(&endpoints, center) else {
+        let Some(mesh) = generate_intersection_mesh(&endpoints, center, intersection) else {
+            if let Some(mesh_entity) = existing_mesh_entity {
+                commands.entity(mesh_entity).despawn();
+            }
             continue;
         };
 
         let mesh_handle = meshes.add(mesh);
 
-        // Find or create the mesh entity
-        let mut found_mesh_entity = None;
-        if let Ok(children) = children_query.get(intersection_entity) {
-            for child in children.iter() {
-                if existing_meshes.get(child).is_ok() {
-                    found_mesh_entity = Some(child);
-                    break;
-                }
-            }
-        }
-
-        if let Some(mesh_entity) = found_mesh_entity {
+        // Update or create the mesh entity
+        if let Some(mesh_entity) = existing_mesh_entity {
             commands.entity(mesh_entity).insert(Mesh3d(mesh_handle));
             if let Some(mat) = material {
                 commands.entity(mesh_entity).insert(mat.clone());
@@ -396,3 +611,134 @@ pub fn cleanup_intersection_meshes(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_infer_direction_flips_reversed_spline() {
+        // Road whose start sits at x = 1, running away from an intersection at the origin
+        let outgoing = Spline::new(
+            SplineType::CubicBezier,
+            vec![Vec3::X, Vec3::X * 2.0, Vec3::X * 3.0, Vec3::X * 4.0],
+        );
+        // Same road authored the other way: it *ends* at x = 1 while moving away from the origin
+        let reversed = Spline::new(
+            SplineType::CubicBezier,
+            vec![Vec3::X * 4.0, Vec3::X * 3.0, Vec3::X * 2.0, Vec3::X],
+        );
+
+        // Start connection on a correctly-authored road keeps the default
+        assert_eq!(
+            infer_direction(&outgoing, RoadEnd::Start, Vec3::X, Vec3::ZERO),
+            RoadEnd::Start.direction()
+        );
+        // Tangent at the end of the reversed road still points toward the center
+        assert_eq!(
+            infer_direction(&reversed, RoadEnd::End, Vec3::X, Vec3::ZERO),
+            RoadEnd::End.direction()
+        );
+        // Connecting the wrong end flips the direction to face the center
+        assert_eq!(
+            infer_direction(&outgoing, RoadEnd::End, Vec3::X * 4.0, Vec3::ZERO),
+            -RoadEnd::End.direction()
+        );
+    }
+
+    /// Build endpoints for roads that end `distance` from the origin, heading away
+    /// from it at the given angles (in degrees, measured in the XZ plane).
+    fn radial_endpoints(angles: &[f32], distance: f32, half_width: f32) -> Vec<RoadEndpoint> {
+        let mut endpoints: Vec<RoadEndpoint> = angles
+            .iter()
+            .map(|angle| {
+                let (sin, cos) = angle.to_radians().sin_cos();
+                let outward = Vec3::new(cos, 0.0, sin);
+                let position = outward * distance;
+                let right = outward.cross(Vec3::Y);
+                RoadEndpoint {
+                    position,
+                    left_edge: position - right * half_width,
+                    right_edge: position + right * half_width,
+                    outward,
+                    angle: sin.atan2(cos),
+                }
+            })
+            .collect();
+        endpoints.sort_by(|a, b| a.angle.total_cmp(&b.angle));
+        endpoints
+    }
+
+    /// Check the mesh faces up, has no zero-area triangles, and covers its
+    /// boundary exactly once.
+    fn assert_clean_intersection(endpoints: &[RoadEndpoint]) {
+        let center = endpoints.iter().map(|e| e.position).sum::<Vec3>() / endpoints.len() as f32;
+        let mesh =
+            generate_intersection_mesh(endpoints, center, &RoadIntersection::default()).unwrap();
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("intersection mesh should have positions");
+        };
+        let indices: Vec<usize> = mesh.indices().unwrap().iter().collect();
+        assert_eq!(positions.len(), endpoints.len() * 2);
+
+        let mut triangle_area = 0.0;
+        for triangle in indices.chunks(3) {
+            let [a, b, c] =
+                [triangle[0], triangle[1], triangle[2]].map(|i| Vec3::from_array(positions[i]));
+            let normal = (b - a).cross(c - a);
+            assert!(normal.y > 1e-4, "degenerate or flipped triangle: {normal}");
+            triangle_area += normal.y / 2.0;
+        }
+
+        // Overlapping triangles would cover more than the boundary polygon
+        let boundary_area = (0..positions.len())
+            .map(|i| {
+                let a = Vec3::from_array(positions[i]).xz();
+                let b = Vec3::from_array(positions[(i + 1) % positions.len()]).xz();
+                a.perp_dot(b)
+            })
+            .sum::<f32>()
+            .abs()
+            / 2.0;
+        assert!((triangle_area - boundary_area).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_three_way_intersection_mesh() {
+        // T-junction: two collinear roads and a perpendicular branch
+        assert_clean_intersection(&radial_endpoints(&[0.0, 90.0, 180.0], 3.0, 2.0));
+        // Y-junction with unequal angles
+        assert_clean_intersection(&radial_endpoints(&[-20.0, 100.0, 215.0], 3.0, 2.0));
+    }
+
+    #[test]
+    fn test_four_way_intersection_mesh() {
+        assert_clean_intersection(&radial_endpoints(&[0.0, 90.0, 180.0, 270.0], 3.0, 2.0));
+        // Skewed crossing
+        assert_clean_intersection(&radial_endpoints(&[10.0, 60.0, 190.0, 280.0], 4.0, 1.5));
+    }
+
+    #[test]
+    fn test_planar_intersection_uvs() {
+        let endpoints = radial_endpoints(&[0.0, 90.0, 180.0], 3.0, 2.0);
+        let intersection = RoadIntersection::default().with_planar_uvs(0.25);
+        let mesh = generate_intersection_mesh(&endpoints, Vec3::ZERO, &intersection).unwrap();
+
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("intersection mesh should have positions");
+        };
+        let Some(bevy::mesh::VertexAttributeValues::Float32x2(uvs)) =
+            mesh.attribute(Mesh::ATTRIBUTE_UV_0)
+        else {
+            panic!("intersection mesh should have UVs");
+        };
+        for (position, uv) in positions.iter().zip(uvs) {
+            assert_eq!(*uv, [position[0] * 0.25, position[2] * 0.25]);
+        }
+    }
+}
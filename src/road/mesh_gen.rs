@@ -3,11 +3,12 @@ use bevy::{
     mesh::{Indices, PrimitiveTopology, VertexAttributeValues},
 };
 
-use crate::geometry::CoordinateFrame;
 use crate::spline::Spline;
 use crate::surface::SplineMeshProjection;
 
-use super::{GeneratedRoadMesh, SplineRoad};
+use super::{
+    road_geometry, GeneratedRoadMesh, RoadEnd, RoadGeometryOptions, RoadIntersection, SplineRoad,
+};
 use super::projection::NeedsProjection;
 
 /// Creates a simple road segment mesh for testing.
@@ -92,18 +93,47 @@ pub fn create_road_segment_mesh(
 }
 
 /// A vertex in a mesh cross-section profile.
+///
+/// Build one with [`ProfileVertex::new`] and [`ProfileVertex::with_crease`];
+/// more fields may be added, so it can't be built with a struct literal.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct ProfileVertex {
     /// The vertex position.
     pub position: Vec3,
     /// The UV coordinates (if available).
     pub uv: Option<Vec2>,
+    /// Whether the surface folds sharply at this vertex, like the corner of a curb.
+    ///
+    /// Creases get split into separate vertices when a road doesn't smooth its normals.
+    pub crease: bool,
+}
+
+impl ProfileVertex {
+    /// Create a profile vertex that isn't a crease.
+    pub fn new(position: Vec3, uv: Option<Vec2>) -> Self {
+        Self {
+            position,
+            uv,
+            crease: false,
+        }
+    }
+
+    /// Set whether the surface folds sharply at this vertex.
+    pub fn with_crease(mut self, crease: bool) -> Self {
+        self.crease = crease;
+        self
+    }
 }
 
+/// Minimum turn between neighbouring profile edges for a vertex to count as a crease.
+const CREASE_ANGLE: f32 = std::f32::consts::FRAC_PI_6;
+
 /// Extract the cross-section profile from a segment mesh.
 ///
 /// Returns vertices at the front edge (minimum Z) sorted by X coordinate.
 /// If `include_uvs` is true, UV coordinates are extracted when available.
+/// Interior vertices where the profile turns by more than 30° are marked as creases.
 pub fn extract_mesh_profile(mesh: &Mesh, include_uvs: bool) -> Option<Vec<ProfileVertex>> {
     let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?;
     let positions = match positions {
@@ -135,100 +165,150 @@ pub fn extract_mesh_profile(mesh: &Mesh, include_uvs: bool) -> Option<Vec<Profil
         .iter()
         .enumerate()
         .filter(|(_, p)| (p[2] - min_z).abs() < tolerance)
-        .map(|(i, p)| ProfileVertex {
-            position: Vec3::new(p[0], p[1], p[2]),
-            uv: uvs.map(|uvs| Vec2::new(uvs[i][0], uvs[i][1])),
+        .map(|(i, p)| {
+            ProfileVertex::new(
+                Vec3::new(p[0], p[1], p[2]),
+                uvs.map(|uvs| Vec2::new(uvs[i][0], uvs[i][1])),
+            )
         })
         .collect();
 
     // Sort by X coordinate for consistent ordering
     profile.sort_by(|a, b| a.position.x.partial_cmp(&b.position.x).unwrap());
 
+    for i in 1..profile.len().saturating_sub(1) {
+        let incoming = (profile[i].position - profile[i - 1].position).truncate();
+        let outgoing = (profile[i + 1].position - profile[i].position).truncate();
+        profile[i].crease = incoming.angle_to(outgoing).abs() > CREASE_ANGLE;
+    }
+
     Some(profile)
 }
 
 /// Generate a road mesh by extruding a cross-section along a spline.
+///
+/// The road has `segments` segments in total, unless the spline sets
+/// [`Spline::resolution_override`], which gives the segments per spline segment.
+///
+/// Builds on [`road_geometry`]; use that directly to access the raw vertex data.
 pub fn generate_road_mesh(
     spline: &Spline,
     segment_mesh: &Mesh,
     segments: usize,
     uv_tile_length: f32,
 ) -> Option<Mesh> {
-    let profile = extract_mesh_profile(segment_mesh, true)?;
-    if profile.is_empty() {
-        return None;
-    }
-
-    let profile_len = profile.len();
-    let total_vertices = profile_len * (segments + 1);
-
-    let mut positions = Vec::with_capacity(total_vertices);
-    let mut normals = Vec::with_capacity(total_vertices);
-    let mut uvs = Vec::with_capacity(total_vertices);
-
-    // Sample spline at each segment point
-    for seg_idx in 0..=segments {
-        let t = seg_idx as f32 / segments as f32;
-
-        let position = spline.evaluate(t)?;
-        let tangent = spline
-            .evaluate_tangent(t)
-            .map(|t| t.normalize_or_zero())
-            .unwrap_or(Vec3::Z);
-
-        // Build local coordinate frame
-        let frame = CoordinateFrame::from_tangent(tangent);
-
-        // Transform each profile vertex
-        for vertex in &profile {
-            // Transform from local to world space using coordinate frame
-            let world_offset = frame.transform_profile_point(vertex.position.x, vertex.position.y);
-            let world_pos = position + world_offset;
-
-            positions.push([world_pos.x, world_pos.y, world_pos.z]);
-            normals.push([frame.up.x, frame.up.y, frame.up.z]);
-
-            // UV: X from profile, Y from spline progress
-            let v = t * uv_tile_length;
-            let u = vertex.uv.map(|uv| uv.x).unwrap_or(0.0);
-            uvs.push([u, v]);
-        }
-    }
+    let options = RoadGeometryOptions {
+        uv_tile_length,
+        ..default()
+    };
+    generate_road_mesh_with_options(spline, segment_mesh, segments, &options)
+}
 
-    // Generate indices
-    // CCW winding for upward-facing normals
-    let mut indices = Vec::new();
-    for seg_idx in 0..segments {
-        let row_start = seg_idx * profile_len;
-        let next_row_start = (seg_idx + 1) * profile_len;
-
-        for i in 0..(profile_len - 1) {
-            let a = (row_start + i) as u32;
-            let b = (row_start + i + 1) as u32;
-            let c = (next_row_start + i) as u32;
-            let d = (next_row_start + i + 1) as u32;
-
-            // Two triangles per quad (CW winding for upward-facing in Bevy)
-            // a=back-left, b=back-right, c=front-left, d=front-right
-            indices.extend_from_slice(&[a, b, c]);
-            indices.extend_from_slice(&[b, d, c]);
-        }
+/// Number of segments a road mesh is extruded with, giving
+/// [`Spline::resolution_override`] precedence over `segments`.
+pub(crate) fn road_segments(spline: &Spline, segments: usize) -> usize {
+    match spline.resolution_override {
+        Some(_) => spline.resolution_or(segments) * spline.segment_count(),
+        None => segments,
     }
+}
 
-    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
-    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
-    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
-    mesh.insert_indices(Indices::U32(indices));
+/// Generate a road mesh with full control over the extrusion options.
+///
+/// Like [`generate_road_mesh`], [`Spline::resolution_override`] takes precedence
+/// over `segments`.
+pub fn generate_road_mesh_with_options(
+    spline: &Spline,
+    segment_mesh: &Mesh,
+    segments: usize,
+    options: &RoadGeometryOptions,
+) -> Option<Mesh> {
+    let profile = extract_mesh_profile(segment_mesh, true)?;
+    let segments = road_segments(spline, segments);
+    let mut mesh = road_geometry(spline, &profile, segments, options)?.into_mesh();
 
     // Recompute normals for smooth shading
     mesh.compute_normals();
+    insert_mesh_tangents(&mut mesh);
 
     Some(mesh)
 }
 
+/// Compute per-vertex tangents from the mesh's positions, normals and UVs, and
+/// insert them as [`Mesh::ATTRIBUTE_TANGENT`].
+///
+/// Tangents point along increasing U (across the road). The `w` component is the
+/// handedness of the bitangent, which follows increasing V along the spline.
+/// Does nothing if the mesh lacks indices, normals or UVs.
+pub(crate) fn insert_mesh_tangents(mesh: &mut Mesh) {
+    let (
+        Some(VertexAttributeValues::Float32x3(positions)),
+        Some(VertexAttributeValues::Float32x3(normals)),
+        Some(VertexAttributeValues::Float32x2(uvs)),
+        Some(indices),
+    ) = (
+        mesh.attribute(Mesh::ATTRIBUTE_POSITION),
+        mesh.attribute(Mesh::ATTRIBUTE_NORMAL),
+        mesh.attribute(Mesh::ATTRIBUTE_UV_0),
+        mesh.indices(),
+    )
+    else {
+        return;
+    };
+
+    let mut tangents = vec![Vec3::ZERO; positions.len()];
+    let mut bitangents = vec![Vec3::ZERO; positions.len()];
+    let indices: Vec<usize> = indices.iter().collect();
+
+    for &[a, b, c] in indices.as_chunks::<3>().0 {
+        let p0 = Vec3::from_array(positions[a]);
+        let edge1 = Vec3::from_array(positions[b]) - p0;
+        let edge2 = Vec3::from_array(positions[c]) - p0;
+        let uv0 = Vec2::from_array(uvs[a]);
+        let duv1 = Vec2::from_array(uvs[b]) - uv0;
+        let duv2 = Vec2::from_array(uvs[c]) - uv0;
+
+        let det = duv1.x * duv2.y - duv2.x * duv1.y;
+        if det.abs() < f32::EPSILON {
+            continue;
+        }
+        let tangent = (edge1 * duv2.y - edge2 * duv1.y) / det;
+        let bitangent = (edge2 * duv1.x - edge1 * duv2.x) / det;
+
+        for vertex in [a, b, c] {
+            tangents[vertex] += tangent;
+            bitangents[vertex] += bitangent;
+        }
+    }
+
+    let tangents: Vec<[f32; 4]> = tangents
+        .into_iter()
+        .zip(bitangents)
+        .zip(normals)
+        .map(|((tangent, bitangent), normal)| {
+            let normal = Vec3::from_array(*normal);
+            // Gram-Schmidt against the smoothed normal
+            let tangent = (tangent - normal * normal.dot(tangent))
+                .try_normalize()
+                .unwrap_or_else(|| normal.any_orthonormal_vector());
+            let handedness = if normal.cross(tangent).dot(bitangent) < 0.0 {
+                -1.0
+            } else {
+                1.0
+            };
+            tangent.extend(handedness).to_array()
+        })
+        .collect();
+
+    mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, tangents);
+}
+
 /// System to update road meshes when splines change.
+///
+/// Roads with [`SplineRoad::cap_ends`] are also rebuilt when an intersection they
+/// connect to changes, as their ends joined to it are left open.
 #[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
 pub fn update_road_meshes(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
@@ -239,18 +319,35 @@ pub fn update_road_meshes(
     existing_road_meshes: Query<(Entity, &GeneratedRoadMesh)>,
     road_mesh_children: Query<&Children>,
     projection_query: Query<(), With<SplineMeshProjection>>,
+    intersections: Query<Ref<RoadIntersection>>,
 ) {
     let changed_spline_set: std::collections::HashSet<Entity> = changed_splines.iter().collect();
 
+    let mut intersection_ends: std::collections::HashMap<Entity, Vec<RoadEnd>> = default();
+    let mut changed_intersection_roads = std::collections::HashSet::new();
+    for intersection in &intersections {
+        for conn in &intersection.connections {
+            intersection_ends
+                .entry(conn.road)
+                .or_default()
+                .push(conn.end);
+            if intersection.is_changed() {
+                changed_intersection_roads.insert(conn.road);
+            }
+        }
+    }
+
     // Collect roads that need updating
     let mut roads_to_update: Vec<(Entity, &SplineRoad, Option<&MeshMaterial3d<StandardMaterial>>)> = roads.iter().collect();
 
     // Also update roads whose splines changed
     for (entity, road, material) in &all_roads {
-        if road.auto_update && changed_spline_set.contains(&road.spline) {
-            if !roads_to_update.iter().any(|(e, _, _)| *e == entity) {
-                roads_to_update.push((entity, road, material));
-            }
+        let spline_changed = road.auto_update && changed_spline_set.contains(&road.spline);
+        let caps_changed = road.cap_ends && changed_intersection_roads.contains(&entity);
+        if (spline_changed || caps_changed)
+            && !roads_to_update.iter().any(|(e, _, _)| *e == entity)
+        {
+            roads_to_update.push((entity, road, material));
         }
     }
 
@@ -267,11 +364,19 @@ pub fn update_road_meshes(
             continue;
         };
 
-        let Some(generated) = generate_road_mesh(
+        let options = RoadGeometryOptions {
+            uv_tile_length: road.uv_tile_length,
+            uv_mode: road.uv_mode,
+            use_rmf: road.use_rmf,
+            cap_ends: road.cap_ends,
+            intersection_ends: intersection_ends.remove(&road_entity).unwrap_or_default(),
+            smooth_normals: road.smooth_normals,
+        };
+        let Some(generated) = generate_road_mesh_with_options(
             spline,
             segment_mesh,
             road.segments_per_curve,
-            road.uv_tile_length,
+            &options,
         ) else {
             continue;
         };
@@ -325,3 +430,122 @@ pub fn update_road_meshes(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_road_mesh_has_tangents() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(4.0, 0.0, 1.0),
+                Vec3::new(8.0, 0.0, -1.0),
+                Vec3::new(12.0, 0.0, 0.0),
+            ],
+        );
+        let segment = create_road_segment_mesh(4.0, 1.0, 0.0, 0.0);
+        let mesh = generate_road_mesh(&spline, &segment, 16, 1.0).unwrap();
+
+        let Some(VertexAttributeValues::Float32x4(tangents)) =
+            mesh.attribute(Mesh::ATTRIBUTE_TANGENT)
+        else {
+            panic!("road mesh should have Float32x4 tangents");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            mesh.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("road mesh should have normals");
+        };
+        assert_eq!(tangents.len(), mesh.count_vertices());
+
+        for (tangent, normal) in tangents.iter().zip(normals) {
+            let [x, y, z, w] = *tangent;
+            let tangent = Vec3::new(x, y, z);
+            assert!((tangent.length() - 1.0).abs() < 1e-4);
+            assert!(tangent.dot(Vec3::from_array(*normal)).abs() < 1e-4);
+            assert_eq!(w.abs(), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_hard_edges_split_curb_vertices() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 4.0, 8.0, 12.0]
+                .map(|x| Vec3::new(x, 0.0, 0.0))
+                .to_vec(),
+        );
+        let segment = create_road_segment_mesh(4.0, 1.0, 0.2, 0.5);
+
+        // The four inner corners of the curbs are creases, the outer edges aren't
+        let profile = extract_mesh_profile(&segment, true).unwrap();
+        let creases: Vec<bool> = profile.iter().map(|v| v.crease).collect();
+        assert_eq!(creases, [false, true, true, true, true, false]);
+
+        let smooth = generate_road_mesh(&spline, &segment, 8, 1.0).unwrap();
+        let options = RoadGeometryOptions {
+            smooth_normals: false,
+            cap_ends: true,
+            ..default()
+        };
+        let hard = generate_road_mesh_with_options(&spline, &segment, 8, &options).unwrap();
+        assert!(hard.count_vertices() > smooth.count_vertices());
+
+        // The road surface keeps a straight-up normal right up to the curb
+        let Some(VertexAttributeValues::Float32x3(normals)) =
+            hard.attribute(Mesh::ATTRIBUTE_NORMAL)
+        else {
+            panic!("road mesh should have normals");
+        };
+        assert!(normals.iter().all(|n| Vec3::from_array(*n).is_finite()));
+        let road_edge = Vec3::from_array(normals[4]);
+        assert!(road_edge.dot(Vec3::Y) > 0.999);
+    }
+
+    #[test]
+    fn test_curb_profile_caps_are_clean() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 4.0, 8.0, 12.0]
+                .map(|x| Vec3::new(x, 0.0, 0.0))
+                .to_vec(),
+        );
+        let segment = create_road_segment_mesh(4.0, 1.0, 0.2, 0.5);
+        let profile = extract_mesh_profile(&segment, true).unwrap();
+
+        for smooth_normals in [true, false] {
+            let options = RoadGeometryOptions {
+                cap_ends: true,
+                smooth_normals,
+                ..default()
+            };
+            let geometry = road_geometry(&spline, &profile, 8, &options).unwrap();
+            let cap_start = (9 * geometry.profile_len) as u32;
+            let position = |i: u32| Vec3::from_array(geometry.positions[i as usize]);
+
+            let mut cap_area = 0.0;
+            for triangle in geometry.indices.as_chunks::<3>().0 {
+                if triangle.iter().any(|&i| i < cap_start) {
+                    continue;
+                }
+                let [a, b, c] = triangle.map(position);
+                let normal = (b - a).cross(c - a);
+                let area = normal.length() / 2.0;
+                assert!(area > 1e-4, "degenerate cap triangle {triangle:?}");
+                assert!(normal.normalize().dot(Vec3::Y).abs() < 1e-3);
+                cap_area += area;
+            }
+
+            // Only the space between the curbs is enclosed, and nothing overlaps
+            let enclosed = 2.0 * 1.5 * 0.2;
+            assert!((cap_area - 2.0 * enclosed).abs() < 1e-4, "cap area {cap_area}");
+        }
+
+        // On by default
+        assert!(SplineRoad::default().cap_ends);
+    }
+}
@@ -1,10 +1,14 @@
+mod bake;
+mod geometry;
 mod intersection;
 mod mesh_gen;
 mod projection;
 
+pub use bake::*;
+pub use geometry::*;
 pub use intersection::*;
 pub use mesh_gen::*;
-pub use projection::NeedsProjection;
+pub use projection::{NeedsProjection, RoadProjectionCache};
 
 use bevy::prelude::*;
 use bevy::transform::TransformSystems;
@@ -97,7 +101,9 @@ impl Plugin for SplineRoadPlugin {
         }
 
         app.register_type::<SplineRoad>()
+            .register_type::<RoadUvMode>()
             .register_type::<RoadIntersection>()
+            .register_type::<IntersectionUvMode>()
             .register_type::<RoadEnd>()
             .register_type::<RoadConnection>()
             .add_systems(
@@ -111,13 +117,32 @@ impl Plugin for SplineRoadPlugin {
 
         // Run projection in PostUpdate after transform propagation
         // to ensure GlobalTransform is up to date.
-        // Only runs when avian3d physics is available.
+        // Moved roads are marked first; projection only runs when avian3d physics is available.
+        app.add_systems(
+            PostUpdate,
+            projection::mark_changed_road_projections.after(TransformSystems::Propagate),
+        );
+        #[cfg(feature = "avian3d")]
         app.add_systems(
             PostUpdate,
             projection::project_road_meshes
-                .after(TransformSystems::Propagate)
+                .after(projection::mark_changed_road_projections)
                 .run_if(projection::physics_available),
         );
+
+        // Without physics, fall back to ray casting against surface meshes
+        #[cfg(feature = "mesh_raycast")]
+        {
+            let project = (
+                projection::mark_roads_on_surface_changes,
+                projection::project_road_meshes_with_mesh_raycast,
+            )
+                .chain()
+                .after(projection::mark_changed_road_projections);
+            #[cfg(feature = "avian3d")]
+            let project = project.run_if(not(projection::physics_available));
+            app.add_systems(PostUpdate, project);
+        }
     }
 }
 
@@ -138,6 +163,37 @@ pub struct SplineRoad {
     /// UV tiling factor along the road length.
     /// Higher values = more texture repeats.
     pub uv_tile_length: f32,
+    /// How the texture repeats are spread along the road.
+    #[reflect(default)]
+    pub uv_mode: RoadUvMode,
+    /// Whether to orient the cross-section with rotation-minimizing frames.
+    ///
+    /// Prevents the road from flipping on steep climbs and loops; see
+    /// [`RoadGeometryOptions::use_rmf`]. Intersections still use world-up frames
+    /// at the road ends.
+    #[reflect(default)]
+    pub use_rmf: bool,
+    /// Whether to close the ends of the road with flat caps. On by default.
+    ///
+    /// Hides the open cross-section at the start and end of elevated roads. Ends
+    /// connected to a [`RoadIntersection`] stay open. Has no effect on closed
+    /// splines, or on profiles that don't enclose an area.
+    #[reflect(default = "default_cap_ends")]
+    pub cap_ends: bool,
+    /// Whether to smooth normals across the creases of the cross-section.
+    ///
+    /// Disable to keep sharp curb edges; crease vertices are then duplicated so
+    /// each face gets its own normal.
+    #[reflect(default = "default_smooth_normals")]
+    pub smooth_normals: bool,
+}
+
+fn default_cap_ends() -> bool {
+    true
+}
+
+fn default_smooth_normals() -> bool {
+    true
 }
 
 impl Default for SplineRoad {
@@ -148,6 +204,10 @@ impl Default for SplineRoad {
             segments_per_curve: 32,
             auto_update: true,
             uv_tile_length: 1.0,
+            uv_mode: RoadUvMode::Parametric,
+            use_rmf: false,
+            cap_ends: true,
+            smooth_normals: true,
         }
     }
 }
@@ -173,6 +233,30 @@ impl SplineRoad {
         self.uv_tile_length = tile;
         self
     }
+
+    /// Set how the texture repeats are spread along the road.
+    pub fn with_uv_mode(mut self, uv_mode: RoadUvMode) -> Self {
+        self.uv_mode = uv_mode;
+        self
+    }
+
+    /// Enable or disable end caps.
+    pub fn with_cap_ends(mut self, cap_ends: bool) -> Self {
+        self.cap_ends = cap_ends;
+        self
+    }
+
+    /// Enable or disable smoothing normals across profile creases.
+    pub fn with_smooth_normals(mut self, smooth_normals: bool) -> Self {
+        self.smooth_normals = smooth_normals;
+        self
+    }
+
+    /// Enable or disable rotation-minimizing frames.
+    pub fn with_rmf(mut self, use_rmf: bool) -> Self {
+        self.use_rmf = use_rmf;
+        self
+    }
 }
 
 /// Marker component for the generated road mesh entity.
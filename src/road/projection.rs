@@ -1,18 +1,36 @@
 //! Surface projection for road meshes.
+// Without a ray casting backend nothing is projected, leaving the shared code unused
+#![cfg_attr(
+    not(any(feature = "avian3d", feature = "mesh_raycast")),
+    allow(dead_code, unused_imports)
+)]
 
+use std::collections::HashSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+#[cfg(feature = "avian3d")]
 use avian3d::prelude::*;
 use bevy::{
-    camera::primitives::Aabb,
+    camera::primitives::{Aabb, MeshAabb},
     mesh::VertexAttributeValues,
     prelude::*,
 };
 
-use crate::surface::{create_projection_filter, SplineMeshProjection};
+#[cfg(feature = "mesh_raycast")]
+use bevy::picking::mesh_picking::ray_cast::MeshRayCast;
+
+#[cfg(feature = "avian3d")]
+use crate::surface::create_projection_filter;
+use crate::surface::{cast_projection_ray_with, SplineMeshProjection};
+#[cfg(feature = "mesh_raycast")]
+use crate::surface::{mesh_ray_caster, ProjectionSurface};
 
+use super::mesh_gen::insert_mesh_tangents;
 use super::{GeneratedRoadMesh, SplineRoad};
 
 /// Run condition that checks if avian3d physics is available.
 /// We check for the Gravity resource which is always present when PhysicsPlugins is added.
+#[cfg(feature = "avian3d")]
 pub fn physics_available(gravity: Option<Res<Gravity>>) -> bool {
     gravity.is_some()
 }
@@ -22,46 +40,262 @@ pub fn physics_available(gravity: Option<Res<Gravity>>) -> bool {
 #[derive(Component, Debug, Clone, Copy)]
 pub struct NeedsProjection;
 
+/// What a road mesh was last projected from, so unchanged roads aren't projected again.
+///
+/// Added to the road mesh entity after a successful projection. When the road is
+/// marked with [`NeedsProjection`] again, e.g. because its mesh was regenerated, the
+/// cached projection is reused if the unprojected mesh, the mesh's transform and the
+/// road's [`SplineMeshProjection`] are all unchanged.
+///
+/// The surface itself isn't part of the cache. [`ProjectionSurface`] meshes that
+/// move or change invalidate it on their own; after editing collider terrain,
+/// [`invalidate`](Self::invalidate) the cache and insert [`NeedsProjection`] to
+/// project the road again.
+///
+/// Removed along with the projection when the road's [`SplineMeshProjection`] is
+/// disabled or removed, putting the unprojected mesh back.
+#[derive(Component, Debug, Clone)]
+pub struct RoadProjectionCache {
+    /// Hash of the unprojected mesh, its transform and the projection settings,
+    /// or `None` if invalidated.
+    key: Option<u64>,
+    /// The unprojected mesh, kept to project again when the transform or settings change.
+    source: Handle<Mesh>,
+    /// The projected mesh.
+    projected: Handle<Mesh>,
+}
+
+impl RoadProjectionCache {
+    /// Make the next projection of the road cast its rays again, even if nothing
+    /// in the cache changed.
+    pub fn invalidate(&mut self) {
+        self.key = None;
+    }
+}
+
+/// Road meshes waiting to be projected.
+type QueuedRoadMeshes<'w, 's> = Query<
+    'w,
+    's,
+    (
+        Entity,
+        &'static GeneratedRoadMesh,
+        &'static Mesh3d,
+        &'static GlobalTransform,
+        Option<&'static RoadProjectionCache>,
+    ),
+    With<NeedsProjection>,
+>;
+
+/// System to mark projected road meshes for projection again when they move or the
+/// road's projection settings change or are removed.
+///
+/// Mesh regeneration marks roads itself, so spline edits are picked up without this.
+#[allow(clippy::type_complexity)]
+pub fn mark_changed_road_projections(
+    mut commands: Commands,
+    mut removed: RemovedComponents<SplineMeshProjection>,
+    roads: Query<Ref<SplineMeshProjection>, With<SplineRoad>>,
+    road_meshes: Query<
+        (
+            Entity,
+            &GeneratedRoadMesh,
+            Ref<GlobalTransform>,
+            Has<RoadProjectionCache>,
+        ),
+        Without<NeedsProjection>,
+    >,
+) {
+    let removed: HashSet<Entity> = removed.read().collect();
+    for (mesh_entity, generated, global_transform, cached) in &road_meshes {
+        let changed = match roads.get(generated.road) {
+            Ok(config) => config.is_changed() || global_transform.is_changed(),
+            // Put the unprojected mesh back
+            Err(_) => cached && removed.contains(&generated.road),
+        };
+        if changed {
+            commands.entity(mesh_entity).insert(NeedsProjection);
+        }
+    }
+}
+
+/// System to project road meshes again when a [`ProjectionSurface`] moves, changes
+/// its mesh or goes away, since their [`RoadProjectionCache`] doesn't cover surfaces.
+#[cfg(feature = "mesh_raycast")]
+pub fn mark_roads_on_surface_changes(
+    mut commands: Commands,
+    mut mesh_events: MessageReader<AssetEvent<Mesh>>,
+    mut removed: RemovedComponents<ProjectionSurface>,
+    surfaces: Query<(Ref<Mesh3d>, Ref<GlobalTransform>), With<ProjectionSurface>>,
+    mut road_meshes: Query<(Entity, &mut RoadProjectionCache)>,
+) {
+    let modified: HashSet<AssetId<Mesh>> = mesh_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    let removed = removed.read().count() > 0;
+    let changed = surfaces.iter().any(|(mesh, transform)| {
+        mesh.is_changed() || transform.is_changed() || modified.contains(&mesh.id())
+    });
+    if !removed && !changed {
+        return;
+    }
+
+    for (mesh_entity, mut cache) in &mut road_meshes {
+        cache.invalidate();
+        commands.entity(mesh_entity).insert(NeedsProjection);
+    }
+}
+
 /// System to project road mesh vertices onto surfaces below.
+#[cfg(feature = "avian3d")]
 pub fn project_road_meshes(
     mut commands: Commands,
     spatial_query: SpatialQuery,
-    mut meshes: ResMut<Assets<Mesh>>,
+    meshes: Res<Assets<Mesh>>,
     roads: Query<&SplineMeshProjection, With<SplineRoad>>,
-    road_meshes: Query<(Entity, &GeneratedRoadMesh, &Mesh3d, &GlobalTransform), With<NeedsProjection>>,
+    road_meshes: QueuedRoadMeshes,
+) {
+    let cast_ray = |config: &SplineMeshProjection, origin, direction, max| {
+        let filter = create_projection_filter(config);
+        let hit = spatial_query.cast_ray(origin, direction, max, true, &filter)?;
+        Some((hit.distance, hit.normal))
+    };
+    project_queued_road_meshes(&mut commands, &meshes, &roads, &road_meshes, cast_ray);
+}
+
+/// System to project road mesh vertices onto [`ProjectionSurface`] meshes below,
+/// for when avian3d physics isn't available.
+///
+/// Like [`project_road_meshes`], a road is retried every frame until some of its
+/// rows hit a surface, e.g. while the surface mesh is still loading.
+#[cfg(feature = "mesh_raycast")]
+pub fn project_road_meshes_with_mesh_raycast(
+    mut commands: Commands,
+    mut mesh_ray_cast: MeshRayCast,
+    surfaces: Query<(), With<ProjectionSurface>>,
+    meshes: Res<Assets<Mesh>>,
+    roads: Query<&SplineMeshProjection, With<SplineRoad>>,
+    road_meshes: QueuedRoadMeshes,
+) {
+    let mut cast_mesh_ray = mesh_ray_caster(&mut mesh_ray_cast, &surfaces);
+    let cast_ray =
+        |_: &SplineMeshProjection, origin, direction, max| cast_mesh_ray(origin, direction, max);
+    project_queued_road_meshes(&mut commands, &meshes, &roads, &road_meshes, cast_ray);
+}
+
+/// Project the road meshes marked with [`NeedsProjection`], or reuse their
+/// [`RoadProjectionCache`] if nothing changed.
+///
+/// `cast_ray` is given the road's projection settings along with the ray.
+fn project_queued_road_meshes(
+    commands: &mut Commands,
+    meshes: &Assets<Mesh>,
+    roads: &Query<&SplineMeshProjection, With<SplineRoad>>,
+    road_meshes: &QueuedRoadMeshes,
+    mut cast_ray: impl FnMut(&SplineMeshProjection, Vec3, Dir3, f32) -> Option<(f32, Vec3)>,
 ) {
-    for (mesh_entity, generated, mesh3d, global_transform) in &road_meshes {
-        let Ok(config) = roads.get(generated.road) else {
-            commands.entity(mesh_entity).remove::<NeedsProjection>();
+    for (mesh_entity, generated, mesh3d, global_transform, cache) in road_meshes {
+        let Some(config) = enabled_projection(roads, generated.road) else {
+            let mut entity_commands = commands.entity(mesh_entity);
+            entity_commands.remove::<(NeedsProjection, RoadProjectionCache)>();
+
+            // Switched off: show the unprojected mesh again
+            if let Some(cache) = cache.filter(|cache| cache.projected == mesh3d.0) {
+                entity_commands.insert(Mesh3d(cache.source.clone()));
+                if let Some(aabb) = meshes
+                    .get(&cache.source)
+                    .and_then(|mesh| mesh.compute_aabb())
+                {
+                    entity_commands.insert(aabb);
+                }
+            }
             continue;
         };
 
-        if !config.enabled {
-            commands.entity(mesh_entity).remove::<NeedsProjection>();
+        // Project again from the unprojected mesh if this one is already projected
+        let source = match cache {
+            Some(cache) if cache.projected == mesh3d.0 => cache.source.clone(),
+            _ => mesh3d.0.clone(),
+        };
+        let Some(source_mesh) = meshes.get(&source) else {
             continue;
-        }
+        };
 
-        let Some(mesh) = meshes.get_mut(&mesh3d.0) else {
+        let key = projection_key(source_mesh, global_transform, config);
+        if let Some(cache) = cache.filter(|cache| cache.key == Some(key)) {
+            commands
+                .entity(mesh_entity)
+                .insert(Mesh3d(cache.projected.clone()))
+                .remove::<NeedsProjection>();
             continue;
-        };
+        }
+
+        // Project a copy, keeping the unprojected mesh for later changes
+        let mut mesh = source_mesh.clone();
 
         // Get the world transform to convert local vertices to world space
         let transform = global_transform.compute_transform();
-        let result = project_mesh_vertices(mesh, &spatial_query, config, &transform);
+        let result =
+            project_mesh_vertices(&mut mesh, config, &transform, |origin, direction, max| {
+                cast_ray(config, origin, direction, max)
+            });
 
         // If we got hits, projection succeeded - update AABB and remove marker
         // If no hits, physics might not be ready yet - keep marker to retry next frame
         if let Some(aabb) = result {
+            let projected = meshes.reserve_handle();
+            let id = projected.id();
+            commands.queue(move |world: &mut World| {
+                let _ = world.resource_mut::<Assets<Mesh>>().insert(id, mesh);
+            });
+
             // Update the AABB component to reflect new mesh bounds
             // This is required for correct frustum culling and shadow maps
-            commands.entity(mesh_entity)
-                .insert(aabb)
+            commands
+                .entity(mesh_entity)
+                .insert((
+                    Mesh3d(projected.clone()),
+                    aabb,
+                    RoadProjectionCache {
+                        key: Some(key),
+                        source,
+                        projected,
+                    },
+                ))
                 .remove::<NeedsProjection>();
         }
     }
 }
 
+/// The projection settings of `road`, if it has them and they're enabled.
+fn enabled_projection<'a>(
+    roads: &'a Query<&SplineMeshProjection, With<SplineRoad>>,
+    road: Entity,
+) -> Option<&'a SplineMeshProjection> {
+    roads.get(road).ok().filter(|config| config.enabled)
+}
+
+/// Hash of everything a road's projection depends on, apart from the surface.
+fn projection_key(mesh: &Mesh, transform: &GlobalTransform, config: &SplineMeshProjection) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for (attribute, values) in mesh.attributes() {
+        attribute.id.hash(&mut hasher);
+        values.get_bytes().hash(&mut hasher);
+    }
+    if let Some(indices) = mesh.indices() {
+        indices.iter().for_each(|index| index.hash(&mut hasher));
+    }
+    // Debug formatting prints floats exactly and covers every setting
+    format!("{transform:?}{config:?}").hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Projection data for a single row (cross-section).
+#[derive(Clone, Copy)]
 struct RowProjection {
     /// Offset to apply to all vertices in this row.
     offset: Vec3,
@@ -76,18 +310,20 @@ struct RowProjection {
 /// Instead of projecting each vertex individually (which flattens the profile),
 /// this function:
 /// 1. Groups vertices into rows using UV V-coordinates (all vertices in a cross-section share the same V)
-/// 2. For each row, finds the center point at the base (minimum Y)
-/// 3. Projects only the center point to the terrain
-/// 4. Smooths projection data across adjacent rows to avoid bumps
+/// 2. For each row, finds the center point at the base (lowest along the projection's up)
+/// 3. Projects only the center point to the terrain, casting rays with `cast_ray`
+///    (see [`cast_projection_ray_with`])
+/// 4. Smooths projection data across adjacent rows to avoid bumps, over
+///    [`SplineMeshProjection::smoothing_window`] rows on each side
 /// 5. Rotates the cross-section to match terrain slope (camber)
 /// 6. Applies offset to all vertices in the row, preserving their relative positions
 ///
 /// Returns the new AABB if any vertices were projected, None otherwise.
 fn project_mesh_vertices(
     mesh: &mut Mesh,
-    spatial_query: &SpatialQuery,
     config: &SplineMeshProjection,
     transform: &Transform,
+    mut cast_ray: impl FnMut(Vec3, Dir3, f32) -> Option<(f32, Vec3)>,
 ) -> Option<Aabb> {
     let positions = mesh.attribute(Mesh::ATTRIBUTE_POSITION)?;
     let VertexAttributeValues::Float32x3(positions) = positions else {
@@ -111,20 +347,25 @@ fn project_mesh_vertices(
         return None;
     }
 
-    let filter = create_projection_filter(config);
-
     // Compute inverse transform for converting world -> local
     let inverse_affine = transform.compute_affine().inverse();
 
+    // The road profile's up, in world space and in the mesh's local space
+    let up = config.up();
+    let local_up = inverse_affine
+        .transform_vector3(up)
+        .try_normalize()
+        .unwrap_or(Vec3::Y);
+
     // First pass: compute world-space centers for each row
     let row_centers: Vec<Vec3> = rows
         .iter()
-        .map(|row_indices| {
+        .map(|(_, row_indices)| {
             let row_positions: Vec<Vec3> = row_indices
                 .iter()
                 .map(|&i| Vec3::from_array(positions[i]))
                 .collect();
-            let local_center = compute_row_base_center(&row_positions);
+            let local_center = compute_row_base_center(&row_positions, local_up);
             transform.transform_point(local_center)
         })
         .collect();
@@ -132,24 +373,27 @@ fn project_mesh_vertices(
     // Second pass: collect raw projection data for each row
     let mut raw_projections: Vec<RowProjection> = Vec::with_capacity(rows.len());
 
-    for (row_idx, _) in rows.iter().enumerate() {
+    // Rows are sorted by V, so the first and last rows are the road's ends
+    let first_v = rows[0].0;
+    let v_span = rows[rows.len() - 1].0 - first_v;
+
+    for (row_idx, (v, _)) in rows.iter().enumerate() {
         let world_center = row_centers[row_idx];
         let tangent = estimate_tangent(&row_centers, row_idx);
 
-        let ray_origin = world_center + Vec3::Y * config.ray_origin_offset;
-        let ray_direction = Dir3::NEG_Y;
-
-        let projection = if let Some(hit) = spatial_query.cast_ray(
-            ray_origin,
-            ray_direction,
-            config.max_distance,
-            true,
-            &filter,
-        ) {
-            let hit_position = ray_origin + *ray_direction * hit.distance;
-            let world_adjusted = hit_position + hit.normal * config.normal_offset;
+        // Position along the road for the offset curve
+        let t = if v_span > 0.0 {
+            (v - first_v) / v_span
+        } else {
+            0.0
+        };
+        let normal_offset = config.normal_offset + config.offset_at(t);
+
+        let hit = cast_projection_ray_with(world_center, config, &mut cast_ray);
+        let projection = if let Some(hit) = hit {
+            let world_adjusted = hit.with_normal_offset(normal_offset);
             let offset = world_adjusted - world_center;
-            let rotation = compute_camber_rotation(tangent, hit.normal);
+            let rotation = compute_camber_rotation(tangent, hit.normal, up);
 
             RowProjection {
                 offset,
@@ -174,12 +418,12 @@ fn project_mesh_vertices(
     }
 
     // Third pass: smooth the projection data
-    let smoothed_projections = smooth_projections(&raw_projections);
+    let smoothed_projections = smooth_projections(&raw_projections, config.smoothing_window);
 
     // Fourth pass: apply smoothed projections to vertices
     let mut new_positions: Vec<[f32; 3]> = vec![[0.0; 3]; positions.len()];
 
-    for (row_idx, row_indices) in rows.iter().enumerate() {
+    for (row_idx, (_, row_indices)) in rows.iter().enumerate() {
         if row_indices.is_empty() {
             continue;
         }
@@ -211,8 +455,8 @@ fn project_mesh_vertices(
     // Recompute normals for smooth shading
     mesh.compute_normals();
 
-    // Remove stale tangent data
-    mesh.remove_attribute(Mesh::ATTRIBUTE_TANGENT);
+    // Tangents must follow the new normals
+    insert_mesh_tangents(mesh);
 
     // Compute new AABB from modified positions
     Aabb::enclosing(new_positions.iter().map(|p| Vec3::from_array(*p)))
@@ -220,15 +464,14 @@ fn project_mesh_vertices(
 
 /// Smooth projection data across adjacent rows using a weighted moving average.
 /// This prevents bumpy roads caused by small terrain variations.
-fn smooth_projections(raw: &[RowProjection]) -> Vec<RowProjection> {
-    if raw.is_empty() {
-        return Vec::new();
+///
+/// `window` is the number of rows on each side to consider. Larger values are
+/// smoother but less terrain-conforming; 0 returns the raw projections.
+fn smooth_projections(raw: &[RowProjection], window: usize) -> Vec<RowProjection> {
+    if window == 0 {
+        return raw.to_vec();
     }
 
-    // Smoothing window size (number of rows on each side to consider)
-    // Larger values = smoother but less terrain-conforming
-    let window = 3;
-
     let mut smoothed = Vec::with_capacity(raw.len());
 
     for i in 0..raw.len() {
@@ -239,8 +482,8 @@ fn smooth_projections(raw: &[RowProjection]) -> Vec<RowProjection> {
         let mut sum_offset = Vec3::ZERO;
         let mut sum_rotation = Vec4::ZERO; // Using Vec4 for quaternion averaging
 
-        for j in start..end {
-            if !raw[j].has_hit {
+        for (j, row) in raw.iter().enumerate().take(end).skip(start) {
+            if !row.has_hit {
                 continue;
             }
 
@@ -248,13 +491,17 @@ fn smooth_projections(raw: &[RowProjection]) -> Vec<RowProjection> {
             let dist = (i as f32 - j as f32).abs();
             let weight = (-dist * dist / (window as f32)).exp();
 
-            sum_offset += raw[j].offset * weight;
+            sum_offset += row.offset * weight;
 
             // For quaternion averaging, accumulate as vec4
-            let q = raw[j].rotation;
+            let q = row.rotation;
             // Handle quaternion sign to ensure proper averaging
             let q_vec = Vec4::new(q.x, q.y, q.z, q.w);
-            let q_vec = if sum_rotation.dot(q_vec) < 0.0 { -q_vec } else { q_vec };
+            let q_vec = if sum_rotation.dot(q_vec) < 0.0 {
+                -q_vec
+            } else {
+                q_vec
+            };
             sum_rotation += q_vec * weight;
 
             total_weight += weight;
@@ -307,15 +554,13 @@ fn estimate_tangent(row_centers: &[Vec3], row_idx: usize) -> Vec3 {
 }
 
 /// Compute rotation to tilt the road cross-section to match terrain slope.
-/// This creates camber by rotating around the tangent (forward) axis.
-fn compute_camber_rotation(tangent: Vec3, terrain_normal: Vec3) -> Quat {
+/// This creates camber by rotating around the tangent (forward) axis, starting
+/// from the road's original `up`.
+fn compute_camber_rotation(tangent: Vec3, terrain_normal: Vec3, original_up: Vec3) -> Quat {
     if tangent.length_squared() < 0.001 {
         return Quat::IDENTITY;
     }
 
-    // The road's original "up" is world Y
-    let original_up = Vec3::Y;
-
     // Project terrain normal onto the plane perpendicular to the tangent
     // This gives us the "effective up" direction for the road at this point
     let normal_along_tangent = tangent * terrain_normal.dot(tangent);
@@ -346,18 +591,21 @@ fn compute_camber_rotation(tangent: Vec3, terrain_normal: Vec3) -> Quat {
 }
 
 /// Group vertex indices by their UV V-coordinate (rows in the mesh).
-/// Returns a Vec of Vec<usize> where each inner Vec contains indices of vertices in the same row.
+/// Returns each row's V with the indices of its vertices, sorted by V.
 fn group_vertices_by_uv_row(
     positions: &[[f32; 3]],
     uvs: Option<&Vec<[f32; 2]>>,
-) -> Vec<Vec<usize>> {
+) -> Vec<(f32, Vec<usize>)> {
+    // No UVs - fall back to single-vertex rows (old behavior, will flatten),
+    // with the vertex index standing in for V
+    let single_vertex_rows = || (0..positions.len()).map(|i| (i as f32, vec![i])).collect();
+
     let Some(uvs) = uvs else {
-        // No UVs - fall back to single-vertex rows (old behavior, will flatten)
-        return positions.iter().enumerate().map(|(i, _)| vec![i]).collect();
+        return single_vertex_rows();
     };
 
     if uvs.len() != positions.len() {
-        return positions.iter().enumerate().map(|(i, _)| vec![i]).collect();
+        return single_vertex_rows();
     }
 
     // Group indices by V coordinate with tolerance
@@ -368,7 +616,9 @@ fn group_vertices_by_uv_row(
         let v = uv[1];
 
         // Find existing row with matching V
-        let found = rows.iter_mut().find(|(row_v, _)| (row_v - v).abs() < tolerance);
+        let found = rows
+            .iter_mut()
+            .find(|(row_v, _)| (row_v - v).abs() < tolerance);
 
         if let Some((_, indices)) = found {
             indices.push(idx);
@@ -380,26 +630,373 @@ fn group_vertices_by_uv_row(
     // Sort rows by V coordinate for consistent ordering
     rows.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
 
-    // Extract just the index vectors
-    rows.into_iter().map(|(_, indices)| indices).collect()
+    rows
 }
 
 /// Compute the base center point of a row of vertices.
-/// Returns a point at (average_x, min_y, average_z) - using min_y as the base height
-/// so we project from the road surface, not the top of curbs.
-fn compute_row_base_center(vertices: &[Vec3]) -> Vec3 {
+/// Returns the average position moved along `up` to the lowest vertex - using the
+/// lowest height as the base so we project from the road surface, not the top of curbs.
+/// With `up` along Y, this is (average_x, min_y, average_z).
+fn compute_row_base_center(vertices: &[Vec3], up: Vec3) -> Vec3 {
     if vertices.is_empty() {
         return Vec3::ZERO;
     }
 
     let mut sum = Vec3::ZERO;
-    let mut min_y = f32::MAX;
+    let mut min_height = f32::MAX;
 
     for v in vertices {
         sum += *v;
-        min_y = min_y.min(v.y);
+        min_height = min_height.min(v.dot(up));
+    }
+
+    let average = sum / vertices.len() as f32;
+    average + up * (min_height - average.dot(up))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surface::plane_cast;
+    use bevy::mesh::{Indices, PrimitiveTopology};
+    use std::f32::consts::FRAC_PI_2;
+
+    /// A flat strip two units wide along +Z, one row of two vertices per unit length.
+    fn strip_mesh(rows: usize) -> Mesh {
+        let mut positions = Vec::new();
+        let mut uvs = Vec::new();
+        let mut indices = Vec::new();
+        for row in 0..rows {
+            let z = row as f32;
+            let v = z / (rows - 1) as f32;
+            positions.extend([[-1.0, 0.0, z], [1.0, 0.0, z]]);
+            uvs.extend([[0.0, v], [1.0, v]]);
+            if row > 0 {
+                let i = (row as u32 - 1) * 2;
+                indices.extend([i, i + 2, i + 1, i + 1, i + 2, i + 3]);
+            }
+        }
+
+        let mut mesh = Mesh::new(PrimitiveTopology::TriangleList, default());
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+        mesh.insert_indices(Indices::U32(indices));
+        mesh
+    }
+
+    fn positions(mesh: &Mesh) -> Vec<Vec3> {
+        let Some(VertexAttributeValues::Float32x3(positions)) =
+            mesh.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("mesh has no positions");
+        };
+        positions.iter().map(|&p| Vec3::from_array(p)).collect()
+    }
+
+    #[test]
+    fn test_project_onto_wall_along_x() {
+        // Stand the strip up so its surface faces -X, toward the strip's up
+        let transform = Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2));
+        let mut mesh = strip_mesh(4);
+        let config = SplineMeshProjection::new()
+            .with_project_direction(Dir3::X)
+            .with_normal_offset(0.1);
+
+        // A wall at x = 5 facing back toward the strip
+        let wall = plane_cast(Vec3::new(5.0, 0.0, 0.0), Vec3::NEG_X);
+        let aabb = project_mesh_vertices(&mut mesh, &config, &transform, wall);
+        assert!(aabb.is_some());
+
+        // Every row moves onto the wall, lifted off it along the wall's normal
+        let original = positions(&strip_mesh(4));
+        for (projected, original) in positions(&mesh).into_iter().zip(original) {
+            let original = transform.transform_point(original);
+            let projected = transform.transform_point(projected);
+            let expected = Vec3::new(4.9, original.y, original.z);
+            assert!(
+                projected.distance(expected) < 1e-4,
+                "{original} projected to {projected}"
+            );
+        }
+
+        // Nothing is hit when casting the default way, straight down
+        let mut mesh = strip_mesh(4);
+        let wall = plane_cast(Vec3::new(5.0, 0.0, 0.0), Vec3::NEG_X);
+        let config = SplineMeshProjection::new();
+        assert!(project_mesh_vertices(&mut mesh, &config, &transform, wall).is_none());
     }
 
-    let n = vertices.len() as f32;
-    Vec3::new(sum.x / n, min_y, sum.z / n)
+    #[derive(Resource, Default)]
+    struct CastCount(usize);
+
+    /// Projects queued road meshes onto the ground plane, counting rays.
+    fn project_onto_ground(
+        mut commands: Commands,
+        meshes: Res<Assets<Mesh>>,
+        roads: Query<&SplineMeshProjection, With<SplineRoad>>,
+        road_meshes: QueuedRoadMeshes,
+        mut casts: ResMut<CastCount>,
+    ) {
+        let ground = plane_cast(Vec3::ZERO, Vec3::Y);
+        let cast_ray = |_: &SplineMeshProjection, origin, direction, max| {
+            casts.0 += 1;
+            ground(origin, direction, max)
+        };
+        project_queued_road_meshes(&mut commands, &meshes, &roads, &road_meshes, cast_ray);
+    }
+
+    #[test]
+    fn test_stable_road_is_not_projected_again() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<CastCount>()
+            .add_systems(
+                Update,
+                (mark_changed_road_projections, project_onto_ground).chain(),
+            );
+
+        let road = app
+            .world_mut()
+            .spawn((
+                SplineRoad::new(Entity::PLACEHOLDER, Handle::default()),
+                SplineMeshProjection::new(),
+            ))
+            .id();
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(strip_mesh(4));
+        let road_mesh = app
+            .world_mut()
+            .spawn((
+                Mesh3d(mesh),
+                GeneratedRoadMesh { road },
+                GlobalTransform::from_xyz(0.0, 2.0, 0.0),
+                NeedsProjection,
+            ))
+            .id();
+        let casts = |app: &App| app.world().resource::<CastCount>().0;
+        let projected = |app: &App| app.world().get::<Mesh3d>(road_mesh).unwrap().0.clone();
+
+        app.update();
+        let first_casts = casts(&app);
+        assert!(first_casts > 0);
+        assert!(app.world().get::<NeedsProjection>(road_mesh).is_none());
+        let first = projected(&app);
+
+        // Nothing changed, so nothing is cast on later frames
+        app.update();
+        app.update();
+        assert_eq!(casts(&app), first_casts);
+
+        // Regenerating the same mesh reuses the cached projection
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(strip_mesh(4));
+        app.world_mut()
+            .entity_mut(road_mesh)
+            .insert((Mesh3d(mesh), NeedsProjection));
+        app.update();
+        assert_eq!(casts(&app), first_casts);
+        assert_eq!(projected(&app), first);
+        assert!(app.world().get::<NeedsProjection>(road_mesh).is_none());
+
+        // Changing the settings projects the unprojected mesh again
+        app.world_mut()
+            .get_mut::<SplineMeshProjection>(road)
+            .unwrap()
+            .normal_offset = 0.5;
+        app.update();
+        assert_eq!(casts(&app), 2 * first_casts);
+        let mesh = projected(&app);
+        let meshes = app.world().resource::<Assets<Mesh>>();
+        for position in positions(meshes.get(&mesh).unwrap()) {
+            assert!((position.y + 1.5).abs() < 1e-4, "{position}");
+        }
+    }
+
+    #[test]
+    fn test_disabling_projection_restores_mesh() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .init_resource::<CastCount>()
+            .add_systems(
+                Update,
+                (mark_changed_road_projections, project_onto_ground).chain(),
+            );
+
+        let road = app
+            .world_mut()
+            .spawn((
+                SplineRoad::new(Entity::PLACEHOLDER, Handle::default()),
+                SplineMeshProjection::new(),
+            ))
+            .id();
+        let mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(strip_mesh(4));
+        let road_mesh = app
+            .world_mut()
+            .spawn((
+                Mesh3d(mesh.clone()),
+                GeneratedRoadMesh { road },
+                GlobalTransform::from_xyz(0.0, 2.0, 0.0),
+                NeedsProjection,
+            ))
+            .id();
+        let current = |app: &App| app.world().get::<Mesh3d>(road_mesh).unwrap().0.clone();
+
+        app.update();
+        assert_ne!(current(&app), mesh);
+        assert!(app.world().get::<RoadProjectionCache>(road_mesh).is_some());
+
+        app.world_mut()
+            .get_mut::<SplineMeshProjection>(road)
+            .unwrap()
+            .enabled = false;
+        app.update();
+        assert_eq!(current(&app), mesh);
+        assert!(app.world().get::<RoadProjectionCache>(road_mesh).is_none());
+        assert!(app.world().get::<NeedsProjection>(road_mesh).is_none());
+        let aabb = app.world().get::<Aabb>(road_mesh).unwrap();
+        assert_eq!(aabb.center.y, 0.0);
+
+        // Removing the settings altogether does the same
+        app.world_mut()
+            .get_mut::<SplineMeshProjection>(road)
+            .unwrap()
+            .enabled = true;
+        app.update();
+        assert_ne!(current(&app), mesh);
+        app.world_mut()
+            .entity_mut(road)
+            .remove::<SplineMeshProjection>();
+        app.update();
+        assert_eq!(current(&app), mesh);
+        assert!(app.world().get::<RoadProjectionCache>(road_mesh).is_none());
+    }
+
+    #[cfg(feature = "mesh_raycast")]
+    #[test]
+    fn test_surface_changes_project_roads_again() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .add_message::<AssetEvent<Mesh>>()
+            .add_systems(Update, mark_roads_on_surface_changes);
+
+        let road_mesh = app
+            .world_mut()
+            .spawn(RoadProjectionCache {
+                key: Some(1),
+                source: Handle::default(),
+                projected: Handle::default(),
+            })
+            .id();
+        let surface_mesh = app
+            .world_mut()
+            .resource_mut::<Assets<Mesh>>()
+            .add(strip_mesh(4));
+        let surface = app
+            .world_mut()
+            .spawn((
+                ProjectionSurface,
+                Mesh3d(surface_mesh.clone()),
+                GlobalTransform::default(),
+            ))
+            .id();
+        let take_marked = |app: &mut App| {
+            let mut entity = app.world_mut().entity_mut(road_mesh);
+            let marked = entity.take::<NeedsProjection>().is_some();
+            let invalidated = entity.get::<RoadProjectionCache>().unwrap().key.is_none();
+            entity.get_mut::<RoadProjectionCache>().unwrap().key = Some(1);
+            assert_eq!(marked, invalidated);
+            marked
+        };
+
+        // A new surface counts as a change
+        app.update();
+        assert!(take_marked(&mut app));
+        app.update();
+        assert!(!take_marked(&mut app));
+
+        // Moving the surface
+        *app.world_mut().get_mut::<GlobalTransform>(surface).unwrap() =
+            GlobalTransform::from_xyz(0.0, 1.0, 0.0);
+        app.update();
+        assert!(take_marked(&mut app));
+
+        // Editing its mesh in place
+        app.world_mut().write_message(AssetEvent::Modified {
+            id: surface_mesh.id(),
+        });
+        app.update();
+        assert!(take_marked(&mut app));
+
+        // Removing it
+        app.world_mut()
+            .entity_mut(surface)
+            .remove::<ProjectionSurface>();
+        app.update();
+        assert!(take_marked(&mut app));
+        app.update();
+        assert!(!take_marked(&mut app));
+    }
+
+    #[test]
+    fn test_constant_offset_curve_matches_normal_offset() {
+        let transform = Transform::from_xyz(0.0, 3.0, 0.0);
+        let slope = Vec3::new(0.0, 2.0, -1.0).normalize();
+        let project = |config: &SplineMeshProjection| {
+            let mut mesh = strip_mesh(5);
+            let ground = plane_cast(Vec3::ZERO, slope);
+            project_mesh_vertices(&mut mesh, config, &transform, ground).unwrap();
+            positions(&mesh)
+        };
+
+        let curved = SplineMeshProjection::new()
+            .with_normal_offset(0.1)
+            .with_offset_curve(vec![(0.0, 0.25), (1.0, 0.25)]);
+        let bumped = SplineMeshProjection::new().with_normal_offset(0.35);
+        for (curved, bumped) in project(&curved).into_iter().zip(project(&bumped)) {
+            assert!(curved.distance(bumped) < 1e-5, "{curved} != {bumped}");
+        }
+
+        // Keys are interpolated linearly and held past the ends
+        let config = SplineMeshProjection::new().with_offset_curve(vec![(0.25, 1.0), (0.75, 2.0)]);
+        assert_eq!(config.offset_at(0.0), 1.0);
+        assert_eq!(config.offset_at(0.5), 1.5);
+        assert_eq!(config.offset_at(1.0), 2.0);
+        assert_eq!(SplineMeshProjection::new().offset_at(0.5), 0.0);
+    }
+
+    #[test]
+    fn test_zero_smoothing_window_keeps_raw_row_offsets() {
+        // Ground that steps up by one unit on every other row
+        let steps = |origin: Vec3, _: Dir3, max_distance: f32| {
+            let height = (origin.z.round() as i32).rem_euclid(2) as f32;
+            let distance = origin.y - height;
+            (distance <= max_distance).then_some((distance, Vec3::Y))
+        };
+        let project = |config: &SplineMeshProjection| {
+            let mut mesh = strip_mesh(6);
+            project_mesh_vertices(&mut mesh, config, &Transform::IDENTITY, steps).unwrap();
+            positions(&mesh)
+        };
+
+        // Every vertex sits exactly on its own row's step
+        let raw = project(&SplineMeshProjection::new().with_smoothing_window(0));
+        for position in &raw {
+            let height = (position.z.round() as i32).rem_euclid(2) as f32;
+            assert_eq!(position.y, height + 0.1, "{position}");
+        }
+
+        // The default window evens the steps out
+        let smoothed = project(&SplineMeshProjection::new());
+        assert!(smoothed
+            .iter()
+            .zip(&raw)
+            .any(|(smoothed, raw)| (smoothed.y - raw.y).abs() > 0.1));
+    }
 }
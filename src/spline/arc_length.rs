@@ -10,6 +10,54 @@ use super::Spline;
 /// Default number of samples for arc length calculations.
 pub const DEFAULT_ARC_LENGTH_SAMPLES: usize = 128;
 
+/// Number of arc length samples per spline segment used by
+/// [`Spline::default_arc_length_samples`].
+pub const ARC_LENGTH_SAMPLES_PER_SEGMENT: usize = 64;
+
+impl Spline {
+    /// Number of arc length samples appropriate for this spline.
+    ///
+    /// Scales with the segment count so long splines stay accurate and short
+    /// ones stay cheap. Followers and distributions use this unless they
+    /// override the resolution.
+    pub fn default_arc_length_samples(&self) -> usize {
+        self.segment_count().max(1) * ARC_LENGTH_SAMPLES_PER_SEGMENT
+    }
+
+    /// Get the t parameter at `distance` along the spline from its start.
+    ///
+    /// `table` must have been computed for this spline. On closed splines the
+    /// distance wraps around the loop; on open splines it is clamped to the ends.
+    pub fn t_at_distance(&self, table: &ArcLengthTable, distance: f32) -> f32 {
+        let total = table.total_length();
+        let distance = if self.closed && total > 0.0 {
+            distance.rem_euclid(total)
+        } else {
+            distance
+        };
+        table.length_to_t(distance)
+    }
+
+    /// Sample `count` points spaced evenly along the arc length.
+    ///
+    /// Unlike [`Spline::sample`], which spaces points by t and bunches them up
+    /// where the curve moves slowly, consecutive points here are (nearly) the same
+    /// distance apart. Builds an [`ArcLengthTable`] on every call; when sampling
+    /// repeatedly, keep a table and use [`ArcLengthTable::uniform_t_values`].
+    pub fn sample_uniform(&self, count: usize) -> Vec<Vec3> {
+        if !self.is_valid() {
+            return Vec::new();
+        }
+
+        let table = ArcLengthTable::compute(self, self.default_arc_length_samples());
+        table
+            .uniform_t_values(count)
+            .into_iter()
+            .filter_map(|t| self.evaluate(t))
+            .collect()
+    }
+}
+
 /// Arc length lookup table for efficient t-to-length and length-to-t conversion.
 ///
 /// The table is built by sampling the spline at regular t intervals and
@@ -115,6 +163,14 @@ impl ArcLengthTable {
         l0 + alpha * (l1 - l0)
     }
 
+    /// Get the arc length of the stretch between two t parameters.
+    ///
+    /// Both values are clamped to [0, 1]. The result is signed: negative when
+    /// `t1 < t0`, so it can be used as a directed distance along the spline.
+    pub fn length_between(&self, t0: f32, t1: f32) -> f32 {
+        self.t_to_length(t1) - self.t_to_length(t0)
+    }
+
     /// Compute t values for uniform spacing along the arc length.
     ///
     /// Returns `count` evenly-spaced t values that correspond to
@@ -140,6 +196,45 @@ impl ArcLengthTable {
             })
             .collect()
     }
+
+    /// Sample `count` points spaced uniformly along the arc length.
+    ///
+    /// Returns `(t, arc_length, position)` for each sample, where `arc_length` is the
+    /// distance from the start of the spline. Useful for rulers and tick marks.
+    /// The table must have been computed for `spline`.
+    pub fn uniform_samples(&self, spline: &Spline, count: usize) -> Vec<(f32, f32, Vec3)> {
+        self.uniform_t_values(count)
+            .into_iter()
+            .filter_map(|t| {
+                let position = spline.evaluate(t)?;
+                Some((t, self.t_to_length(t), position))
+            })
+            .collect()
+    }
+}
+
+/// Arc length table cached on a spline entity.
+///
+/// Kept up to date by [`SplineFollowPlugin`](crate::path_follow::SplineFollowPlugin)
+/// and the editor whenever the [`Spline`] changes, so followers and gizmos sharing
+/// a spline don't each recompute its length every frame.
+#[derive(Component, Debug, Clone)]
+pub struct CachedArcLength {
+    /// The table, computed in the spline's local space.
+    pub table: ArcLengthTable,
+    /// Number of samples the table was computed with.
+    pub samples: usize,
+}
+
+impl CachedArcLength {
+    /// Compute the cache for a spline at its default resolution.
+    pub fn compute(spline: &Spline) -> Self {
+        let samples = spline.default_arc_length_samples();
+        Self {
+            table: ArcLengthTable::compute(spline, samples),
+            samples,
+        }
+    }
 }
 
 /// Approximate the total arc length of a spline without building a table.
@@ -191,4 +286,102 @@ mod tests {
         // total length should give t=1
         assert!((table.length_to_t(table.total_length()) - 1.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_uniform_samples_are_evenly_spaced() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(4.0, 0.0, 0.0),
+                Vec3::new(5.0, 0.0, 0.0),
+            ],
+        );
+
+        let table = ArcLengthTable::compute(&spline, 256);
+        let samples = table.uniform_samples(&spline, 5);
+        assert_eq!(samples.len(), 5);
+
+        let step = table.total_length() / 4.0;
+        for (i, &(t, length, position)) in samples.iter().enumerate() {
+            assert!((length - step * i as f32).abs() < 0.01);
+            assert_eq!(spline.evaluate(t), Some(position));
+            // The spline runs along X from x = 1, so distance maps to x directly
+            assert!((position.x - 1.0 - length).abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn test_length_between() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 2.0, 0.0),
+                Vec3::new(3.0, -1.0, 1.0),
+                Vec3::new(4.0, 0.0, 0.0),
+            ],
+        );
+
+        let table = ArcLengthTable::compute(&spline, 100);
+
+        assert!((table.length_between(0.0, 1.0) - table.total_length()).abs() < 0.01);
+        assert!((table.length_between(-1.0, 2.0) - table.total_length()).abs() < 0.01);
+
+        // Reversed bounds give the negated length
+        let forward = table.length_between(0.2, 0.6);
+        assert!(forward > 0.0);
+        assert!((table.length_between(0.6, 0.2) + forward).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_sample_uniform_spacing() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(1.5, 2.0, 0.0),
+                Vec3::new(6.0, 2.0, 1.0),
+                Vec3::new(7.0, 0.0, 1.0),
+            ],
+        );
+
+        let points = spline.sample_uniform(20);
+        assert_eq!(points.len(), 20);
+        assert_eq!(points.first().copied(), spline.evaluate(0.0));
+        assert_eq!(points.last().copied(), spline.evaluate(1.0));
+
+        let gaps: Vec<f32> = points.windows(2).map(|w| w[0].distance(w[1])).collect();
+        let mean = gaps.iter().sum::<f32>() / gaps.len() as f32;
+        for gap in gaps {
+            assert!((gap - mean).abs() < mean * 0.02);
+        }
+
+        assert!(Spline::new(SplineType::CatmullRom, vec![Vec3::ZERO])
+            .sample_uniform(4)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_t_at_distance() {
+        // Straight line from x = 1 to x = 4 with t proportional to x
+        let mut spline = Spline::new(
+            SplineType::CatmullRom,
+            (0..6).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+        );
+        let table = ArcLengthTable::compute(&spline, 256);
+
+        assert!((spline.t_at_distance(&table, 1.5) - 0.5).abs() < 1e-3);
+        assert_eq!(spline.t_at_distance(&table, 10.0), 1.0);
+        assert_eq!(spline.t_at_distance(&table, -1.0), 0.0);
+
+        // Closed splines wrap around
+        spline.closed = true;
+        let table = ArcLengthTable::compute(&spline, 256);
+        let total = table.total_length();
+        let wrapped = spline.t_at_distance(&table, total + 1.0);
+        assert!((wrapped - spline.t_at_distance(&table, 1.0)).abs() < 1e-4);
+    }
 }
@@ -0,0 +1,145 @@
+//! Structural change notifications for splines.
+
+use bevy::prelude::*;
+
+use super::Spline;
+
+/// What kind of edit was made to a spline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SplineChangeKind {
+    /// The interpolation type or its shape parameters changed.
+    TypeChanged,
+    /// The spline was opened or closed.
+    ClosedToggled,
+    /// Control points were added.
+    PointsAdded,
+    /// Control points were removed.
+    PointsRemoved,
+    /// Existing control points moved without the point count changing.
+    PointsMoved,
+    /// Point pin flags changed.
+    PinnedChanged,
+}
+
+/// Message emitted when a [`Spline`] is modified.
+///
+/// Every change to a spline component is picked up, whether it comes from the
+/// editor's dragging, hotkeys, numeric entry and undo or from user code. A single
+/// edit can emit several messages, one per [`SplineChangeKind`] that applies.
+/// Newly spawned splines, including the editor's duplicates, aren't reported; use
+/// `Added<Spline>` for those.
+#[derive(Message, Debug, Clone)]
+pub struct SplineChanged {
+    /// The spline entity.
+    pub entity: Entity,
+    /// What changed.
+    pub kind: SplineChangeKind,
+}
+
+/// Copy of a spline's last seen state, used to diff against on change.
+#[derive(Component)]
+pub(crate) struct SplineSnapshot(Spline);
+
+/// Compare two versions of a spline and list the kinds of change between them.
+pub fn spline_changes(old: &Spline, new: &Spline) -> Vec<SplineChangeKind> {
+    let mut changes = Vec::new();
+
+    if old.basis() != new.basis() {
+        changes.push(SplineChangeKind::TypeChanged);
+    }
+    if old.closed != new.closed {
+        changes.push(SplineChangeKind::ClosedToggled);
+    }
+
+    let (old_len, new_len) = (old.control_points.len(), new.control_points.len());
+    if new_len > old_len {
+        changes.push(SplineChangeKind::PointsAdded);
+    } else if new_len < old_len {
+        changes.push(SplineChangeKind::PointsRemoved);
+    } else if old.control_points != new.control_points {
+        changes.push(SplineChangeKind::PointsMoved);
+    }
+
+    // Missing pin entries are treated as unpinned
+    let pinned = |spline: &Spline, i: usize| spline.pinned.get(i).copied().unwrap_or(false);
+    if (0..old_len.max(new_len)).any(|i| pinned(old, i) != pinned(new, i)) {
+        changes.push(SplineChangeKind::PinnedChanged);
+    }
+
+    changes
+}
+
+/// Diff changed splines against their snapshots and emit [`SplineChanged`] messages.
+pub(crate) fn detect_spline_changes(
+    mut commands: Commands,
+    mut splines: Query<(Entity, &Spline, Option<&mut SplineSnapshot>), Changed<Spline>>,
+    mut changed: MessageWriter<SplineChanged>,
+) {
+    for (entity, spline, snapshot) in &mut splines {
+        let Some(mut snapshot) = snapshot else {
+            // Newly added spline: record its initial state
+            commands
+                .entity(entity)
+                .insert(SplineSnapshot(spline.clone()));
+            continue;
+        };
+
+        for kind in spline_changes(&snapshot.0, spline) {
+            changed.write(SplineChanged { entity, kind });
+        }
+        snapshot.0.clone_from(spline);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_spline_changes_diff() {
+        let points = vec![Vec3::ZERO, Vec3::X, Vec3::new(2.0, 1.0, 0.0), Vec3::Z];
+        let spline = Spline::new(SplineType::CatmullRom, points);
+
+        assert!(spline_changes(&spline, &spline).is_empty());
+
+        let mut moved = spline.clone();
+        moved.control_points[1].y += 1.0;
+        assert_eq!(
+            spline_changes(&spline, &moved),
+            [SplineChangeKind::PointsMoved]
+        );
+
+        let mut added = spline.clone();
+        added.add_point(Vec3::ONE);
+        assert_eq!(
+            spline_changes(&spline, &added),
+            [SplineChangeKind::PointsAdded]
+        );
+        assert_eq!(
+            spline_changes(&added, &spline),
+            [SplineChangeKind::PointsRemoved]
+        );
+
+        let mut retyped = spline.clone();
+        retyped.spline_type = SplineType::BSpline;
+        retyped.closed = true;
+        assert_eq!(
+            spline_changes(&spline, &retyped),
+            [
+                SplineChangeKind::TypeChanged,
+                SplineChangeKind::ClosedToggled
+            ]
+        );
+
+        // An explicit `false` pin entry is the same as a missing one
+        let mut pinned = spline.clone();
+        pinned.pinned = vec![false, true];
+        assert_eq!(
+            spline_changes(&spline, &pinned),
+            [SplineChangeKind::PinnedChanged]
+        );
+        pinned.pinned = vec![false];
+        assert!(spline_changes(&spline, &pinned).is_empty());
+    }
+}
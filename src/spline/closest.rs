@@ -0,0 +1,165 @@
+//! Closest-point queries on splines.
+
+use bevy::prelude::*;
+
+use super::Spline;
+
+/// Bisection refinement iterations after the coarse sample pass.
+const CLOSEST_POINT_REFINE_ITERATIONS: usize = 24;
+
+impl Spline {
+    /// Find the point on the spline closest to `target`.
+    ///
+    /// `target` is in the spline's local space. The curve is first sampled at
+    /// `samples` evenly spaced t values, then the best sample is refined by
+    /// bisecting on the tangent direction. On closed splines the search wraps
+    /// around t = 0.
+    ///
+    /// Returns `(t, position, distance_squared)`, or `None` if the spline is invalid.
+    pub fn closest_point(&self, target: Vec3, samples: usize) -> Option<(f32, Vec3, f32)> {
+        if !self.is_valid() || self.segment_count() == 0 {
+            return None;
+        }
+
+        // Closed splines wrap, so t outside 0.0 - 1.0 is folded back in
+        let wrap = |t: f32| {
+            if self.closed {
+                t.rem_euclid(1.0)
+            } else {
+                t.clamp(0.0, 1.0)
+            }
+        };
+        let distance = |t: f32| {
+            self.evaluate(wrap(t))
+                .map_or(f32::INFINITY, |p| p.distance_squared(target))
+        };
+
+        // Coarse pass; t = 1.0 is the same point as t = 0.0 on closed splines
+        let samples = samples.max(2);
+        let step = 1.0 / samples as f32;
+        let last = if self.closed { samples - 1 } else { samples };
+        let best_t = (0..=last)
+            .map(|i| i as f32 * step)
+            .min_by(|a, b| distance(*a).total_cmp(&distance(*b)))?;
+
+        // Bisect on the slope of the distance, (p - target) · p', within one sample
+        // step either side. The slope is far better conditioned than the distance
+        // itself when the target is far from the curve.
+        let (mut lo, mut hi) = if self.closed {
+            (best_t - step, best_t + step)
+        } else {
+            ((best_t - step).max(0.0), (best_t + step).min(1.0))
+        };
+        let slope = |t: f32| {
+            self.evaluate_pos_tan(wrap(t))
+                .map_or(0.0, |(p, tangent)| (p - target).dot(tangent))
+        };
+        let t = if !self.closed && slope(lo) >= 0.0 {
+            // The distance only grows from the start of the bracket
+            lo
+        } else if !self.closed && slope(hi) <= 0.0 {
+            hi
+        } else {
+            for _ in 0..CLOSEST_POINT_REFINE_ITERATIONS {
+                let mid = (lo + hi) * 0.5;
+                if slope(mid) < 0.0 {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            wrap((lo + hi) * 0.5)
+        };
+        // The bracket can hold more than one dip; never do worse than the coarse sample
+        let t = if distance(t) <= distance(best_t) {
+            t
+        } else {
+            wrap(best_t)
+        };
+        let position = self.evaluate(t)?;
+        Some((t, position, position.distance_squared(target)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_closest_point_on_straight_line() {
+        // Catmull-Rom through evenly spaced collinear points is a straight line
+        // from x = 2 to x = 6 with t proportional to x
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            (0..5)
+                .map(|i| Vec3::new(i as f32 * 2.0, 0.0, 0.0))
+                .collect(),
+        );
+
+        for x in [2.5, 3.0, 4.7] {
+            let (t, position, distance_squared) =
+                spline.closest_point(Vec3::new(x, 3.0, 4.0), 16).unwrap();
+            assert!((t - (x - 2.0) / 4.0).abs() < 1e-3);
+            assert!(position.distance(Vec3::new(x, 0.0, 0.0)) < 1e-3);
+            assert!((distance_squared - 25.0).abs() < 1e-2);
+        }
+
+        // Points past the ends clamp to the ends
+        let (t, position, _) = spline.closest_point(Vec3::new(-5.0, 0.0, 0.0), 16).unwrap();
+        assert_eq!(t, 0.0);
+        assert!(position.distance(Vec3::new(2.0, 0.0, 0.0)) < 1e-5);
+        assert_eq!(
+            spline
+                .closest_point(Vec3::new(20.0, 0.0, 0.0), 16)
+                .unwrap()
+                .0,
+            1.0
+        );
+
+        assert!(Spline::new(SplineType::CatmullRom, vec![Vec3::ZERO])
+            .closest_point(Vec3::ZERO, 16)
+            .is_none());
+    }
+
+    #[test]
+    fn test_closest_point_wraps_on_closed_splines() {
+        let spline = Spline::closed(
+            SplineType::BSpline,
+            vec![
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, 1.0),
+                Vec3::new(-1.0, 0.0, 0.0),
+                Vec3::new(0.0, 0.0, -1.0),
+            ],
+        );
+
+        // The start of the loop is reachable from either side of t = 0
+        let start = spline.evaluate(0.0).unwrap();
+        let (t, position, _) = spline.closest_point(start * 2.0, 8).unwrap();
+        assert!(t < 1e-3 || t > 1.0 - 1e-3);
+        assert!(position.distance(start) < 1e-3);
+    }
+
+    #[test]
+    fn test_closest_point_never_worse_than_coarse_sample() {
+        // Starts 1 away from the target heading away from it, then swings back
+        // past it around t = 0.5
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            vec![
+                Vec3::ZERO,
+                Vec3::X,
+                Vec3::new(3.0, 0.0, 0.0),
+                Vec3::new(-3.0, 0.0, 0.2),
+                Vec3::new(-3.0, 0.0, 3.0),
+                Vec3::new(-3.0, 0.0, 4.0),
+            ],
+        );
+
+        let coarse = spline.evaluate(0.5).unwrap().length_squared();
+        assert!(coarse < 0.1);
+        let (t, _, distance_squared) = spline.closest_point(Vec3::ZERO, 2).unwrap();
+        assert!(distance_squared <= coarse, "t = {t}: {distance_squared} > {coarse}");
+    }
+}
@@ -0,0 +1,230 @@
+//! Continuity analysis at spline segment joins.
+
+use bevy::prelude::*;
+
+use super::{Spline, SplineType};
+
+/// Relative tolerance used when comparing values across a segment join.
+const CONTINUITY_EPSILON: f32 = 1e-4;
+
+/// The highest order of parametric continuity held at a point on a spline.
+///
+/// Levels are ordered, so `level < ContinuityLevel::C2` means the join is not
+/// fully smooth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Reflect)]
+pub enum ContinuityLevel {
+    /// Positions differ: the curve has a gap.
+    Discontinuous,
+    /// Positions match, but tangents differ (a visible kink).
+    C0,
+    /// Positions and tangents match, but curvature jumps.
+    C1,
+    /// Positions, tangents and curvature all match.
+    C2,
+}
+
+impl ContinuityLevel {
+    /// Get the display name for this continuity level.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Discontinuous => "Discontinuous",
+            Self::C0 => "C0",
+            Self::C1 => "C1",
+            Self::C2 => "C2",
+        }
+    }
+}
+
+/// How moving a Bézier handle affects the paired handle across its anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HandleMirror {
+    /// The paired handle stays put (a broken handle).
+    #[default]
+    Free,
+    /// The paired handle turns to stay collinear, keeping its own length (G1).
+    Aligned,
+    /// The paired handle mirrors both direction and length (C1).
+    Mirrored,
+}
+
+impl Spline {
+    /// Get the anchor and the opposite handle paired with the Bézier handle at `index`.
+    ///
+    /// Returns `None` for anchors, handles at either end of the spline, and
+    /// splines that aren't [`SplineType::CubicBezier`]. On closed splines whose
+    /// closing segment has its own handles, the first and last handle pair up
+    /// around the first anchor.
+    pub fn bezier_handle_pair(&self, index: usize) -> Option<(usize, usize)> {
+        if self.spline_type != SplineType::CubicBezier {
+            return None;
+        }
+        let n = self.control_points.len();
+        let wraps = self.closed && n.is_multiple_of(3);
+        // Anchors sit at multiples of 3, with their handles on either side
+        let (anchor, opposite) = match index % 3 {
+            1 if index == 1 && wraps => (0, n - 1),
+            1 => (index.checked_sub(1)?, index.checked_sub(2)?),
+            2 if index == n - 1 && wraps => (0, 1),
+            2 => (index + 1, index + 2),
+            _ => return None,
+        };
+        (opposite < n).then_some((anchor, opposite))
+    }
+
+    /// Move the handle paired with the Bézier handle at `index` according to `mode`,
+    /// keeping the join smooth after the handle was moved.
+    pub fn mirror_bezier_handle(&mut self, index: usize, mode: HandleMirror) {
+        let Some((anchor, opposite)) = self.bezier_handle_pair(index) else {
+            return;
+        };
+        let anchor_pos = self.control_points[anchor];
+        let offset = self.control_points[index] - anchor_pos;
+        self.control_points[opposite] = match mode {
+            HandleMirror::Free => return,
+            HandleMirror::Aligned => {
+                let Some(direction) = offset.try_normalize() else {
+                    return;
+                };
+                let length = self.control_points[opposite].distance(anchor_pos);
+                anchor_pos - direction * length
+            }
+            HandleMirror::Mirrored => anchor_pos - offset,
+        };
+    }
+
+    /// Find the segment joins where the spline is less than C2 continuous.
+    ///
+    /// Returns the curve parameter `t` of each join together with the highest
+    /// continuity level that still holds there. Continuity is parametric: a Bézier
+    /// join with collinear handles of unequal length is reported as C0.
+    pub fn continuity_breaks(&self) -> Vec<(f32, ContinuityLevel)> {
+        let num_segments = self.segment_count();
+        if num_segments == 0 {
+            return Vec::new();
+        }
+
+        let num_joins = if self.closed { num_segments } else { num_segments - 1 };
+
+        (0..num_joins)
+            .filter_map(|i| {
+                let next = (i + 1) % num_segments;
+                let level = self.continuity_at_join(i, next)?;
+                let t = if next == 0 {
+                    0.0
+                } else {
+                    next as f32 / num_segments as f32
+                };
+                (level < ContinuityLevel::C2).then_some((t, level))
+            })
+            .collect()
+    }
+
+    /// Continuity between the end of segment `before` and the start of segment `after`.
+    fn continuity_at_join(&self, before: usize, after: usize) -> Option<ContinuityLevel> {
+        let end = self.basis().evaluate_segment_derivatives(
+            &self.control_points,
+            before,
+            1.0,
+            self.closed,
+        )?;
+        let start = self.basis().evaluate_segment_derivatives(
+            &self.control_points,
+            after,
+            0.0,
+            self.closed,
+        )?;
+
+        let levels = [
+            ContinuityLevel::Discontinuous,
+            ContinuityLevel::C0,
+            ContinuityLevel::C1,
+        ];
+        for (order, level) in levels.into_iter().enumerate() {
+            if !nearly_equal(end[order], start[order]) {
+                return Some(level);
+            }
+        }
+        Some(ContinuityLevel::C2)
+    }
+}
+
+fn nearly_equal(a: Vec3, b: Vec3) -> bool {
+    let scale = a.length().max(b.length()).max(1.0);
+    a.distance(b) <= CONTINUITY_EPSILON * scale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_continuity_breaks_by_spline_type() {
+        // Bézier with a kink at the shared anchor
+        let bezier = Spline::new(
+            SplineType::CubicBezier,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(2.0, 0.0, 0.0),
+                Vec3::new(3.0, 0.0, 0.0),
+                Vec3::new(3.0, 1.0, 0.0),
+                Vec3::new(3.0, 2.0, 0.0),
+                Vec3::new(3.0, 3.0, 0.0),
+            ],
+        );
+        assert_eq!(bezier.continuity_breaks(), vec![(0.5, ContinuityLevel::C0)]);
+
+        let points = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 2.0, 0.0),
+            Vec3::new(3.0, -1.0, 1.0),
+            Vec3::new(4.0, 0.5, -2.0),
+            Vec3::new(6.0, 1.0, 0.0),
+        ];
+
+        // Uniform Catmull-Rom is C1 but not C2
+        let catmull_rom = Spline::new(SplineType::CatmullRom, points.clone());
+        assert_eq!(
+            catmull_rom.continuity_breaks(),
+            vec![(0.5, ContinuityLevel::C1)]
+        );
+
+        // Uniform B-splines are C2 everywhere, including the closing join
+        let bspline = Spline::closed(SplineType::BSpline, points);
+        assert!(bspline.continuity_breaks().is_empty());
+    }
+
+    #[test]
+    fn test_mirror_bezier_handle() {
+        let mut spline = Spline::new(
+            SplineType::CubicBezier,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 1.0, 0.0),
+                Vec3::new(2.0, 1.0, 0.0),
+                Vec3::new(3.0, 0.0, 0.0),
+                Vec3::new(4.0, 0.0, 0.0),
+                Vec3::new(5.0, 1.0, 0.0),
+                Vec3::new(6.0, 0.0, 0.0),
+            ],
+        );
+        assert_eq!(spline.bezier_handle_pair(2), Some((3, 4)));
+        assert_eq!(spline.bezier_handle_pair(4), Some((3, 2)));
+        assert_eq!(spline.bezier_handle_pair(1), None);
+        assert_eq!(spline.bezier_handle_pair(3), None);
+
+        // Drag the incoming handle of the middle anchor
+        spline.control_points[2] = Vec3::new(3.0, 2.0, 0.0);
+
+        let mut aligned = spline.clone();
+        aligned.mirror_bezier_handle(2, HandleMirror::Aligned);
+        assert!(aligned.control_points[4].distance(Vec3::new(3.0, -1.0, 0.0)) < 1e-6);
+
+        spline.mirror_bezier_handle(2, HandleMirror::Mirrored);
+        assert!(spline.control_points[4].distance(Vec3::new(3.0, -2.0, 0.0)) < 1e-6);
+        assert!(spline
+            .continuity_breaks()
+            .iter()
+            .all(|&(_, level)| level == ContinuityLevel::C1));
+    }
+}
@@ -0,0 +1,66 @@
+//! Curvature of splines.
+
+use super::Spline;
+
+/// Speeds below this are treated as a stationary point with undefined curvature.
+const MIN_CURVATURE_SPEED: f32 = 1e-6;
+
+impl Spline {
+    /// Get the unsigned curvature at parameter t, computed as `|r' × r''| / |r'|³`.
+    ///
+    /// Curvature is the reciprocal of the radius of the osculating circle and does
+    /// not depend on the parameterization. Returns `None` if the spline can't be
+    /// evaluated or the curve is stationary at t (zero-length tangent), where the
+    /// curvature is undefined.
+    pub fn curvature_at(&self, t: f32) -> Option<f32> {
+        let velocity = self.evaluate_tangent(t)?;
+        let acceleration = self.evaluate_second_derivative(t)?;
+
+        let speed = velocity.length();
+        if speed < MIN_CURVATURE_SPEED {
+            return None;
+        }
+        Some(velocity.cross(acceleration).length() / (speed * speed * speed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::spline::{Spline, SplineType};
+
+    #[test]
+    fn test_curvature_of_circular_bezier() {
+        // Quarter circle of radius 5 approximated by a single cubic Bézier
+        let radius = 5.0;
+        let handle = radius * 0.552_284_8;
+        let spline = Spline::new(
+            SplineType::CubicBezier,
+            vec![
+                Vec3::new(radius, 0.0, 0.0),
+                Vec3::new(radius, handle, 0.0),
+                Vec3::new(handle, radius, 0.0),
+                Vec3::new(0.0, radius, 0.0),
+            ],
+        );
+
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            let curvature = spline.curvature_at(t).unwrap();
+            // The approximation's curvature deviates by up to ~2% at the ends
+            assert!((curvature - 1.0 / radius).abs() < 0.03 / radius);
+        }
+
+        // Straight lines have no curvature
+        let line = Spline::new(
+            SplineType::CatmullRom,
+            (0..4).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+        );
+        assert!(line.curvature_at(0.5).unwrap().abs() < 1e-6);
+
+        // Coincident points give a stationary curve
+        let point = Spline::new(SplineType::BSpline, vec![Vec3::ONE; 4]);
+        assert_eq!(point.curvature_at(0.5), None);
+    }
+}
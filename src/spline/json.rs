@@ -0,0 +1,90 @@
+//! JSON import and export of splines, independent of Bevy's scene format.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{Spline, SplineType};
+
+/// The JSON form of a [`Spline`]: its type, points as `[x, y, z]` arrays and
+/// whether it's closed.
+#[derive(Serialize, Deserialize)]
+struct SplineJson {
+    spline_type: SplineType,
+    control_points: Vec<[f32; 3]>,
+    #[serde(default)]
+    closed: bool,
+}
+
+impl Spline {
+    /// Serialize the spline to JSON for exchange with external tools.
+    ///
+    /// Only the type, control points and closed flag are written, e.g.
+    /// `{"spline_type":"CatmullRom","control_points":[[0.0,0.0,0.0],...],"closed":false}`.
+    /// Shape parameters, pins and timestamps are left out.
+    pub fn to_json(&self) -> String {
+        let json = SplineJson {
+            spline_type: self.spline_type,
+            control_points: self.control_points.iter().map(|p| p.to_array()).collect(),
+            closed: self.closed,
+        };
+        serde_json::to_string(&json).expect("spline JSON has no non-string keys")
+    }
+
+    /// Parse a spline written by [`Spline::to_json`] or an external tool.
+    ///
+    /// `closed` may be omitted and defaults to `false`; everything not in the JSON
+    /// form gets its default value.
+    pub fn from_json(json: &str) -> Result<Spline, serde_json::Error> {
+        let json: SplineJson = serde_json::from_str(json)?;
+        let control_points = json.control_points.into_iter().map(Vec3::from).collect();
+        Ok(if json.closed {
+            Spline::closed(json.spline_type, control_points)
+        } else {
+            Spline::new(json.spline_type, control_points)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::spline::{Spline, SplineType};
+
+    #[test]
+    fn test_json_round_trip() {
+        let points = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.5, 2.0, -0.25),
+            Vec3::new(3.0, 2.0, 1.0),
+            Vec3::new(4.0, 0.0, 0.0),
+        ];
+        for spline_type in [
+            SplineType::CubicBezier,
+            SplineType::CatmullRom,
+            SplineType::BSpline,
+            SplineType::Tcb,
+        ] {
+            for spline in [
+                Spline::new(spline_type, points.clone()),
+                Spline::closed(spline_type, points.clone()),
+            ] {
+                let parsed = Spline::from_json(&spline.to_json()).unwrap();
+                assert_eq!(parsed.spline_type, spline.spline_type);
+                assert_eq!(parsed.control_points, spline.control_points);
+                assert_eq!(parsed.closed, spline.closed);
+            }
+        }
+
+        // Hand-written JSON without the closed flag
+        let parsed = Spline::from_json(
+            r#"{"spline_type": "BSpline", "control_points": [[0, 0, 0], [1, 2, 3]]}"#,
+        )
+        .unwrap();
+        assert_eq!(parsed.spline_type, SplineType::BSpline);
+        assert_eq!(parsed.control_points[1], Vec3::new(1.0, 2.0, 3.0));
+        assert!(!parsed.closed);
+
+        assert!(Spline::from_json(r#"{"spline_type": "Nurbs", "control_points": []}"#).is_err());
+    }
+}
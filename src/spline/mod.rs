@@ -1,28 +1,61 @@
 mod arc_length;
+mod changes;
+mod closest;
 mod components;
+mod continuity;
+mod curvature;
+#[cfg(feature = "json")]
+mod json;
+mod offset;
+mod picking;
 mod projection;
+mod simplify;
+mod spline_2d;
+mod timestamps;
 mod types;
+mod validate;
 
-pub use arc_length::{approximate_arc_length, ArcLengthTable, DEFAULT_ARC_LENGTH_SAMPLES};
+pub use arc_length::{
+    approximate_arc_length, ArcLengthTable, CachedArcLength, ARC_LENGTH_SAMPLES_PER_SEGMENT,
+    DEFAULT_ARC_LENGTH_SAMPLES,
+};
+pub use changes::{spline_changes, SplineChangeKind, SplineChanged};
 pub use components::*;
+pub use continuity::{ContinuityLevel, HandleMirror};
+pub use picking::{pick_point, pick_polyline, pick_polyline_screen};
 pub use projection::{
-    get_effective_control_points, get_effective_curve_points, project_spline_point,
-    ProjectedSplineCache, SplineProjectionConfig,
+    get_effective_control_points, get_effective_curve_points, ProjectedSplineCache,
+    SplineProjectionConfig,
 };
+#[cfg(feature = "avian3d")]
+pub use projection::{project_spline, project_spline_point};
+pub use spline_2d::Spline2d;
 pub use types::*;
+pub use validate::SplineError;
 
 use bevy::prelude::*;
 
-/// Plugin that registers spline types for reflection/serialization.
+/// Plugin that registers spline types for reflection/serialization and emits
+/// [`SplineChanged`] messages when splines are modified. Bézier splines whose
+/// control point count doesn't fit [`Spline::validate`] are reported with a warning.
 /// This plugin does NOT include editor functionality - use `SplineEditorPlugin` for that.
 pub struct SplinePlugin;
 
 impl Plugin for SplinePlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<SplineType>()
+            .register_type::<ContinuityLevel>()
+            .register_type::<TcbParams>()
             .register_type::<Spline>()
+            .register_type::<Spline2d>()
             .register_type::<SelectedSpline>()
+            .register_type::<SplinePreview>()
             .register_type::<ControlPointMarker>()
-            .register_type::<SelectedControlPoint>();
+            .register_type::<SelectedControlPoint>()
+            .add_message::<SplineChanged>()
+            .add_systems(
+                PostUpdate,
+                (changes::detect_spline_changes, validate::warn_malformed_splines),
+            );
     }
 }
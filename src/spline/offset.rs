@@ -0,0 +1,63 @@
+//! Curves offset to the side of splines.
+
+use bevy::prelude::*;
+
+use super::Spline;
+use crate::geometry::CoordinateFrame;
+
+impl Spline {
+    /// Sample a curve running `distance` to the side of the spline.
+    ///
+    /// The spline is sampled at `samples` evenly spaced t values (at least two, from
+    /// t = 0.0 to 1.0, as in [`Spline::sample_with_tangents`]), and each sample is
+    /// moved along the right axis of a [`CoordinateFrame`] built from the tangent and
+    /// `up`, so positive distances offset to the right when looking along the
+    /// spline. Useful for lane dividers and road edges.
+    ///
+    /// This is an approximation: the true offset of a cubic curve isn't a cubic,
+    /// and offsets larger than the radius of curvature fold back on themselves on
+    /// the inside of bends. Returns an empty list if the spline is invalid.
+    pub fn offset_points(&self, distance: f32, up: Vec3, samples: usize) -> Vec<Vec3> {
+        if !self.is_valid() || self.segment_count() == 0 {
+            return Vec::new();
+        }
+
+        let last = samples.max(2) - 1;
+        (0..=last)
+            .filter_map(|i| self.evaluate_pos_tan(i as f32 / last as f32))
+            .map(|(position, tangent)| {
+                let frame = CoordinateFrame::from_tangent_with_up(tangent, up);
+                position + frame.right * distance
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::spline::{Spline, SplineType};
+
+    #[test]
+    fn test_offset_of_straight_spline_is_parallel() {
+        let spline = Spline::new(
+            SplineType::CatmullRom,
+            [0.0, 1.0, 2.0, 3.0, 4.0]
+                .map(|z| Vec3::new(0.0, 0.0, -z))
+                .to_vec(),
+        );
+
+        // Looking down -Z with Y up, right is +X
+        let offset = spline.offset_points(1.5, Vec3::Y, 11);
+        assert_eq!(offset.len(), 11);
+        for (i, point) in offset.iter().enumerate() {
+            let expected = Vec3::new(1.5, 0.0, -1.0 - 2.0 * i as f32 / 10.0);
+            assert!(point.distance(expected) < 1e-5);
+        }
+
+        // Negative distances offset to the left
+        let left = spline.offset_points(-2.0, Vec3::Y, 5);
+        assert!(left[2].distance(Vec3::new(-2.0, 0.0, -2.0)) < 1e-5);
+    }
+}
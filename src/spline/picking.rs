@@ -0,0 +1,268 @@
+//! Ray picking against control points and spline curves.
+//!
+//! These helpers work on plain point slices so they can be used with local,
+//! world-space, or projected points alike. [`Spline::pick_control_point`] and
+//! [`Spline::pick_curve`] wrap them for the common case of picking in the
+//! spline's local space.
+
+use bevy::prelude::*;
+
+use super::Spline;
+
+/// Number of samples per segment used when picking against the curve body.
+const CURVE_PICK_SAMPLES_PER_SEGMENT: usize = 32;
+
+/// Pick the point closest along the ray whose sphere of `radius` the ray hits.
+///
+/// Returns `(index, ray_distance)` of the picked point.
+pub fn pick_point(
+    points: &[Vec3],
+    ray_origin: Vec3,
+    ray_direction: Vec3,
+    radius: f32,
+) -> Option<(usize, f32)> {
+    let direction = ray_direction.normalize_or_zero();
+    if direction == Vec3::ZERO {
+        return None;
+    }
+
+    let mut closest: Option<(usize, f32)> = None;
+    for (i, &point) in points.iter().enumerate() {
+        if let Some(dist) = ray_sphere_intersect(ray_origin, direction, point, radius) {
+            if closest.is_none_or(|(_, closest_dist)| dist < closest_dist) {
+                closest = Some((i, dist));
+            }
+        }
+    }
+    closest
+}
+
+/// Pick the polyline segment closest along the ray that passes within `max_distance`.
+///
+/// Returns `(fraction, ray_distance)` where `fraction` is the position along the
+/// polyline in index space, normalized to 0.0 - 1.0.
+pub fn pick_polyline(
+    points: &[Vec3],
+    ray_origin: Vec3,
+    ray_direction: Vec3,
+    max_distance: f32,
+) -> Option<(f32, f32)> {
+    let direction = ray_direction.normalize_or_zero();
+    if direction == Vec3::ZERO || points.len() < 2 {
+        return None;
+    }
+
+    closest_segment(points.len() - 1, max_distance, |i| {
+        ray_segment_distance(ray_origin, direction, points[i], points[i + 1])
+    })
+}
+
+/// Pick the screen-space polyline segment closest to `cursor`, if within `max_distance`.
+///
+/// `points` are viewport positions, e.g. from [`Camera::world_to_viewport`];
+/// `None` entries (points that couldn't be projected, such as those behind the
+/// camera) break the polyline. Returns `(fraction, distance)` where `fraction` is
+/// the position along the polyline in index space, normalized to 0.0 - 1.0, and
+/// `distance` is in the same units as the points.
+pub fn pick_polyline_screen(
+    points: &[Option<Vec2>],
+    cursor: Vec2,
+    max_distance: f32,
+) -> Option<(f32, f32)> {
+    if points.len() < 2 {
+        return None;
+    }
+
+    closest_segment(points.len() - 1, max_distance, |i| {
+        let (Some(a), Some(b)) = (points[i], points[i + 1]) else {
+            return None;
+        };
+        let segment = b - a;
+        let len_sq = segment.length_squared();
+        let s = if len_sq > 1e-8 {
+            ((cursor - a).dot(segment) / len_sq).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let distance = cursor.distance(a + segment * s);
+        Some((distance, distance, s))
+    })
+}
+
+impl Spline {
+    /// Pick the control point hit by a ray, treating each point as a sphere of `radius`.
+    ///
+    /// The ray is in the spline's local space. Returns the index of the hit point
+    /// closest to the ray origin.
+    pub fn pick_control_point(
+        &self,
+        ray_origin: Vec3,
+        ray_direction: Vec3,
+        radius: f32,
+    ) -> Option<usize> {
+        pick_point(&self.control_points, ray_origin, ray_direction, radius).map(|(i, _)| i)
+    }
+
+    /// Pick the curve body with a ray passing within `max_distance` of it.
+    ///
+    /// The ray is in the spline's local space. Returns the curve parameter `t`
+    /// of the hit closest to the ray origin.
+    pub fn pick_curve(
+        &self,
+        ray_origin: Vec3,
+        ray_direction: Vec3,
+        max_distance: f32,
+    ) -> Option<f32> {
+        if !self.is_valid() {
+            return None;
+        }
+        // `sample` is uniform in t, so the polyline fraction maps directly to t
+        let points = self.sample(CURVE_PICK_SAMPLES_PER_SEGMENT);
+        pick_polyline(&points, ray_origin, ray_direction, max_distance).map(|(t, _)| t)
+    }
+}
+
+/// Find the polyline segment with the lowest rank among those within `max_distance`.
+///
+/// `measure` returns `(distance, rank, s)` for a segment, where `s` is the position
+/// of the closest point along it (0.0 - 1.0), or `None` to skip it. Returns
+/// `(fraction, rank)` where `fraction` is the position along the polyline in index
+/// space, normalized to 0.0 - 1.0.
+fn closest_segment(
+    segment_count: usize,
+    max_distance: f32,
+    mut measure: impl FnMut(usize) -> Option<(f32, f32, f32)>,
+) -> Option<(f32, f32)> {
+    let mut closest: Option<(f32, f32)> = None;
+    for i in 0..segment_count {
+        let Some((distance, rank, s)) = measure(i) else {
+            continue;
+        };
+        if distance <= max_distance && closest.is_none_or(|(_, closest_rank)| rank < closest_rank) {
+            closest = Some(((i as f32 + s) / segment_count as f32, rank));
+        }
+    }
+    closest
+}
+
+/// Ray-sphere intersection, returning the distance along the ray to the entry point.
+/// `direction` must be normalized.
+fn ray_sphere_intersect(origin: Vec3, direction: Vec3, center: Vec3, radius: f32) -> Option<f32> {
+    let oc = origin - center;
+    let b = 2.0 * oc.dot(direction);
+    let c = oc.dot(oc) - radius * radius;
+    let discriminant = b * b - 4.0 * c;
+
+    if discriminant < 0.0 {
+        None
+    } else {
+        let t = (-b - discriminant.sqrt()) / 2.0;
+        if t > 0.0 {
+            Some(t)
+        } else {
+            None
+        }
+    }
+}
+
+/// Shortest distance between a ray and a line segment.
+/// `direction` must be normalized.
+///
+/// Returns `(distance, ray_t, s)` where `ray_t` is the distance along the ray and
+/// `s` the position along the segment (0.0 - 1.0) of the closest points, or `None`
+/// if the closest point lies behind the ray origin.
+fn ray_segment_distance(
+    origin: Vec3,
+    direction: Vec3,
+    a: Vec3,
+    b: Vec3,
+) -> Option<(f32, f32, f32)> {
+    let segment = b - a;
+    let w = origin - a;
+    let seg_len_sq = segment.dot(segment);
+    let d_dot_s = direction.dot(segment);
+    let d_dot_w = direction.dot(w);
+    let s_dot_w = segment.dot(w);
+
+    // `direction` is normalized, so the ray term of the denominator is 1
+    let denom = seg_len_sq - d_dot_s * d_dot_s;
+
+    let s = if seg_len_sq > 1e-8 && denom > 1e-8 {
+        ((s_dot_w - d_dot_s * d_dot_w) / denom).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    // Closest point on the ray to the chosen segment point
+    let ray_t = (segment * s - w).dot(direction);
+    if ray_t <= 0.0 {
+        return None;
+    }
+
+    let on_ray = origin + direction * ray_t;
+    let on_segment = a + segment * s;
+    Some((on_ray.distance(on_segment), ray_t, s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::SplineType;
+
+    #[test]
+    fn test_pick_control_point_and_curve() {
+        let spline = Spline::new(
+            SplineType::CubicBezier,
+            vec![
+                Vec3::new(0.0, 0.0, 0.0),
+                Vec3::new(1.0, 0.0, 0.0),
+                Vec3::new(3.0, 0.0, 0.0),
+                Vec3::new(4.0, 0.0, 0.0),
+            ],
+        );
+
+        // Looking straight down onto the second control point
+        let down = Vec3::NEG_Y;
+        assert_eq!(
+            spline.pick_control_point(Vec3::new(1.0, 5.0, 0.0), down, 0.2),
+            Some(1)
+        );
+        assert_eq!(
+            spline.pick_control_point(Vec3::new(2.0, 5.0, 0.0), down, 0.2),
+            None
+        );
+
+        // Looking down onto the middle of the curve (x = 2 at t = 0.5 by symmetry)
+        let t = spline
+            .pick_curve(Vec3::new(2.0, 5.0, 0.05), down, 0.1)
+            .unwrap();
+        assert!((t - 0.5).abs() < 0.02);
+        assert!(spline
+            .pick_curve(Vec3::new(2.0, 5.0, 1.0), down, 0.1)
+            .is_none());
+    }
+
+    #[test]
+    fn test_pick_polyline_screen() {
+        let points = [
+            Some(Vec2::new(0.0, 0.0)),
+            Some(Vec2::new(100.0, 0.0)),
+            None,
+            Some(Vec2::new(300.0, 0.0)),
+            Some(Vec2::new(300.0, 100.0)),
+        ];
+
+        let (fraction, distance) =
+            pick_polyline_screen(&points, Vec2::new(50.0, 5.0), 8.0).unwrap();
+        assert!((fraction - 0.125).abs() < 1e-5);
+        assert!((distance - 5.0).abs() < 1e-5);
+
+        // Too far away, or across the gap left by the unprojected point
+        assert!(pick_polyline_screen(&points, Vec2::new(50.0, 20.0), 8.0).is_none());
+        assert!(pick_polyline_screen(&points, Vec2::new(200.0, 0.0), 8.0).is_none());
+
+        // Segments past the gap still pick
+        let (fraction, _) = pick_polyline_screen(&points, Vec2::new(297.0, 4.0), 8.0).unwrap();
+        assert!((fraction - (3.0 + 0.04) / 4.0).abs() < 1e-5);
+    }
+}
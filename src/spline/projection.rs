@@ -4,10 +4,13 @@
 //! and control points onto surfaces, used by both the editor visualization
 //! and selection/picking systems.
 
+#[cfg(feature = "avian3d")]
 use avian3d::prelude::*;
 use bevy::prelude::*;
 
-use crate::surface::{project_point, SplineMeshProjection};
+#[cfg(feature = "avian3d")]
+use crate::surface::{project_point, project_point_or_original};
+use crate::surface::SplineMeshProjection;
 
 use super::{CachedSplineCurve, Spline};
 
@@ -42,6 +45,7 @@ pub struct SplineProjectionConfig<'a> {
 ///
 /// Uses the surface projection settings plus an additional visual offset
 /// to ensure the projected point is visible above the terrain.
+#[cfg(feature = "avian3d")]
 pub fn project_spline_point(
     spatial_query: &SpatialQuery,
     point: Vec3,
@@ -56,6 +60,28 @@ pub fn project_spline_point(
     }
 }
 
+/// Create a copy of a spline with its control points projected onto the surface.
+///
+/// Control points are projected in world space using `spline_transform` and
+/// converted back to the spline's local space. Points without a surface below
+/// them are left unchanged. Use this to permanently bake a surface projection.
+#[cfg(feature = "avian3d")]
+pub fn project_spline(
+    spatial_query: &SpatialQuery,
+    spline: &Spline,
+    spline_transform: &GlobalTransform,
+    surface: &SplineMeshProjection,
+) -> Spline {
+    let to_local = spline_transform.affine().inverse();
+    let mut projected = spline.clone();
+    for point in &mut projected.control_points {
+        let world = spline_transform.transform_point(*point);
+        let hit = project_point_or_original(spatial_query, world, surface);
+        *point = to_local.transform_point3(hit);
+    }
+    projected
+}
+
 /// Helper to get the effective control points for a spline.
 ///
 /// Returns projected positions if available, otherwise returns the original positions.
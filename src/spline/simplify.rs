@@ -0,0 +1,255 @@
+//! Reducing the number of control points of splines.
+
+use bevy::prelude::*;
+
+use super::{Spline, SplineType};
+
+impl Spline {
+    /// Remove control points that barely change the shape of the spline.
+    ///
+    /// Runs a Ramer–Douglas–Peucker pass, removing points that lie within
+    /// `tolerance` of the polyline through the points that are kept. For Bézier
+    /// splines the pass runs over the anchors; the handles around a merged segment
+    /// keep their directions and are scaled to its new length. For the other types
+    /// it runs over all points, always keeping the outer two at each end of open
+    /// splines since they set where and how the curve ends.
+    ///
+    /// Pinned points are never removed, and pins and timestamps of kept points are
+    /// kept. Does nothing if the spline is invalid, a closed Bézier spline, or if
+    /// simplifying would leave too few points for its type.
+    pub fn simplify(&mut self, tolerance: f32) {
+        if !self.is_valid()
+            || self.segment_count() == 0
+            || (self.closed && self.spline_type == SplineType::CubicBezier)
+        {
+            return;
+        }
+
+        let n = self.control_points.len();
+        let segment_count = self.segment_count();
+
+        // Polyline to simplify, with the indices of its points in `control_points`
+        let indices: Vec<usize> = match self.spline_type {
+            SplineType::CubicBezier => (0..=segment_count).map(|k| k * 3).collect(),
+            // The start is repeated at the end so the pass sees the closing edge
+            _ if self.closed => (0..n).chain([0]).collect(),
+            _ => (0..n).collect(),
+        };
+        let polyline: Vec<Vec3> = indices.iter().map(|&i| self.control_points[i]).collect();
+
+        let mut keep: Vec<bool> = indices.iter().map(|&i| self.is_pinned(i)).collect();
+        let last = keep.len() - 1;
+        keep[0] = true;
+        keep[last] = true;
+        if self.spline_type != SplineType::CubicBezier && !self.closed {
+            keep[1] = true;
+            keep[last - 1] = true;
+        }
+        douglas_peucker(&polyline, tolerance, &mut keep);
+
+        // Mark which control points survive
+        let mut kept_points = vec![true; n];
+        for (&index, &kept) in indices.iter().zip(&keep) {
+            if !kept {
+                kept_points[index] = false;
+            }
+        }
+        if self.spline_type == SplineType::CubicBezier {
+            // Handles go with their anchors
+            for (k, &kept) in keep.iter().enumerate() {
+                if !kept {
+                    kept_points[k * 3 - 1] = false;
+                    kept_points[k * 3 + 1] = false;
+                }
+            }
+        }
+        let remaining = kept_points.iter().filter(|&&kept| kept).count();
+        if remaining == n || remaining < self.spline_type.min_points() {
+            return;
+        }
+
+        if self.spline_type == SplineType::CubicBezier {
+            self.rescale_merged_handles(&keep);
+        }
+
+        // Timestamps belong to the segment boundaries: the anchors for Bézier, every
+        // point on loops and clamped B-Splines, and the points between the outer
+        // ones for the others
+        if self.timestamps.len() == segment_count + 1 {
+            let every_point = self.closed || self.basis().clamps(self.closed);
+            let boundary_kept = |k: usize| match self.spline_type {
+                SplineType::CubicBezier => keep[k],
+                _ if every_point => keep[k],
+                _ => keep[k + 1],
+            };
+            let timestamps = std::mem::take(&mut self.timestamps);
+            self.timestamps = timestamps
+                .into_iter()
+                .enumerate()
+                .filter(|&(k, _)| boundary_kept(k))
+                .map(|(_, time)| time)
+                .collect();
+        }
+
+        retain_kept(&mut self.control_points, &kept_points);
+        if !self.pinned.is_empty() {
+            self.pinned.resize(n, false);
+            retain_kept(&mut self.pinned, &kept_points);
+        }
+    }
+
+    /// Stretch the Bézier handles on either side of each run of removed anchors
+    /// in proportion to the length of the merged segment.
+    fn rescale_merged_handles(&mut self, keep: &[bool]) {
+        let kept: Vec<usize> = (0..keep.len()).filter(|&k| keep[k]).collect();
+        for pair in kept.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            if end == start + 1 {
+                continue;
+            }
+            let points = &mut self.control_points;
+            let (a, b) = (points[start * 3], points[end * 3]);
+            let span = a.distance(b);
+
+            let out_chord = a.distance(points[start * 3 + 3]);
+            if out_chord > f32::EPSILON {
+                points[start * 3 + 1] = a + (points[start * 3 + 1] - a) * (span / out_chord);
+            }
+            let in_chord = b.distance(points[end * 3 - 3]);
+            if in_chord > f32::EPSILON {
+                points[end * 3 - 1] = b + (points[end * 3 - 1] - b) * (span / in_chord);
+            }
+        }
+    }
+}
+
+/// Keep the values whose entry in `kept` is set.
+fn retain_kept<T>(values: &mut Vec<T>, kept: &[bool]) {
+    let mut kept = kept.iter();
+    values.retain(|_| kept.next().copied().unwrap_or(true));
+}
+
+/// Mark the points of `points` needed to stay within `tolerance` of the polyline.
+///
+/// Points already marked in `keep` are kept, and the polyline is simplified
+/// between them.
+fn douglas_peucker(points: &[Vec3], tolerance: f32, keep: &mut [bool]) {
+    let fixed: Vec<usize> = (0..points.len()).filter(|&i| keep[i]).collect();
+    for pair in fixed.windows(2) {
+        simplify_span(points, pair[0], pair[1], tolerance, keep);
+    }
+}
+
+fn simplify_span(points: &[Vec3], start: usize, end: usize, tolerance: f32, keep: &mut [bool]) {
+    let (a, b) = (points[start], points[end]);
+    let farthest = (start + 1..end)
+        .map(|i| (i, distance_to_segment(points[i], a, b)))
+        .max_by(|x, y| x.1.total_cmp(&y.1));
+    if let Some((index, distance)) = farthest {
+        if distance > tolerance {
+            keep[index] = true;
+            simplify_span(points, start, index, tolerance, keep);
+            simplify_span(points, index, end, tolerance, keep);
+        }
+    }
+}
+
+fn distance_to_segment(point: Vec3, a: Vec3, b: Vec3) -> f32 {
+    let ab = b - a;
+    let length_sq = ab.length_squared();
+    let t = if length_sq > f32::EPSILON {
+        ((point - a).dot(ab) / length_sq).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    point.distance(a + ab * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::spline::{Spline, SplineType};
+
+    #[test]
+    fn test_simplify_collapses_lines_and_keeps_corners() {
+        // Ten Bézier segments along a straight line
+        let mut bezier = Spline::new(
+            SplineType::CubicBezier,
+            (0..=30)
+                .map(|i| Vec3::new(i as f32 / 3.0, 0.0, 0.0))
+                .collect(),
+        );
+        bezier.simplify(0.01);
+        assert_eq!(bezier.control_points.len(), 4);
+        let expected = [0.0, 10.0 / 3.0, 20.0 / 3.0, 10.0].map(|x| Vec3::new(x, 0.0, 0.0));
+        for (point, expected) in bezier.control_points.iter().zip(expected) {
+            assert!(point.distance(expected) < 1e-4);
+        }
+
+        // A dense Catmull-Rom line keeps its two end points and their neighbours
+        let line: Vec<Vec3> = (0..20).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect();
+        let mut catmull_rom = Spline::new(SplineType::CatmullRom, line.clone());
+        catmull_rom.simplify(0.01);
+        assert_eq!(
+            catmull_rom.control_points,
+            vec![line[0], line[1], line[18], line[19]]
+        );
+
+        // The corner of an L survives, the points along its legs don't
+        let mut corner = Spline::new(
+            SplineType::CatmullRom,
+            (0..10)
+                .map(|i| Vec3::new(i as f32, 0.0, 0.0))
+                .chain((1..10).map(|i| Vec3::new(9.0, 0.0, i as f32)))
+                .collect(),
+        );
+        corner.set_pinned(4, true);
+        corner.simplify(0.01);
+        assert_eq!(
+            corner.control_points,
+            [
+                (0.0, 0.0),
+                (1.0, 0.0),
+                (4.0, 0.0),
+                (9.0, 0.0),
+                (9.0, 8.0),
+                (9.0, 9.0)
+            ]
+            .map(|(x, z)| Vec3::new(x, 0.0, z))
+            .to_vec()
+        );
+        // The pin follows its point
+        assert!(corner.is_pinned(2));
+        assert!(!corner.is_pinned(4));
+
+        // A large enough tolerance cuts the corner
+        let mut cut = corner.clone();
+        cut.simplify(5.0);
+        assert_eq!(cut.control_points.len(), 5);
+        assert!(!cut.control_points.contains(&Vec3::new(9.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_simplify_clamped_bspline_keeps_timestamps() {
+        // A clamped B-Spline has a segment boundary at every point
+        let mut spline = Spline::new(
+            SplineType::BSpline,
+            (0..8).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+        )
+        .with_clamped(true);
+        spline.control_points[4].z = 3.0;
+        spline.timestamps = (0..8).map(|i| i as f32).collect();
+        assert_eq!(spline.segment_count() + 1, spline.timestamps.len());
+
+        spline.simplify(0.01);
+        assert_eq!(
+            spline.control_points,
+            [0.0, 1.0, 3.0, 4.0, 5.0, 6.0, 7.0]
+                .map(|x| Vec3::new(x, 0.0, if x == 4.0 { 3.0 } else { 0.0 }))
+                .to_vec()
+        );
+        assert_eq!(spline.timestamps, vec![0.0, 1.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(spline.segment_count() + 1, spline.timestamps.len());
+    }
+}
@@ -0,0 +1,183 @@
+use bevy::prelude::*;
+
+use super::types::{
+    SplineBasis, SplineEvaluator, SplineType, TcbParams, DEFAULT_CATMULL_ROM_TENSION,
+};
+
+/// A 2D spline component with [`Vec2`] control points.
+///
+/// The 2D counterpart of [`Spline`](super::Spline) for paths that are purely
+/// planar, such as minimap routes or UI flourishes. Evaluation uses the same
+/// interpolation math and returns `Vec2`.
+#[derive(Component, Debug, Clone, Reflect)]
+#[reflect(Component)]
+pub struct Spline2d {
+    /// The type of spline interpolation.
+    pub spline_type: SplineType,
+    /// Shape parameters used when `spline_type` is [`SplineType::Tcb`].
+    #[reflect(default)]
+    pub tcb: TcbParams,
+    /// Tension used when `spline_type` is [`SplineType::CatmullRom`]. See
+    /// [`Spline::tension`](super::Spline::tension).
+    #[reflect(default = "default_tension")]
+    pub tension: f32,
+    /// Control points defining the spline shape.
+    pub control_points: Vec<Vec2>,
+    /// Whether the spline forms a closed loop.
+    pub closed: bool,
+}
+
+fn default_tension() -> f32 {
+    DEFAULT_CATMULL_ROM_TENSION
+}
+
+impl Default for Spline2d {
+    fn default() -> Self {
+        Self::new(SplineType::default(), Vec::new())
+    }
+}
+
+impl Spline2d {
+    /// Create a new 2D spline with the given type and control points.
+    pub fn new(spline_type: SplineType, control_points: Vec<Vec2>) -> Self {
+        Self {
+            spline_type,
+            tcb: TcbParams::default(),
+            tension: DEFAULT_CATMULL_ROM_TENSION,
+            control_points,
+            closed: false,
+        }
+    }
+
+    /// Create a new closed 2D spline.
+    pub fn closed(spline_type: SplineType, control_points: Vec<Vec2>) -> Self {
+        Self {
+            spline_type,
+            tcb: TcbParams::default(),
+            tension: DEFAULT_CATMULL_ROM_TENSION,
+            control_points,
+            closed: true,
+        }
+    }
+
+    /// Set the Catmull-Rom tension (see [`Spline2d::tension`]).
+    pub fn with_tension(mut self, tension: f32) -> Self {
+        self.tension = tension;
+        self
+    }
+
+    /// Get the spline type together with its shape parameters.
+    pub fn basis(&self) -> SplineBasis {
+        SplineBasis {
+            spline_type: self.spline_type,
+            tcb: self.tcb,
+            tension: self.tension,
+            ..default()
+        }
+    }
+
+    /// Evaluate the spline at parameter t (0.0 to 1.0).
+    pub fn evaluate(&self, t: f32) -> Option<Vec2> {
+        self.basis().evaluate(&self.control_points, t, self.closed)
+    }
+
+    /// Evaluate the tangent at parameter t.
+    pub fn evaluate_tangent(&self, t: f32) -> Option<Vec2> {
+        self.basis()
+            .evaluate_tangent(&self.control_points, t, self.closed)
+    }
+
+    /// Evaluate position and tangent at parameter t in a single segment lookup.
+    pub fn evaluate_pos_tan(&self, t: f32) -> Option<(Vec2, Vec2)> {
+        self.basis()
+            .evaluate_pos_tan(&self.control_points, t, self.closed)
+    }
+
+    /// Get the number of segments in this spline.
+    pub fn segment_count(&self) -> usize {
+        self.spline_type
+            .segment_count(&self.control_points, self.closed)
+    }
+
+    /// Check if the spline has enough points to be valid.
+    pub fn is_valid(&self) -> bool {
+        self.control_points.len() >= self.spline_type.min_points()
+    }
+
+    /// Sample the spline into a series of points.
+    pub fn sample(&self, samples_per_segment: usize) -> Vec<Vec2> {
+        let segment_count = self.segment_count();
+        if segment_count == 0 {
+            return Vec::new();
+        }
+
+        let total_samples = segment_count * samples_per_segment + 1;
+        (0..total_samples)
+            .filter_map(|i| self.evaluate(i as f32 / (total_samples - 1) as f32))
+            .collect()
+    }
+
+    /// Add a control point at the given position.
+    pub fn add_point(&mut self, position: Vec2) {
+        self.control_points.push(position);
+    }
+
+    /// Insert a control point at the given index.
+    pub fn insert_point(&mut self, index: usize, position: Vec2) {
+        if index <= self.control_points.len() {
+            self.control_points.insert(index, position);
+        }
+    }
+
+    /// Remove the control point at the given index.
+    pub fn remove_point(&mut self, index: usize) -> Option<Vec2> {
+        (index < self.control_points.len()).then(|| self.control_points.remove(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::spline::Spline;
+
+    #[test]
+    fn test_spline_2d_matches_3d_evaluation() {
+        let points = vec![
+            Vec2::new(0.0, 0.0),
+            Vec2::new(1.0, 2.0),
+            Vec2::new(3.0, -1.0),
+            Vec2::new(4.0, 0.5),
+            Vec2::new(6.0, 1.0),
+        ];
+
+        for spline_type in [
+            SplineType::CubicBezier,
+            SplineType::CatmullRom,
+            SplineType::BSpline,
+        ] {
+            let spline_2d = Spline2d::new(spline_type, points.clone());
+            let spline_3d =
+                Spline::new(spline_type, points.iter().map(|p| p.extend(0.0)).collect());
+
+            for i in 0..=20 {
+                let t = i as f32 / 20.0;
+                let (position, tangent) = spline_2d.evaluate_pos_tan(t).unwrap();
+                let (expected_position, expected_tangent) = spline_3d.evaluate_pos_tan(t).unwrap();
+                assert_eq!(position.extend(0.0), expected_position);
+                assert_eq!(tangent.extend(0.0), expected_tangent);
+            }
+        }
+
+        // The Catmull-Rom tension carries over as well
+        let spline_2d = Spline2d::new(SplineType::CatmullRom, points.clone()).with_tension(0.8);
+        let spline_3d = Spline::new(
+            SplineType::CatmullRom,
+            points.iter().map(|p| p.extend(0.0)).collect(),
+        )
+        .with_tension(0.8);
+        let position = spline_2d.evaluate(0.3).unwrap();
+        assert_eq!(position.extend(0.0), spline_3d.evaluate(0.3).unwrap());
+        let standard = Spline2d::new(SplineType::CatmullRom, points);
+        assert_ne!(Some(position), standard.evaluate(0.3));
+    }
+}
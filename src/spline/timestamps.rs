@@ -0,0 +1,99 @@
+//! Mapping between recorded time and curve parameter for timestamped splines.
+
+use super::Spline;
+
+impl Spline {
+    /// Check whether the spline has a usable [`timestamps`](Spline::timestamps) list.
+    ///
+    /// Requires one entry per segment boundary, in non-decreasing order, spanning a
+    /// positive duration.
+    pub fn has_timestamps(&self) -> bool {
+        let segments = self.segment_count();
+        segments > 0
+            && self.timestamps.len() == segments + 1
+            && self.timestamps.windows(2).all(|w| w[0] <= w[1])
+            && self.timestamps[segments] > self.timestamps[0]
+    }
+
+    /// Get the recorded time range as `(start, end)`.
+    pub fn time_range(&self) -> Option<(f32, f32)> {
+        self.has_timestamps().then(|| {
+            (
+                self.timestamps[0],
+                self.timestamps[self.timestamps.len() - 1],
+            )
+        })
+    }
+
+    /// Get the recorded time at parameter t, interpolating linearly between knots.
+    pub fn time_at(&self, t: f32) -> Option<f32> {
+        if !self.has_timestamps() {
+            return None;
+        }
+
+        let segments = self.timestamps.len() - 1;
+        let scaled = t.clamp(0.0, 1.0) * segments as f32;
+        let segment = (scaled as usize).min(segments - 1);
+        let local = scaled - segment as f32;
+
+        let (t0, t1) = (self.timestamps[segment], self.timestamps[segment + 1]);
+        Some(t0 + (t1 - t0) * local)
+    }
+
+    /// Get the parameter t at a recorded time, interpolating linearly between knots.
+    ///
+    /// Times outside the recorded range are clamped to the ends of the spline.
+    pub fn t_at_time(&self, time: f32) -> Option<f32> {
+        let (start, end) = self.time_range()?;
+        let time = time.clamp(start, end);
+
+        // First knot at or after the requested time
+        let next = self.timestamps.partition_point(|&knot| knot < time).max(1);
+        let segment = next - 1;
+
+        let (t0, t1) = (self.timestamps[segment], self.timestamps[next]);
+        let local = if t1 > t0 {
+            (time - t0) / (t1 - t0)
+        } else {
+            0.0
+        };
+        Some((segment as f32 + local) / (self.timestamps.len() - 1) as f32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::spline::{Spline, SplineType};
+
+    #[test]
+    fn test_timestamp_mapping_round_trips() {
+        let mut spline = Spline::new(
+            SplineType::CatmullRom,
+            (0..6).map(|i| Vec3::new(i as f32, 0.0, 0.0)).collect(),
+        );
+        assert_eq!(spline.segment_count(), 3);
+        assert_eq!(spline.t_at_time(0.0), None);
+
+        // Slow first segment, fast last two
+        spline.timestamps = vec![1.0, 5.0, 6.0, 7.0];
+        assert!(spline.has_timestamps());
+        assert_eq!(spline.time_range(), Some((1.0, 7.0)));
+        assert_eq!(spline.t_at_time(3.0), Some(1.0 / 6.0));
+        assert_eq!(spline.t_at_time(5.5), Some(0.5));
+        assert_eq!(spline.t_at_time(100.0), Some(1.0));
+
+        for time in [1.0, 2.5, 5.0, 6.25, 7.0] {
+            let t = spline.t_at_time(time).unwrap();
+            assert!((spline.time_at(t).unwrap() - time).abs() < 1e-5);
+        }
+
+        // Reversing keeps the timestamps increasing from the same start
+        spline.reverse();
+        assert_eq!(spline.timestamps, vec![1.0, 2.0, 3.0, 7.0]);
+
+        spline.timestamps = vec![0.0, 2.0, 1.0, 3.0];
+        assert!(!spline.has_timestamps());
+    }
+}
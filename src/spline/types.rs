@@ -1,11 +1,20 @@
+use std::ops::{Add, Mul, Sub};
+
 use bevy::prelude::*;
 
 /// The type of spline interpolation to use.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[cfg_attr(feature = "json", derive(serde::Serialize, serde::Deserialize))]
 #[reflect(Default)]
 pub enum SplineType {
     /// Cubic Bézier spline - 4 control points per segment.
     /// Points 0 and 3 are on the curve, 1 and 2 are handles.
+    ///
+    /// Closed splines add a segment from the last anchor back to the first. Its
+    /// handles are the two points after the last anchor if there are two, and
+    /// otherwise mirror the handles next to the last and first anchors. A last
+    /// anchor sitting exactly on the first already closes the loop, so no segment
+    /// is added.
     #[default]
     CubicBezier,
     /// Catmull-Rom spline - passes through all control points.
@@ -14,6 +23,9 @@ pub enum SplineType {
     /// B-Spline - smooth curve with local control.
     /// Does not pass through control points except endpoints.
     BSpline,
+    /// Kochanek–Bartels spline - passes through all control points like Catmull-Rom,
+    /// with tension, continuity and bias controls (see [`TcbParams`]).
+    Tcb,
 }
 
 impl SplineType {
@@ -22,7 +34,8 @@ impl SplineType {
         match self {
             Self::CubicBezier => Self::CatmullRom,
             Self::CatmullRom => Self::BSpline,
-            Self::BSpline => Self::CubicBezier,
+            Self::BSpline => Self::Tcb,
+            Self::Tcb => Self::CubicBezier,
         }
     }
 
@@ -32,6 +45,7 @@ impl SplineType {
             Self::CubicBezier => "Cubic Bézier",
             Self::CatmullRom => "Catmull-Rom",
             Self::BSpline => "B-Spline",
+            Self::Tcb => "TCB",
         }
     }
 
@@ -41,49 +55,202 @@ impl SplineType {
             Self::CubicBezier => 4,
             Self::CatmullRom => 4,
             Self::BSpline => 4,
+            Self::Tcb => 4,
         }
     }
 }
 
+/// Kochanek–Bartels shape parameters used by [`SplineType::Tcb`].
+///
+/// Each parameter typically lies in -1.0 - 1.0. All zeros reproduce Catmull-Rom.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Default)]
+pub struct TcbParams {
+    /// Tension: positive values tighten the curve around the points, negative
+    /// values make it rounder.
+    pub tension: f32,
+    /// Continuity: non-zero values make the tangents on either side of a point
+    /// differ, creating corners.
+    pub continuity: f32,
+    /// Bias: positive values shoot the curve past a point, negative values
+    /// pull it in before the point.
+    pub bias: f32,
+}
+
+impl TcbParams {
+    /// Create TCB parameters.
+    pub fn new(tension: f32, continuity: f32, bias: f32) -> Self {
+        Self {
+            tension,
+            continuity,
+            bias,
+        }
+    }
+}
+
+/// Tension (tangent scale) of a standard Catmull-Rom spline.
+pub const DEFAULT_CATMULL_ROM_TENSION: f32 = 0.5;
+
+/// A spline type together with its shape parameters.
+///
+/// This is what [`Spline`](super::Spline) evaluates with. Evaluating a bare
+/// [`SplineType`] is equivalent to using default parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SplineBasis {
+    /// The type of spline interpolation.
+    pub spline_type: SplineType,
+    /// Shape parameters for [`SplineType::Tcb`], ignored by other types.
+    pub tcb: TcbParams,
+    /// Tension for [`SplineType::CatmullRom`], ignored by other types.
+    /// See [`Spline::tension`](super::Spline::tension).
+    pub tension: f32,
+    /// Whether an open [`SplineType::BSpline`] is clamped to its end points,
+    /// ignored by other types. See [`Spline::clamped`](super::Spline::clamped).
+    pub clamped: bool,
+}
+
+impl Default for SplineBasis {
+    fn default() -> Self {
+        Self {
+            spline_type: SplineType::default(),
+            tcb: TcbParams::default(),
+            tension: DEFAULT_CATMULL_ROM_TENSION,
+            clamped: false,
+        }
+    }
+}
+
+impl From<SplineType> for SplineBasis {
+    fn from(spline_type: SplineType) -> Self {
+        Self {
+            spline_type,
+            ..default()
+        }
+    }
+}
+
+/// A control point type that splines can be evaluated over.
+///
+/// Implemented for [`Vec3`] (used by [`Spline`](super::Spline)) and [`Vec2`]
+/// (used by [`Spline2d`](super::Spline2d)). The evaluation math only needs
+/// addition, subtraction and scaling, so both share the same code.
+pub trait SplinePoint:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<f32, Output = Self> + Send + Sync + 'static
+{
+    /// Distance between two points.
+    fn distance(self, other: Self) -> f32;
+}
+
+impl SplinePoint for Vec2 {
+    fn distance(self, other: Self) -> f32 {
+        Vec2::distance(self, other)
+    }
+}
+
+impl SplinePoint for Vec3 {
+    fn distance(self, other: Self) -> f32 {
+        Vec3::distance(self, other)
+    }
+}
+
 /// Trait for evaluating spline curves.
-pub trait SplineEvaluator {
+///
+/// Generic over the control point type, defaulting to [`Vec3`].
+pub trait SplineEvaluator<P: SplinePoint = Vec3> {
     /// Evaluate the spline at parameter t (0.0 to 1.0 across entire spline).
-    fn evaluate(&self, points: &[Vec3], t: f32, closed: bool) -> Option<Vec3>;
+    fn evaluate(&self, points: &[P], t: f32, closed: bool) -> Option<P>;
 
     /// Evaluate the tangent at parameter t.
-    fn evaluate_tangent(&self, points: &[Vec3], t: f32, closed: bool) -> Option<Vec3>;
+    fn evaluate_tangent(&self, points: &[P], t: f32, closed: bool) -> Option<P>;
+
+    /// Evaluate the second derivative at parameter t.
+    ///
+    /// Like the tangent, this is with respect to the local parameter of the
+    /// segment containing t.
+    fn evaluate_second_derivative(&self, points: &[P], t: f32, closed: bool) -> Option<P>;
+
+    /// Evaluate position and tangent at parameter t together.
+    ///
+    /// Implementations can override this to share the segment lookup between both.
+    fn evaluate_pos_tan(&self, points: &[P], t: f32, closed: bool) -> Option<(P, P)> {
+        Some((
+            self.evaluate(points, t, closed)?,
+            self.evaluate_tangent(points, t, closed)?,
+        ))
+    }
 
     /// Get the number of segments in the spline.
-    fn segment_count(&self, points: &[Vec3], closed: bool) -> usize;
+    fn segment_count(&self, points: &[P], closed: bool) -> usize;
 }
 
-impl SplineEvaluator for SplineType {
-    fn evaluate(&self, points: &[Vec3], t: f32, closed: bool) -> Option<Vec3> {
-        match self {
-            Self::CubicBezier => evaluate_cubic_bezier(points, t, closed),
-            Self::CatmullRom => evaluate_catmull_rom(points, t, closed),
-            Self::BSpline => evaluate_bspline(points, t, closed),
-        }
+impl<P: SplinePoint> SplineEvaluator<P> for SplineBasis {
+    fn evaluate(&self, points: &[P], t: f32, closed: bool) -> Option<P> {
+        let (segment_points, local_t) = self.segment_at(points, t, closed)?;
+        Some(combine(segment_points, self.weights(0, local_t)))
     }
 
-    fn evaluate_tangent(&self, points: &[Vec3], t: f32, closed: bool) -> Option<Vec3> {
-        match self {
-            Self::CubicBezier => evaluate_cubic_bezier_tangent(points, t, closed),
-            Self::CatmullRom => evaluate_catmull_rom_tangent(points, t, closed),
-            Self::BSpline => evaluate_bspline_tangent(points, t, closed),
+    fn evaluate_tangent(&self, points: &[P], t: f32, closed: bool) -> Option<P> {
+        let (segment_points, local_t) = self.segment_at(points, t, closed)?;
+        Some(combine(segment_points, self.weights(1, local_t)))
+    }
+
+    fn evaluate_second_derivative(&self, points: &[P], t: f32, closed: bool) -> Option<P> {
+        let (segment_points, local_t) = self.segment_at(points, t, closed)?;
+        Some(combine(segment_points, self.weights(2, local_t)))
+    }
+
+    fn evaluate_pos_tan(&self, points: &[P], t: f32, closed: bool) -> Option<(P, P)> {
+        let (segment_points, local_t) = self.segment_at(points, t, closed)?;
+        Some((
+            combine(segment_points, self.weights(0, local_t)),
+            combine(segment_points, self.weights(1, local_t)),
+        ))
+    }
+
+    fn segment_count(&self, points: &[P], closed: bool) -> usize {
+        if self.clamps(closed) && points.len() >= self.spline_type.min_points() {
+            // One segment between each pair of points, see `segment_points`
+            points.len() - 1
+        } else {
+            self.spline_type.segment_count(points, closed)
         }
     }
+}
 
-    fn segment_count(&self, points: &[Vec3], closed: bool) -> usize {
+impl<P: SplinePoint> SplineEvaluator<P> for SplineType {
+    fn evaluate(&self, points: &[P], t: f32, closed: bool) -> Option<P> {
+        SplineBasis::from(*self).evaluate(points, t, closed)
+    }
+
+    fn evaluate_tangent(&self, points: &[P], t: f32, closed: bool) -> Option<P> {
+        SplineBasis::from(*self).evaluate_tangent(points, t, closed)
+    }
+
+    fn evaluate_second_derivative(&self, points: &[P], t: f32, closed: bool) -> Option<P> {
+        SplineBasis::from(*self).evaluate_second_derivative(points, t, closed)
+    }
+
+    fn evaluate_pos_tan(&self, points: &[P], t: f32, closed: bool) -> Option<(P, P)> {
+        SplineBasis::from(*self).evaluate_pos_tan(points, t, closed)
+    }
+
+    fn segment_count(&self, points: &[P], closed: bool) -> usize {
         match self {
             Self::CubicBezier => {
                 if points.len() < 4 {
                     0
+                } else if closed {
+                    // Plus the segment wrapping back to the first anchor, unless the
+                    // last anchor is already on it
+                    let n = points.len();
+                    let ends_on_first = (n - 1).is_multiple_of(3)
+                        && SplinePoint::distance(points[n - 1], points[0]) < 1e-4;
+                    (n - 1) / 3 + usize::from(!ends_on_first)
                 } else {
                     (points.len() - 1) / 3
                 }
             }
-            Self::CatmullRom | Self::BSpline => {
+            Self::CatmullRom | Self::BSpline | Self::Tcb => {
                 if points.len() < 4 {
                     0
                 } else if closed {
@@ -96,277 +263,400 @@ impl SplineEvaluator for SplineType {
     }
 }
 
-// Cubic Bézier implementation
-fn evaluate_cubic_bezier(points: &[Vec3], t: f32, _closed: bool) -> Option<Vec3> {
-    if points.len() < 4 {
-        return None;
-    }
-
-    let num_segments = (points.len() - 1) / 3;
-    if num_segments == 0 {
-        return None;
+impl SplineType {
+    /// The four control points defining segment `segment`.
+    /// `segment` must be less than the segment count.
+    fn segment_points<P: SplinePoint>(&self, points: &[P], segment: usize, closed: bool) -> [P; 4] {
+        let n = points.len();
+        match self {
+            // Cubic Bézier: segments share their end anchors
+            Self::CubicBezier => {
+                let i = segment * 3;
+                if i + 3 < n {
+                    [points[i], points[i + 1], points[i + 2], points[i + 3]]
+                } else if i + 2 < n {
+                    // Closed, with the handles of the wrapping segment after the last anchor
+                    [points[i], points[i + 1], points[i + 2], points[0]]
+                } else {
+                    // Closed, ending on an anchor: the wrapping segment's handles
+                    // mirror the handles next to the end anchors
+                    [
+                        points[i],
+                        points[i] * 2.0 - points[i - 1],
+                        points[0] * 2.0 - points[1],
+                        points[0],
+                    ]
+                }
+            }
+            // Catmull-Rom and TCB: the segment runs between the middle two points
+            Self::CatmullRom | Self::Tcb if closed => [
+                points[(segment + n - 1) % n],
+                points[segment % n],
+                points[(segment + 1) % n],
+                points[(segment + 2) % n],
+            ],
+            // B-Spline (uniform cubic)
+            Self::BSpline if closed => [
+                points[segment % n],
+                points[(segment + 1) % n],
+                points[(segment + 2) % n],
+                points[(segment + 3) % n],
+            ],
+            Self::CatmullRom | Self::BSpline | Self::Tcb => [
+                points[segment],
+                points[segment + 1],
+                points[segment + 2],
+                points[segment + 3],
+            ],
+        }
     }
 
-    let t_scaled = t * num_segments as f32;
-    let segment = (t_scaled.floor() as usize).min(num_segments - 1);
-    let local_t = t_scaled - segment as f32;
-
-    let i = segment * 3;
-    if i + 3 >= points.len() {
-        return Some(points[points.len() - 1]);
+    /// Weights of the four segment control points for the given derivative
+    /// order (0 = position, 1 = tangent, 2 = second derivative) at local t.
+    fn basis(&self, order: usize, t: f32) -> [f32; 4] {
+        let t2 = t * t;
+        let t3 = t2 * t;
+        match (self, order) {
+            // Cubic Bézier (Bernstein polynomials)
+            (Self::CubicBezier, 0) => {
+                let mt = 1.0 - t;
+                [mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t2, t3]
+            }
+            (Self::CubicBezier, 1) => {
+                let mt = 1.0 - t;
+                [
+                    -3.0 * mt * mt,
+                    3.0 * mt * mt - 6.0 * mt * t,
+                    6.0 * mt * t - 3.0 * t2,
+                    3.0 * t2,
+                ]
+            }
+            (Self::CubicBezier, _) => {
+                let mt = 1.0 - t;
+                [6.0 * mt, 6.0 * t - 12.0 * mt, 6.0 * mt - 12.0 * t, 6.0 * t]
+            }
+            // Catmull-Rom (uniform, tension 0.5), also TCB with default parameters
+            (Self::CatmullRom | Self::Tcb, 0) => [
+                0.5 * (-t + 2.0 * t2 - t3),
+                0.5 * (2.0 - 5.0 * t2 + 3.0 * t3),
+                0.5 * (t + 4.0 * t2 - 3.0 * t3),
+                0.5 * (-t2 + t3),
+            ],
+            (Self::CatmullRom | Self::Tcb, 1) => [
+                0.5 * (-1.0 + 4.0 * t - 3.0 * t2),
+                0.5 * (-10.0 * t + 9.0 * t2),
+                0.5 * (1.0 + 8.0 * t - 9.0 * t2),
+                0.5 * (-2.0 * t + 3.0 * t2),
+            ],
+            (Self::CatmullRom | Self::Tcb, _) => [
+                0.5 * (4.0 - 6.0 * t),
+                0.5 * (-10.0 + 18.0 * t),
+                0.5 * (8.0 - 18.0 * t),
+                0.5 * (-2.0 + 6.0 * t),
+            ],
+            // B-Spline (uniform cubic)
+            (Self::BSpline, 0) => [
+                (1.0 - 3.0 * t + 3.0 * t2 - t3) / 6.0,
+                (4.0 - 6.0 * t2 + 3.0 * t3) / 6.0,
+                (1.0 + 3.0 * t + 3.0 * t2 - 3.0 * t3) / 6.0,
+                t3 / 6.0,
+            ],
+            (Self::BSpline, 1) => [
+                (-3.0 + 6.0 * t - 3.0 * t2) / 6.0,
+                (-12.0 * t + 9.0 * t2) / 6.0,
+                (3.0 + 6.0 * t - 9.0 * t2) / 6.0,
+                (3.0 * t2) / 6.0,
+            ],
+            (Self::BSpline, _) => [
+                (6.0 - 6.0 * t) / 6.0,
+                (-12.0 + 18.0 * t) / 6.0,
+                (6.0 - 18.0 * t) / 6.0,
+                (6.0 * t) / 6.0,
+            ],
+        }
     }
-
-    let p0 = points[i];
-    let p1 = points[i + 1];
-    let p2 = points[i + 2];
-    let p3 = points[i + 3];
-
-    Some(cubic_bezier(p0, p1, p2, p3, local_t))
 }
 
-fn evaluate_cubic_bezier_tangent(points: &[Vec3], t: f32, _closed: bool) -> Option<Vec3> {
-    if points.len() < 4 {
-        return None;
+impl SplineBasis {
+    /// Check if evaluation adds phantom end points to clamp a B-Spline.
+    pub(crate) fn clamps(&self, closed: bool) -> bool {
+        self.clamped && !closed && self.spline_type == SplineType::BSpline
     }
 
-    let num_segments = (points.len() - 1) / 3;
-    if num_segments == 0 {
-        return None;
-    }
-
-    let t_scaled = t * num_segments as f32;
-    let segment = (t_scaled.floor() as usize).min(num_segments - 1);
-    let local_t = t_scaled - segment as f32;
+    /// Locate the segment containing parameter t.
+    /// Returns the four control points of that segment and the local t within it.
+    fn segment_at<P: SplinePoint>(
+        &self,
+        points: &[P],
+        t: f32,
+        closed: bool,
+    ) -> Option<([P; 4], f32)> {
+        let num_segments = self.segment_count(points, closed);
+        if num_segments == 0 {
+            return None;
+        }
 
-    let i = segment * 3;
-    if i + 3 >= points.len() {
-        let i = (num_segments - 1) * 3;
-        let p0 = points[i];
-        let p1 = points[i + 1];
-        let p2 = points[i + 2];
-        let p3 = points[i + 3];
-        return Some(cubic_bezier_derivative(p0, p1, p2, p3, 1.0));
+        let (segment, local_t) = locate_segment(t, num_segments);
+        Some((self.segment_points(points, segment, closed), local_t))
     }
 
-    let p0 = points[i];
-    let p1 = points[i + 1];
-    let p2 = points[i + 2];
-    let p3 = points[i + 3];
-
-    Some(cubic_bezier_derivative(p0, p1, p2, p3, local_t))
-}
-
-fn cubic_bezier(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
-    let t2 = t * t;
-    let t3 = t2 * t;
-    let mt = 1.0 - t;
-    let mt2 = mt * mt;
-    let mt3 = mt2 * mt;
-
-    p0 * mt3 + p1 * 3.0 * mt2 * t + p2 * 3.0 * mt * t2 + p3 * t3
-}
-
-fn cubic_bezier_derivative(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
-    let t2 = t * t;
-    let mt = 1.0 - t;
-    let mt2 = mt * mt;
-
-    (p1 - p0) * 3.0 * mt2 + (p2 - p1) * 6.0 * mt * t + (p3 - p2) * 3.0 * t2
-}
+    /// The four control points defining segment `segment`.
+    /// `segment` must be less than the segment count.
+    fn segment_points<P: SplinePoint>(&self, points: &[P], segment: usize, closed: bool) -> [P; 4] {
+        if !self.clamps(closed) {
+            return self.spline_type.segment_points(points, segment, closed);
+        }
 
-// Catmull-Rom implementation
-fn evaluate_catmull_rom(points: &[Vec3], t: f32, closed: bool) -> Option<Vec3> {
-    if points.len() < 4 {
-        return None;
+        // Clamped B-Spline: each end gets a phantom point, its neighbour reflected
+        // through it, so the boundary (phantom + 4 * end + neighbour) / 6 lands on
+        // the end point
+        let n = points.len();
+        let point = |i: usize| match i {
+            0 => points[0] + (points[0] - points[1]),
+            i if i == n + 1 => points[n - 1] + (points[n - 1] - points[n - 2]),
+            i => points[i - 1],
+        };
+        [segment, segment + 1, segment + 2, segment + 3].map(point)
     }
 
-    let num_segments = if closed {
-        points.len()
-    } else {
-        points.len() - 3
-    };
-
-    if num_segments == 0 {
-        return None;
+    /// Evaluate the position on a single segment at its local parameter.
+    pub(crate) fn evaluate_segment<P: SplinePoint>(
+        &self,
+        points: &[P],
+        segment: usize,
+        local_t: f32,
+        closed: bool,
+    ) -> Option<P> {
+        if segment >= self.segment_count(points, closed) {
+            return None;
+        }
+        let segment_points = self.segment_points(points, segment, closed);
+        Some(combine(segment_points, self.weights(0, local_t)))
     }
 
-    let t_scaled = t * num_segments as f32;
-    let segment = (t_scaled.floor() as usize).min(num_segments - 1);
-    let local_t = t_scaled - segment as f32;
+    /// Evaluate position, first and second derivative with respect to the local
+    /// parameter of a single segment.
+    pub(crate) fn evaluate_segment_derivatives<P: SplinePoint>(
+        &self,
+        points: &[P],
+        segment: usize,
+        local_t: f32,
+        closed: bool,
+    ) -> Option<[P; 3]> {
+        if segment >= self.segment_count(points, closed) {
+            return None;
+        }
+        let segment_points = self.segment_points(points, segment, closed);
+        Some([0, 1, 2].map(|order| combine(segment_points, self.weights(order, local_t))))
+    }
 
-    let (p0, p1, p2, p3) = if closed {
-        let n = points.len();
-        (
-            points[(segment + n - 1) % n],
-            points[segment % n],
-            points[(segment + 1) % n],
-            points[(segment + 2) % n],
-        )
-    } else {
-        (
-            points[segment],
-            points[segment + 1],
-            points[segment + 2],
-            points[segment + 3],
-        )
-    };
-
-    Some(catmull_rom(p0, p1, p2, p3, local_t))
-}
+    /// Evaluate positions at many parameters, appending them to `out`.
+    ///
+    /// Gives the same results as calling [`SplineEvaluator::evaluate`] for each
+    /// parameter, but only gathers a segment's control points when the segment
+    /// changes from one parameter to the next. Parameters are deliberately streamed
+    /// rather than bucketed by segment; see
+    /// [`Spline::evaluate_many`](super::Spline::evaluate_many).
+    pub(crate) fn evaluate_many<P: SplinePoint>(
+        &self,
+        points: &[P],
+        ts: &[f32],
+        closed: bool,
+        out: &mut Vec<P>,
+    ) {
+        let num_segments = self.segment_count(points, closed);
+        if num_segments == 0 {
+            return;
+        }
 
-fn evaluate_catmull_rom_tangent(points: &[Vec3], t: f32, closed: bool) -> Option<Vec3> {
-    if points.len() < 4 {
-        return None;
+        out.reserve(ts.len());
+        let mut current: Option<(usize, [P; 4])> = None;
+        for &t in ts {
+            let (segment, local_t) = locate_segment(t, num_segments);
+            let segment_points = match current {
+                Some((index, segment_points)) if index == segment => segment_points,
+                _ => {
+                    let segment_points = self.segment_points(points, segment, closed);
+                    current = Some((segment, segment_points));
+                    segment_points
+                }
+            };
+            out.push(combine(segment_points, self.weights(0, local_t)));
+        }
     }
 
-    let num_segments = if closed {
-        points.len()
-    } else {
-        points.len() - 3
-    };
+    /// Weights of the four segment control points for the given derivative order
+    /// at local t, taking the shape parameters into account.
+    fn weights(&self, order: usize, t: f32) -> [f32; 4] {
+        match self.spline_type {
+            // Cardinal spline: Hermite segment with tangents s * (p2 - p0) and
+            // s * (p3 - p1), where s is the tension
+            SplineType::CatmullRom if self.tension != DEFAULT_CATMULL_ROM_TENSION => {
+                let [h00, h10, h01, h11] = hermite(order, t);
+                let s = self.tension;
+                return [-s * h10, h00 - s * h11, h01 + s * h10, s * h11];
+            }
+            SplineType::Tcb => {}
+            _ => return self.spline_type.basis(order, t),
+        }
 
-    if num_segments == 0 {
-        return None;
+        // Hermite segment from p1 to p2 with Kochanek–Bartels tangents:
+        //   outgoing at p1: a (p1 - p0) + b (p2 - p1)
+        //   incoming at p2: c (p2 - p1) + d (p3 - p2)
+        let TcbParams {
+            tension,
+            continuity,
+            bias,
+        } = self.tcb;
+        let scale = (1.0 - tension) * 0.5;
+        let a = scale * (1.0 + bias) * (1.0 + continuity);
+        let b = scale * (1.0 - bias) * (1.0 - continuity);
+        let c = scale * (1.0 + bias) * (1.0 - continuity);
+        let d = scale * (1.0 - bias) * (1.0 + continuity);
+
+        let [h00, h10, h01, h11] = hermite(order, t);
+        [
+            -a * h10,
+            h00 + (a - b) * h10 - c * h11,
+            h01 + b * h10 + (c - d) * h11,
+            d * h11,
+        ]
     }
+}
 
+/// Split parameter t into a segment index and the local t within that segment.
+fn locate_segment(t: f32, num_segments: usize) -> (usize, f32) {
     let t_scaled = t * num_segments as f32;
     let segment = (t_scaled.floor() as usize).min(num_segments - 1);
-    let local_t = t_scaled - segment as f32;
-
-    let (p0, p1, p2, p3) = if closed {
-        let n = points.len();
-        (
-            points[(segment + n - 1) % n],
-            points[segment % n],
-            points[(segment + 1) % n],
-            points[(segment + 2) % n],
-        )
-    } else {
-        (
-            points[segment],
-            points[segment + 1],
-            points[segment + 2],
-            points[segment + 3],
-        )
-    };
-
-    Some(catmull_rom_derivative(p0, p1, p2, p3, local_t))
+    (segment, t_scaled - segment as f32)
 }
 
-fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+/// Cubic Hermite basis functions `[h00, h10, h01, h11]` (start point, start
+/// tangent, end point, end tangent) for the given derivative order at t.
+fn hermite(order: usize, t: f32) -> [f32; 4] {
     let t2 = t * t;
     let t3 = t2 * t;
-
-    0.5 * ((2.0 * p1)
-        + (-p0 + p2) * t
-        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t2
-        + (-p0 + 3.0 * p1 - 3.0 * p2 + p3) * t3)
+    match order {
+        0 => [
+            2.0 * t3 - 3.0 * t2 + 1.0,
+            t3 - 2.0 * t2 + t,
+            -2.0 * t3 + 3.0 * t2,
+            t3 - t2,
+        ],
+        1 => [
+            6.0 * t2 - 6.0 * t,
+            3.0 * t2 - 4.0 * t + 1.0,
+            -6.0 * t2 + 6.0 * t,
+            3.0 * t2 - 2.0 * t,
+        ],
+        _ => [
+            12.0 * t - 6.0,
+            6.0 * t - 4.0,
+            -12.0 * t + 6.0,
+            6.0 * t - 2.0,
+        ],
+    }
 }
 
-fn catmull_rom_derivative(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
-    let t2 = t * t;
-
-    0.5 * ((-p0 + p2)
-        + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * 2.0 * t
-        + (-p0 + 3.0 * p1 - 3.0 * p2 + p3) * 3.0 * t2)
+/// Weighted sum of the four segment control points.
+fn combine<P: SplinePoint>([p0, p1, p2, p3]: [P; 4], [w0, w1, w2, w3]: [f32; 4]) -> P {
+    p0 * w0 + p1 * w1 + p2 * w2 + p3 * w3
 }
 
-// B-Spline implementation (uniform cubic)
-fn evaluate_bspline(points: &[Vec3], t: f32, closed: bool) -> Option<Vec3> {
-    if points.len() < 4 {
-        return None;
-    }
-
-    let num_segments = if closed {
-        points.len()
-    } else {
-        points.len() - 3
-    };
-
-    if num_segments == 0 {
-        return None;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_evaluate_pos_tan_matches_separate_calls() {
+        let points = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 2.0, 0.5),
+            Vec3::new(3.0, -1.0, 1.0),
+            Vec3::new(4.0, 0.5, -2.0),
+            Vec3::new(6.0, 1.0, 0.0),
+            Vec3::new(7.0, -2.0, 3.0),
+            Vec3::new(9.0, 0.0, 1.0),
+        ];
+
+        for spline_type in [
+            SplineType::CubicBezier,
+            SplineType::CatmullRom,
+            SplineType::BSpline,
+        ] {
+            for closed in [false, true] {
+                for i in 0..=100 {
+                    let t = i as f32 / 100.0;
+                    let (position, tangent) =
+                        spline_type.evaluate_pos_tan(&points, t, closed).unwrap();
+                    let expected_position = spline_type.evaluate(&points, t, closed).unwrap();
+                    let expected_tangent =
+                        spline_type.evaluate_tangent(&points, t, closed).unwrap();
+
+                    assert_eq!(position.to_array(), expected_position.to_array());
+                    assert_eq!(tangent.to_array(), expected_tangent.to_array());
+                }
+            }
+        }
     }
 
-    let t_scaled = t * num_segments as f32;
-    let segment = (t_scaled.floor() as usize).min(num_segments - 1);
-    let local_t = t_scaled - segment as f32;
-
-    let (p0, p1, p2, p3) = if closed {
-        let n = points.len();
-        (
-            points[segment % n],
-            points[(segment + 1) % n],
-            points[(segment + 2) % n],
-            points[(segment + 3) % n],
-        )
-    } else {
-        (
-            points[segment],
-            points[segment + 1],
-            points[segment + 2],
-            points[segment + 3],
-        )
-    };
-
-    Some(bspline(p0, p1, p2, p3, local_t))
-}
+    #[test]
+    fn test_tcb_defaults_match_catmull_rom() {
+        let points = vec![
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 2.0, 0.5),
+            Vec3::new(3.0, -1.0, 1.0),
+            Vec3::new(4.0, 0.5, -2.0),
+            Vec3::new(6.0, 1.0, 0.0),
+        ];
+        let tcb = SplineBasis::from(SplineType::Tcb);
+
+        for closed in [false, true] {
+            for i in 0..=50 {
+                let t = i as f32 / 50.0;
+                let (position, tangent) = tcb.evaluate_pos_tan(&points, t, closed).unwrap();
+                let (expected_position, expected_tangent) = SplineType::CatmullRom
+                    .evaluate_pos_tan(&points, t, closed)
+                    .unwrap();
+                assert!(position.distance(expected_position) < 1e-5);
+                assert!(tangent.distance(expected_tangent) < 1e-5);
+            }
+        }
 
-fn evaluate_bspline_tangent(points: &[Vec3], t: f32, closed: bool) -> Option<Vec3> {
-    if points.len() < 4 {
-        return None;
+        // Full tension zeroes the tangents at the points, so the curve still
+        // interpolates them but comes to a stop there
+        let tight = SplineBasis {
+            spline_type: SplineType::Tcb,
+            tcb: TcbParams::new(1.0, 0.0, 0.0),
+            ..default()
+        };
+        let t = 1.0 / 2.0;
+        assert!(
+            tight
+                .evaluate(&points, t, false)
+                .unwrap()
+                .distance(points[2])
+                < 1e-5
+        );
+        assert!(tight.evaluate_tangent(&points, t, false).unwrap().length() < 1e-5);
     }
 
-    let num_segments = if closed {
-        points.len()
-    } else {
-        points.len() - 3
-    };
-
-    if num_segments == 0 {
-        return None;
+    #[test]
+    fn test_tcb_params_reflect_round_trip() {
+        use bevy::reflect::ReflectFromReflect;
+
+        let mut app = App::new();
+        app.add_plugins(crate::spline::SplinePlugin);
+        let registry = app.world().resource::<AppTypeRegistry>().read();
+        let from_reflect = registry
+            .get_type_data::<ReflectFromReflect>(std::any::TypeId::of::<TcbParams>())
+            .expect("TcbParams should be registered");
+
+        let params = TcbParams::new(0.3, -0.5, 0.8);
+        let dynamic = params.to_dynamic();
+        let restored = from_reflect
+            .from_reflect(dynamic.as_partial_reflect())
+            .unwrap();
+        assert_eq!(restored.downcast_ref::<TcbParams>(), Some(&params));
     }
-
-    let t_scaled = t * num_segments as f32;
-    let segment = (t_scaled.floor() as usize).min(num_segments - 1);
-    let local_t = t_scaled - segment as f32;
-
-    let (p0, p1, p2, p3) = if closed {
-        let n = points.len();
-        (
-            points[segment % n],
-            points[(segment + 1) % n],
-            points[(segment + 2) % n],
-            points[(segment + 3) % n],
-        )
-    } else {
-        (
-            points[segment],
-            points[segment + 1],
-            points[segment + 2],
-            points[segment + 3],
-        )
-    };
-
-    Some(bspline_derivative(p0, p1, p2, p3, local_t))
-}
-
-fn bspline(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
-    let t2 = t * t;
-    let t3 = t2 * t;
-
-    (1.0 / 6.0)
-        * ((1.0 - 3.0 * t + 3.0 * t2 - t3) * p0
-            + (4.0 - 6.0 * t2 + 3.0 * t3) * p1
-            + (1.0 + 3.0 * t + 3.0 * t2 - 3.0 * t3) * p2
-            + t3 * p3)
-}
-
-fn bspline_derivative(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
-    let t2 = t * t;
-
-    (1.0 / 6.0)
-        * ((-3.0 + 6.0 * t - 3.0 * t2) * p0
-            + (-12.0 * t + 9.0 * t2) * p1
-            + (3.0 + 6.0 * t - 9.0 * t2) * p2
-            + 3.0 * t2 * p3)
 }
@@ -0,0 +1,198 @@
+//! Structural checks of spline control points.
+
+use std::fmt;
+
+use bevy::prelude::*;
+
+use super::{Spline, SplineType};
+
+/// A structural problem with the control points of a [`Spline`].
+///
+/// Returned by [`Spline::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplineError {
+    /// The spline has fewer control points than its type needs for one segment.
+    TooFewPoints {
+        /// The type of the spline.
+        spline_type: SplineType,
+        /// The number of control points the spline has.
+        count: usize,
+        /// The minimum number of control points for the type.
+        min: usize,
+    },
+    /// A cubic Bézier spline's control points don't divide into segments.
+    ///
+    /// Open splines need `3n + 1` points (anchor, two handles, anchor, ...).
+    /// Closed splines also accept `3n`, with the last two points being the
+    /// handles of the segment back to the first anchor.
+    BezierPointCount {
+        /// The number of control points the spline has.
+        count: usize,
+        /// Whether the spline is closed.
+        closed: bool,
+        /// The number of trailing points that the curve ignores.
+        unused: usize,
+    },
+}
+
+impl fmt::Display for SplineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooFewPoints {
+                spline_type,
+                count,
+                min,
+            } => write!(
+                f,
+                "{} spline needs at least {min} control points, found {count}",
+                spline_type.name()
+            ),
+            Self::BezierPointCount {
+                count,
+                closed,
+                unused,
+            } => {
+                let expected = if *closed { "3n + 1 or 3n" } else { "3n + 1" };
+                write!(
+                    f,
+                    "{} cubic Bézier spline needs {expected} control points, found {count}, \
+                     leaving {unused} unused",
+                    if *closed { "closed" } else { "open" }
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SplineError {}
+
+impl Spline {
+    /// Check that the number of control points fits the spline type.
+    ///
+    /// Every type needs [`SplineType::min_points`]; cubic Bézier splines also need
+    /// a count that divides into segments. Evaluation skips points that don't fit
+    /// into a segment, so a malformed count shows up as a curve that stops short.
+    pub fn validate(&self) -> Result<(), SplineError> {
+        let count = self.control_points.len();
+        let min = self.spline_type.min_points();
+        if count < min {
+            return Err(SplineError::TooFewPoints {
+                spline_type: self.spline_type,
+                count,
+                min,
+            });
+        }
+
+        if self.spline_type == SplineType::CubicBezier {
+            let unused = match (count - 1) % 3 {
+                0 => 0,
+                // Two points after the last anchor close the loop
+                2 if self.closed => 0,
+                extra => extra,
+            };
+            if unused > 0 {
+                return Err(SplineError::BezierPointCount {
+                    count,
+                    closed: self.closed,
+                    unused,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// System that warns once when a Bézier spline is created or edited into a
+/// malformed control point count.
+///
+/// Too few points aren't reported, as splines are often built up point by point.
+/// The warning is repeated if the spline is fixed and later malformed again.
+pub(crate) fn warn_malformed_splines(
+    splines: Query<(Entity, &Spline), Changed<Spline>>,
+    mut removed: RemovedComponents<Spline>,
+    mut warned: Local<std::collections::HashSet<Entity>>,
+) {
+    // Forget removed splines, so the set doesn't grow as splines come and go
+    for entity in removed.read() {
+        warned.remove(&entity);
+    }
+
+    for (entity, spline) in &splines {
+        match spline.validate() {
+            Err(error @ SplineError::BezierPointCount { .. }) => {
+                if warned.insert(entity) {
+                    warn!("Spline {entity:?}: {error}");
+                }
+            }
+            _ => {
+                warned.remove(&entity);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::SplineError;
+    use crate::spline::{Spline, SplineType};
+
+    #[test]
+    fn test_validate_checks_point_counts() {
+        let points = |count: usize| (0..count).map(|i| Vec3::X * i as f32).collect::<Vec<_>>();
+
+        // Open Bézier splines need 3n + 1 points
+        for count in [4, 7, 10] {
+            assert!(Spline::new(SplineType::CubicBezier, points(count))
+                .validate()
+                .is_ok());
+        }
+        assert_eq!(
+            Spline::new(SplineType::CubicBezier, points(8)).validate(),
+            Err(SplineError::BezierPointCount {
+                count: 8,
+                closed: false,
+                unused: 1
+            })
+        );
+        assert_eq!(
+            Spline::new(SplineType::CubicBezier, points(6)).validate(),
+            Err(SplineError::BezierPointCount {
+                count: 6,
+                closed: false,
+                unused: 2
+            })
+        );
+
+        // Closed ones also take two closing handles after the last anchor
+        assert!(Spline::closed(SplineType::CubicBezier, points(6))
+            .validate()
+            .is_ok());
+        assert!(Spline::closed(SplineType::CubicBezier, points(7))
+            .validate()
+            .is_ok());
+        let error = Spline::closed(SplineType::CubicBezier, points(8))
+            .validate()
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "closed cubic Bézier spline needs 3n + 1 or 3n control points, found 8, \
+             leaving 1 unused"
+        );
+
+        // Other types only need enough points for a segment
+        assert!(Spline::new(SplineType::CatmullRom, points(5))
+            .validate()
+            .is_ok());
+        assert_eq!(
+            Spline::new(SplineType::BSpline, points(3)).validate(),
+            Err(SplineError::TooFewPoints {
+                spline_type: SplineType::BSpline,
+                count: 3,
+                min: 4
+            })
+        );
+    }
+}
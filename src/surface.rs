@@ -1,32 +1,73 @@
 //! Surface projection for conforming spline-based geometry to terrain.
 //!
 //! This module provides components and utilities for projecting roads and
-//! distributed objects onto surfaces using raycasting via avian3d physics.
+//! distributed objects onto surfaces using raycasting via avian3d physics, with
+//! the default `avian3d` feature.
+//!
+//! With the `mesh_raycast` feature, roads and distributions are also projected
+//! without physics, by ray casting against [`ProjectionSurface`] meshes with
+//! Bevy's [`MeshRayCast`]. This backend is used whenever avian3d's
+//! `PhysicsPlugins` aren't added, and needs no physics dependency.
 
+#[cfg(feature = "avian3d")]
 use avian3d::prelude::*;
+#[cfg(feature = "mesh_raycast")]
+use bevy::picking::mesh_picking::ray_cast::{MeshRayCast, MeshRayCastSettings, RayCastVisibility};
 use bevy::prelude::*;
 
 /// Configuration for projecting geometry onto surfaces.
 ///
 /// Add this component to a `SplineRoad` or `SplineDistribution` entity
-/// to make it conform to terrain below.
+/// to make it conform to terrain below, or to any surface along
+/// [`project_direction`](Self::project_direction).
 #[derive(Component, Debug, Clone, Reflect)]
 #[reflect(Component, Default)]
 pub struct SplineMeshProjection {
     /// Whether surface projection is enabled.
     pub enabled: bool,
-    /// Offset above the spline point to start the raycast from.
-    /// Increase this if your spline passes through terrain.
+    /// Direction to cast projection rays in, toward the surface.
+    ///
+    /// Straight down by default. Point it at walls or ceilings, or along a custom
+    /// gravity, to project onto them; the opposite direction is treated as up.
+    pub project_direction: Dir3,
+    /// Offset against the projection direction (above the spline point, by default)
+    /// to start the raycast from. Increase this if your spline passes through terrain.
     pub ray_origin_offset: f32,
-    /// Maximum distance to cast the ray downward.
+    /// Maximum distance to cast the ray along the projection direction.
     pub max_distance: f32,
+    /// Radius of the disk around each projected point that
+    /// [`sample_count`](Self::sample_count) rays are spread over.
+    pub sample_radius: f32,
+    /// Number of rays to cast per projected point. Their hits are averaged into a
+    /// smoothed position and normal, ignoring rays that miss.
+    ///
+    /// One ray (the default) follows every bump in the surface. More rays over a
+    /// wider radius settle wide objects straddling a crease, at the cost of more
+    /// raycasts.
+    pub sample_count: usize,
     /// Offset along the surface normal to prevent z-fighting.
     /// Applied in the direction of the hit normal.
     pub normal_offset: f32,
+    /// Extra normal offset along the road (roads only), as `(t, offset)` keys sorted by `t`.
+    ///
+    /// `t` runs from 0 at the start of the road mesh to 1 at its end, following the
+    /// rows' V texture coordinates. Offsets are interpolated linearly between keys
+    /// and added to [`normal_offset`](Self::normal_offset), e.g. to lift a road out
+    /// of a valley for drainage or press it into a crest. Empty by default.
+    pub offset_curve: Vec<(f32, f32)>,
+    /// Number of rows on each side that a road's projection is averaged over (roads only).
+    ///
+    /// Larger windows give smoother roads that ride over small bumps but follow the
+    /// terrain less closely, floating over dips and cutting into crests; smaller
+    /// windows hug the terrain but show every bump. Long highways with many rows
+    /// suit larger windows than short roads. 0 disables smoothing, placing each row
+    /// exactly where its own ray hit.
+    pub smoothing_window: usize,
     /// Whether to align object rotation to surface normal (distribution only).
     pub align_to_normal: bool,
     /// Optional collision layers to query against.
     /// If None, all layers are queried.
+    #[cfg(feature = "avian3d")]
     #[reflect(ignore)]
     pub collision_layers: Option<LayerMask>,
 }
@@ -35,10 +76,16 @@ impl Default for SplineMeshProjection {
     fn default() -> Self {
         Self {
             enabled: true,
+            project_direction: Dir3::NEG_Y,
             ray_origin_offset: 10.0,
             max_distance: 100.0,
+            sample_radius: 0.0,
+            sample_count: 1,
             normal_offset: 0.1,
+            offset_curve: Vec::new(),
+            smoothing_window: 3,
             align_to_normal: false,
+            #[cfg(feature = "avian3d")]
             collision_layers: None,
         }
     }
@@ -50,6 +97,17 @@ impl SplineMeshProjection {
         Self::default()
     }
 
+    /// Set the direction to cast projection rays in.
+    pub fn with_project_direction(mut self, direction: Dir3) -> Self {
+        self.project_direction = direction;
+        self
+    }
+
+    /// Get the up direction of projected geometry, opposite to the projection direction.
+    pub fn up(&self) -> Vec3 {
+        -*self.project_direction
+    }
+
     /// Set the ray origin offset.
     pub fn with_ray_offset(mut self, offset: f32) -> Self {
         self.ray_origin_offset = offset;
@@ -62,12 +120,55 @@ impl SplineMeshProjection {
         self
     }
 
+    /// Average `count` rays spread over a disk of `radius` around each projected point.
+    pub fn with_sampling(mut self, radius: f32, count: usize) -> Self {
+        self.sample_radius = radius;
+        self.sample_count = count;
+        self
+    }
+
     /// Set the normal offset to prevent z-fighting.
     pub fn with_normal_offset(mut self, offset: f32) -> Self {
         self.normal_offset = offset;
         self
     }
 
+    /// Set the extra normal offset along the road, as `(t, offset)` keys sorted by `t`.
+    pub fn with_offset_curve(mut self, curve: Vec<(f32, f32)>) -> Self {
+        self.offset_curve = curve;
+        self
+    }
+
+    /// Get the extra normal offset from [`offset_curve`](Self::offset_curve) at `t`.
+    ///
+    /// Outside the keys, the first or last offset is held. Returns 0.0 for an empty curve.
+    pub fn offset_at(&self, t: f32) -> f32 {
+        let curve = &self.offset_curve;
+        let Some(&(first_t, first_offset)) = curve.first() else {
+            return 0.0;
+        };
+        if t <= first_t {
+            return first_offset;
+        }
+        for pair in curve.windows(2) {
+            let [(t0, offset0), (t1, offset1)] = [pair[0], pair[1]];
+            if t <= t1 {
+                let span = t1 - t0;
+                if span <= f32::EPSILON {
+                    return offset1;
+                }
+                return offset0 + (offset1 - offset0) * (t - t0) / span;
+            }
+        }
+        curve[curve.len() - 1].1
+    }
+
+    /// Set the number of rows on each side to smooth road projections over.
+    pub fn with_smoothing_window(mut self, window: usize) -> Self {
+        self.smoothing_window = window;
+        self
+    }
+
     /// Enable alignment to surface normal.
     pub fn with_normal_alignment(mut self, align: bool) -> Self {
         self.align_to_normal = align;
@@ -75,6 +176,7 @@ impl SplineMeshProjection {
     }
 
     /// Set collision layers to query.
+    #[cfg(feature = "avian3d")]
     pub fn with_layers(mut self, layers: LayerMask) -> Self {
         self.collision_layers = Some(layers);
         self
@@ -82,6 +184,7 @@ impl SplineMeshProjection {
 }
 
 /// Collision layers for surface projection.
+#[cfg(feature = "avian3d")]
 #[derive(PhysicsLayer, Clone, Copy, Debug, Default)]
 pub enum ProjectionLayer {
     /// Layer for terrain/surfaces that can be projected onto.
@@ -120,6 +223,7 @@ impl RawProjectionHit {
 }
 
 /// Create a spatial query filter from projection config.
+#[cfg(feature = "avian3d")]
 pub fn create_projection_filter(config: &SplineMeshProjection) -> SpatialQueryFilter {
     if let Some(layers) = config.collision_layers {
         SpatialQueryFilter::default().with_mask(layers)
@@ -132,6 +236,7 @@ pub fn create_projection_filter(config: &SplineMeshProjection) -> SpatialQueryFi
 ///
 /// Unlike `project_point`, this does not apply the normal offset,
 /// giving you access to the exact surface position and normal.
+#[cfg(feature = "avian3d")]
 pub fn cast_projection_ray(
     spatial_query: &SpatialQuery,
     point: Vec3,
@@ -141,24 +246,74 @@ pub fn cast_projection_ray(
         return None;
     }
 
-    let ray_origin = point + Vec3::Y * config.ray_origin_offset;
-    let ray_direction = Dir3::NEG_Y;
     let filter = create_projection_filter(config);
+    cast_projection_ray_with(point, config, |origin, direction, max_distance| {
+        let hit = spatial_query.cast_ray(origin, direction, max_distance, true, &filter)?;
+        Some((hit.distance, hit.normal))
+    })
+}
+
+/// Cast the projection ray for `point` with `cast_ray`, returning raw hit data.
+///
+/// `cast_ray` is given the ray origin, direction and maximum distance, and returns
+/// the distance to and surface normal of the closest hit. The ray starts
+/// [`ray_origin_offset`](SplineMeshProjection::ray_origin_offset) before `point`
+/// and points along [`project_direction`](SplineMeshProjection::project_direction).
+///
+/// With several [`sample_count`](SplineMeshProjection::sample_count) rays, the hits
+/// are averaged into a plane, and the hit is where the center ray meets it.
+pub(crate) fn cast_projection_ray_with(
+    point: Vec3,
+    config: &SplineMeshProjection,
+    mut cast_ray: impl FnMut(Vec3, Dir3, f32) -> Option<(f32, Vec3)>,
+) -> Option<RawProjectionHit> {
+    let ray_origin = point + config.up() * config.ray_origin_offset;
+    let ray_direction = config.project_direction;
 
-    let hit = spatial_query.cast_ray(
-        ray_origin,
-        ray_direction,
-        config.max_distance,
-        true,
-        &filter,
-    )?;
+    if config.sample_count <= 1 || config.sample_radius <= 0.0 {
+        let (distance, normal) = cast_ray(ray_origin, ray_direction, config.max_distance)?;
+        return Some(RawProjectionHit {
+            position: ray_origin + *ray_direction * distance,
+            normal,
+            distance,
+        });
+    }
 
-    let hit_position = ray_origin + *ray_direction * hit.distance;
+    // Spread the rays over the disk in a sunflower pattern, starting at its center
+    let (side, forward) = ray_direction.any_orthonormal_pair();
+    let golden_angle = std::f32::consts::PI * (3.0 - 5.0_f32.sqrt());
+    let mut hits = 0;
+    let mut position_sum = Vec3::ZERO;
+    let mut normal_sum = Vec3::ZERO;
+    for i in 0..config.sample_count {
+        let radius = config.sample_radius * (i as f32 / config.sample_count as f32).sqrt();
+        let (sin, cos) = (i as f32 * golden_angle).sin_cos();
+        let origin = ray_origin + (side * cos + forward * sin) * radius;
+        if let Some((distance, normal)) = cast_ray(origin, ray_direction, config.max_distance) {
+            hits += 1;
+            position_sum += origin + *ray_direction * distance;
+            normal_sum += normal;
+        }
+    }
+    if hits == 0 {
+        return None;
+    }
+
+    let average_position = position_sum / hits as f32;
+    let normal = normal_sum.try_normalize()?;
+    // Meet the averaged plane along the center ray, or take the average hit when
+    // the ray runs along it
+    let facing = ray_direction.dot(normal);
+    let distance = if facing.abs() > 1e-4 {
+        (average_position - ray_origin).dot(normal) / facing
+    } else {
+        (average_position - ray_origin).dot(*ray_direction)
+    };
 
     Some(RawProjectionHit {
-        position: hit_position,
-        normal: hit.normal,
-        distance: hit.distance,
+        position: ray_origin + *ray_direction * distance,
+        normal,
+        distance,
     })
 }
 
@@ -166,6 +321,7 @@ pub fn cast_projection_ray(
 ///
 /// Returns `None` if no surface is found within the max distance.
 /// The returned position includes the normal offset from the config.
+#[cfg(feature = "avian3d")]
 pub fn project_point(
     spatial_query: &SpatialQuery,
     point: Vec3,
@@ -180,6 +336,7 @@ pub fn project_point(
 }
 
 /// Project a point onto the surface, returning the original if no hit.
+#[cfg(feature = "avian3d")]
 pub fn project_point_or_original(
     spatial_query: &SpatialQuery,
     point: Vec3,
@@ -190,6 +347,54 @@ pub fn project_point_or_original(
         .unwrap_or(point)
 }
 
+/// Marks a mesh that roads and distributions are projected onto when ray casting
+/// against meshes instead of physics colliders.
+///
+/// Only used by the `mesh_raycast` backend; with avian3d physics, surfaces need a
+/// collider instead. The editor's projection preview and baking still need physics.
+#[cfg(feature = "mesh_raycast")]
+#[derive(Component, Debug, Clone, Copy, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct ProjectionSurface;
+
+/// Cast a projection ray against [`ProjectionSurface`] meshes and return raw hit data.
+///
+/// The mesh ray casting counterpart of [`cast_projection_ray`]. Collision layers
+/// don't apply, as only [`ProjectionSurface`] meshes are hit.
+#[cfg(feature = "mesh_raycast")]
+pub fn cast_projection_ray_mesh(
+    mesh_ray_cast: &mut MeshRayCast,
+    surfaces: &Query<(), With<ProjectionSurface>>,
+    point: Vec3,
+    config: &SplineMeshProjection,
+) -> Option<RawProjectionHit> {
+    if !config.enabled {
+        return None;
+    }
+
+    cast_projection_ray_with(point, config, mesh_ray_caster(mesh_ray_cast, surfaces))
+}
+
+/// Ray cast function for [`cast_projection_ray_with`] that hits [`ProjectionSurface`]
+/// meshes, regardless of their visibility.
+#[cfg(feature = "mesh_raycast")]
+pub(crate) fn mesh_ray_caster<'a, 'w, 's>(
+    mesh_ray_cast: &'a mut MeshRayCast<'w, 's>,
+    surfaces: &'a Query<(), With<ProjectionSurface>>,
+) -> impl FnMut(Vec3, Dir3, f32) -> Option<(f32, Vec3)> + use<'a, 'w, 's> {
+    move |origin, direction, max_distance| {
+        let filter = |entity| surfaces.contains(entity);
+        let settings = MeshRayCastSettings::default()
+            .with_visibility(RayCastVisibility::Any)
+            .with_filter(&filter);
+        let (_, hit) = mesh_ray_cast
+            .cast_ray(Ray3d::new(origin, direction), &settings)
+            .first()?;
+        // Normals of scaled meshes aren't normalized
+        (hit.distance <= max_distance).then(|| (hit.distance, hit.normal.normalize_or_zero()))
+    }
+}
+
 /// Plugin for surface projection functionality.
 ///
 /// This plugin registers the `SplineMeshProjection` component and integrates
@@ -200,5 +405,120 @@ pub struct SplineMeshProjectionPlugin;
 impl Plugin for SplineMeshProjectionPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<SplineMeshProjection>();
+        #[cfg(feature = "mesh_raycast")]
+        app.register_type::<ProjectionSurface>();
+    }
+}
+
+/// Ray cast against the plane through `point` facing `normal`, from its front side.
+#[cfg(test)]
+pub(crate) fn plane_cast(
+    point: Vec3,
+    normal: Vec3,
+) -> impl Fn(Vec3, Dir3, f32) -> Option<(f32, Vec3)> {
+    move |origin, direction, max_distance| {
+        let facing = -direction.dot(normal);
+        if facing <= 0.0 {
+            return None;
+        }
+        let distance = (origin - point).dot(normal) / facing;
+        (0.0..=max_distance)
+            .contains(&distance)
+            .then_some((distance, normal))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_averaged_projection_over_flat_surface() {
+        let ground = plane_cast(Vec3::ZERO, Vec3::Y);
+        let point = Vec3::new(2.0, 5.0, -1.0);
+
+        let single = SplineMeshProjection::new();
+        let center = cast_projection_ray_with(point, &single, &ground).unwrap();
+        assert!(center.position.distance(Vec3::new(2.0, 0.0, -1.0)) < 1e-5);
+
+        let averaged = SplineMeshProjection::new().with_sampling(1.5, 8);
+        let hit = cast_projection_ray_with(point, &averaged, &ground).unwrap();
+        assert!(hit.position.distance(center.position) < 1e-5, "{hit:?}");
+        assert!((hit.distance - center.distance).abs() < 1e-5);
+        assert!(hit.normal.distance(Vec3::Y) < 1e-5);
+    }
+
+    #[test]
+    fn test_averaged_projection_over_slope() {
+        // A slope rising along +X, so off-center rays hit at different heights
+        let normal = Vec3::new(-1.0, 2.0, 0.0).normalize();
+        let slope = plane_cast(Vec3::new(0.0, 1.0, 0.0), normal);
+        let point = Vec3::new(2.0, 5.0, -1.0);
+
+        let single = SplineMeshProjection::new();
+        let center = cast_projection_ray_with(point, &single, &slope).unwrap();
+        assert!(center.position.distance(Vec3::new(2.0, 2.0, -1.0)) < 1e-4);
+
+        let averaged = SplineMeshProjection::new().with_sampling(1.5, 8);
+        let hit = cast_projection_ray_with(point, &averaged, &slope).unwrap();
+        assert!(hit.position.distance(center.position) < 1e-4, "{hit:?}");
+        assert!((hit.distance - center.distance).abs() < 1e-4);
+        assert!(hit.normal.distance(normal) < 1e-4);
+
+        // Rays that miss are left out of the average
+        let half = |origin: Vec3, direction: Dir3, max_distance: f32| {
+            (origin.z > -1.0)
+                .then(|| slope(origin, direction, max_distance))
+                .flatten()
+        };
+        let hit = cast_projection_ray_with(point, &averaged, half).unwrap();
+        assert!(hit.position.distance(center.position) < 1e-4, "{hit:?}");
+    }
+
+    #[cfg(feature = "mesh_raycast")]
+    #[test]
+    fn test_mesh_raycast_projection_onto_plane() {
+        use bevy::camera::{
+            primitives::MeshAabb,
+            visibility::{InheritedVisibility, ViewVisibility},
+        };
+        use bevy::ecs::system::RunSystemOnce;
+
+        let mut world = World::new();
+        world.init_resource::<Assets<Mesh>>();
+        let mesh = Mesh::from(Plane3d::default().mesh().size(10.0, 10.0));
+        let aabb = mesh.compute_aabb().unwrap();
+        let handle = world.resource_mut::<Assets<Mesh>>().add(mesh);
+        let surface = (
+            Mesh3d(handle),
+            aabb,
+            InheritedVisibility::VISIBLE,
+            ViewVisibility::default(),
+        );
+        // A tilted surface, and one that isn't marked and must be ignored
+        let tilt = Quat::from_rotation_x(0.3);
+        world.spawn((
+            surface.clone(),
+            GlobalTransform::from(Transform::from_xyz(0.0, 1.0, 0.0).with_rotation(tilt)),
+            ProjectionSurface,
+        ));
+        world.spawn((surface, GlobalTransform::from_xyz(0.0, 3.0, 0.0)));
+
+        let hit = world
+            .run_system_once(
+                |mut mesh_ray_cast: MeshRayCast, surfaces: Query<(), With<ProjectionSurface>>| {
+                    let config = SplineMeshProjection::new();
+                    let point = Vec3::new(0.0, 5.0, 2.0);
+                    cast_projection_ray_mesh(&mut mesh_ray_cast, &surfaces, point, &config)
+                },
+            )
+            .unwrap()
+            .unwrap();
+
+        // Straight below the point on the tilted plane through (0, 1, 0)
+        let normal = tilt * Vec3::Y;
+        let expected = Vec3::new(0.0, 1.0 - 2.0 * normal.z / normal.y, 2.0);
+        assert!(hit.position.distance(expected) < 1e-4);
+        assert!(hit.normal.distance(normal) < 1e-4);
     }
 }